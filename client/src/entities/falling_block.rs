@@ -0,0 +1,90 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+use shared::{messages::FallingBlockUpdateEvent, CHUNK_SIZE};
+
+use crate::{
+    player::CurrentPlayerMarker,
+    world::{GlobalMaterial, MaterialResource, RenderDistance},
+};
+
+#[derive(Debug, Component)]
+pub struct FallingBlockMarker {
+    pub id: u128,
+}
+
+pub fn falling_block_update_system(
+    mut events: EventReader<FallingBlockUpdateEvent>,
+    mut commands: Commands,
+    mut falling_blocks: Query<
+        (Entity, &FallingBlockMarker, &mut Transform),
+        Without<CurrentPlayerMarker>,
+    >,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material_resource: Res<MaterialResource>,
+    distance: Res<RenderDistance>,
+    player_pos: Query<&Transform, With<CurrentPlayerMarker>>,
+) {
+    'ev_loop: for ev in events.read() {
+        let Some((block, pos)) = ev.data else {
+            for (entity, marker, _) in falling_blocks.iter() {
+                if marker.id == ev.id {
+                    commands.entity(entity).despawn_recursive();
+                    continue 'ev_loop;
+                }
+            }
+            continue;
+        };
+
+        for (_, marker, mut transform) in falling_blocks.iter_mut() {
+            if marker.id == ev.id {
+                transform.translation = pos;
+                continue 'ev_loop;
+            }
+        }
+
+        let mut mesh = Cuboid::from_size(Vec3::ONE).mesh().build();
+
+        let uv_attribute = mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0).unwrap();
+
+        let VertexAttributeValues::Float32x2(uv_attribute) = uv_attribute else {
+            panic!("Unexpected vertex format, expected Float32x2.");
+        };
+
+        // Every face uses the side texture of the block
+        if let Some(uv_coords) = material_resource
+            .blocks
+            .as_ref()
+            .unwrap()
+            .uvs
+            .get(&format!("{:?}", block.id))
+        {
+            for uv in uv_attribute.iter_mut() {
+                uv[0] = uv_coords.u0 + uv[0] * (uv_coords.u1 - uv_coords.u0);
+                uv[1] = uv_coords.v0 + uv[1] * (uv_coords.v1 - uv_coords.v0);
+            }
+        }
+
+        commands.spawn((
+            FallingBlockMarker { id: ev.id },
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(
+                material_resource
+                    .global_materials
+                    .get(&GlobalMaterial::Blocks)
+                    .unwrap()
+                    .clone_weak(),
+            ),
+            Transform::from_translation(pos),
+        ));
+    }
+
+    for (entity, _, transform) in falling_blocks.iter() {
+        if player_pos
+            .single()
+            .translation
+            .distance(transform.translation)
+            > distance.distance as f32 * CHUNK_SIZE as f32
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
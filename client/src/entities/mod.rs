@@ -1 +1,2 @@
+pub mod falling_block;
 pub mod stack;
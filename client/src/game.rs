@@ -1,19 +1,28 @@
 use std::collections::HashMap;
 
+use crate::entities::falling_block::falling_block_update_system;
 use crate::entities::stack::stack_update_system;
 use crate::mob::*;
 use crate::network::buffered_client::{CurrentFrameInputs, PlayerTickInputsBuffer, SyncTime};
 use crate::ui::hud::chat::{render_chat, setup_chat};
+use crate::ui::hud::health::{setup_health_bar, update_health_bar};
+use crate::ui::menus::death::{render_death_screen, setup_death_screen};
 use crate::ui::menus::{setup_server_connect_loading_screen, update_server_connect_loading_screen};
 use bevy::prelude::*;
 use bevy_atmosphere::prelude::*;
-use shared::messages::mob::MobUpdateEvent;
-use shared::messages::{ItemStackUpdateEvent, PlayerSpawnEvent, PlayerUpdateEvent};
+use light::light_system;
+use shared::crafting::RecipeRegistry;
+use shared::messages::mob::{MobDespawnEvent, MobHitEvent, MobUpdateEvent};
+use shared::messages::{
+    FallingBlockUpdateEvent, ItemStackUpdateEvent, PlayerGameModeUpdate, PlayerHealthUpdate,
+    PlayerSpawnEvent, PlayerUpdateEvent,
+};
 use shared::players::Inventory;
 use shared::TICKS_PER_SECOND;
 use time::time_update_system;
 
 use crate::world::time::ClientTime;
+use crate::world::weather::{precipitation_system, setup_precipitation, ClientWeather};
 use crate::world::ClientWorldMap;
 
 use crate::ui::hud::debug::BlockDebugWireframeSettings;
@@ -58,7 +67,8 @@ pub fn game_plugin(app: &mut App) {
         .add_plugins(bevy_simple_text_input::TextInputPlugin)
         .add_plugins(AtmospherePlugin)
         .insert_resource(WorldSeed(0))
-        .insert_resource(ClientTime(0))
+        .insert_resource(ClientTime::default())
+        .init_resource::<ClientWeather>()
         .insert_resource(FirstChunkReceived(false))
         .insert_resource(AmbientLight {
             color: Color::WHITE,
@@ -85,11 +95,14 @@ pub fn game_plugin(app: &mut App) {
         .insert_resource(ViewMode::FirstPerson)
         .insert_resource(DebugOptions::default())
         .insert_resource(Inventory::new())
+        .init_resource::<RecipeRegistry>()
         .init_resource::<CurrentPlayerProfile>()
         .init_resource::<ParticleAssets>()
         .init_resource::<FoxFeetTargets>()
         .init_resource::<Animations>()
         .init_resource::<TargetedMob>()
+        .init_resource::<HurtMobs>()
+        .init_resource::<OpenedContainer>()
         .init_resource::<PlayerTickInputsBuffer>()
         .init_resource::<CurrentFrameInputs>()
         .init_resource::<SyncTime>()
@@ -99,13 +112,19 @@ pub fn game_plugin(app: &mut App) {
         .add_event::<PlayerSpawnEvent>()
         .add_event::<PlayerUpdateEvent>()
         .add_event::<MobUpdateEvent>()
+        .add_event::<MobDespawnEvent>()
+        .add_event::<MobHitEvent>()
         .add_event::<ItemStackUpdateEvent>()
+        .add_event::<FallingBlockUpdateEvent>()
+        .add_event::<PlayerHealthUpdate>()
+        .add_event::<PlayerGameModeUpdate>()
         .add_systems(
             OnEnter(GameState::PreGameLoading),
             (
                 launch_local_server_system,
                 init_server_connection,
                 setup_materials,
+                load_recipes,
                 setup_server_connect_loading_screen,
                 spawn_camera,
             )
@@ -130,20 +149,25 @@ pub fn game_plugin(app: &mut App) {
                 setup_hud,
                 setup_chat,
                 setup_pause_menu,
+                setup_death_screen,
             )
                 .chain(),
         )
         .add_systems(
             OnEnter(GameState::Game),
-            (setup_hotbar, setup_inventory).chain(),
+            (setup_hotbar, setup_health_bar, setup_inventory).chain(),
         )
         .add_systems(OnEnter(GameState::Game), setup_chunk_ghost)
+        .add_systems(OnEnter(GameState::Game), setup_precipitation)
         .add_systems(
             Update,
             (
                 render_pause_menu,
+                render_death_screen,
                 render_chat,
                 render_inventory_hotbar,
+                render_container,
+                render_creative_items,
                 set_ui_mode,
             )
                 .run_if(in_state(GameState::Game)),
@@ -160,6 +184,7 @@ pub fn game_plugin(app: &mut App) {
                 (handle_block_interactions, camera_control_system).chain(),
                 fps_text_update_system,
                 coords_text_update_system,
+                light_text_update_system,
                 total_blocks_text_update_system,
                 block_text_update_system,
                 time_text_update_system,
@@ -180,13 +205,17 @@ pub fn game_plugin(app: &mut App) {
                 simulate_particles,
                 update_targetted_mob_color,
                 stack_update_system,
+                falling_block_update_system,
+                precipitation_system,
             )
                 .run_if(in_state(GameState::Game)),
         )
         .add_observer(observe_on_step)
         .add_systems(
             PostUpdate,
-            (world_render_system).run_if(in_state(GameState::Game)),
+            (light_system, world_render_system)
+                .chain()
+                .run_if(in_state(GameState::Game)),
         )
         .add_systems(
             Update,
@@ -194,7 +223,13 @@ pub fn game_plugin(app: &mut App) {
                 network_failure_handler,
                 spawn_players_system,
                 update_players_system,
-                spawn_mobs_system,
+                (
+                    update_player_health_system,
+                    update_player_game_mode_system,
+                    update_health_bar,
+                )
+                    .chain(),
+                (spawn_mobs_system, despawn_mobs_system, mob_hit_system).chain(),
                 player_labels_system,
             )
                 .run_if(in_state(GameState::Game)),
@@ -221,11 +256,13 @@ pub fn game_plugin(app: &mut App) {
         );
 }
 
-fn clear_resources(mut world_map: ResMut<ClientWorldMap>) {
+fn clear_resources(mut world_map: ResMut<ClientWorldMap>, mut weather: ResMut<ClientWeather>) {
     world_map.map = HashMap::new();
     world_map.total_blocks_count = 0;
     world_map.total_chunks_count = 0;
+    world_map.light_updates.clear();
     world_map.name = "".into();
+    *weather = ClientWeather::default();
 }
 
 fn check_pre_loading_complete(
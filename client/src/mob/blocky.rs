@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use super::{MobMarker, MobRoot};
+
+/// Box of a mob model, mobs face +Z
+pub struct ModelPart {
+    pub size: Vec3,
+    /// Centre of the box, relative to the feet of the mob
+    pub center: Vec3,
+    pub color: Color,
+}
+
+/// Spawns a mob drawn as colored boxes\
+/// The colors are stored in the meshes, so the material of the mob can be tinted when it is targeted
+pub fn spawn_blocky_mob(
+    (id, name): (u128, &str),
+    parts: &[ModelPart],
+    spawn_pos: Vec3,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+) -> Entity {
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        perceptual_roughness: 1.0,
+        ..default()
+    });
+
+    commands
+        .spawn((
+            Transform::from_translation(spawn_pos),
+            Visibility::default(),
+            MobRoot {
+                name: name.to_string(),
+                id,
+            },
+        ))
+        .with_children(|root| {
+            for part in parts.iter() {
+                let mut mesh = Mesh::from(Cuboid::from_size(part.size));
+                let color = part.color.to_linear().to_f32_array();
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![color; mesh.count_vertices()]);
+
+                root.spawn((
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_translation(part.center),
+                    MobMarker {
+                        name: name.to_string(),
+                        id,
+                    },
+                ));
+            }
+        })
+        .id()
+}
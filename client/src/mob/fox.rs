@@ -5,7 +5,7 @@ use std::time::Duration;
 use bevy::{animation::AnimationTargetId, color::palettes::css::WHITE, prelude::*};
 use rand::{thread_rng, Rng};
 
-use super::{MobMarker, MobRoot, TargetedMob};
+use super::{HurtMobs, MobMarker, MobRoot, TargetedMob};
 
 const FOX_PATH: &str = "models/animated/Fox.glb";
 
@@ -283,6 +283,7 @@ impl Default for FoxFeetTargets {
 pub fn update_targetted_mob_color(
     mut query: Query<(&mut MeshMaterial3d<StandardMaterial>, &MobMarker)>,
     targeted_mob: Res<TargetedMob>,
+    hurt_mobs: Res<HurtMobs>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let target_id = match &targeted_mob.target {
@@ -291,7 +292,11 @@ pub fn update_targetted_mob_color(
     };
 
     for (material, mob) in &mut query.iter_mut() {
-        if mob.id == target_id {
+        if hurt_mobs.is_hurt(mob.id) {
+            let handle = material.0.clone();
+            let material = materials.get_mut(&handle).unwrap();
+            material.base_color = Color::srgb(0.5, 0.0, 0.0);
+        } else if mob.id == target_id {
             let handle = material.0.clone();
             let material = materials.get_mut(&handle).unwrap();
             material.base_color = Color::srgb(1.0, 0.0, 0.0);
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use shared::messages::mob::MobHitEvent;
+use std::collections::HashMap;
+
+use super::MobRoot;
+
+/// How long a hit mob stays tinted, in seconds
+const HURT_DURATION: f32 = 0.3;
+
+/// Mobs recently hit, tinted until their timer finishes
+#[derive(Resource, Default)]
+pub struct HurtMobs {
+    timers: HashMap<u128, Timer>,
+}
+
+impl HurtMobs {
+    pub fn is_hurt(&self, id: u128) -> bool {
+        self.timers.contains_key(&id)
+    }
+}
+
+/// Shows the hits broadcast by the server, removing the mobs they killed
+pub fn mob_hit_system(
+    mut ev_hit: EventReader<MobHitEvent>,
+    mut hurt_mobs: ResMut<HurtMobs>,
+    mut commands: Commands,
+    mobs: Query<(Entity, &MobRoot)>,
+    time: Res<Time>,
+) {
+    hurt_mobs.timers.retain(|_, timer| {
+        timer.tick(time.delta());
+        !timer.finished()
+    });
+
+    for event in ev_hit.read() {
+        if event.health > 0 {
+            hurt_mobs.timers.insert(
+                event.id,
+                Timer::from_seconds(HURT_DURATION, TimerMode::Once),
+            );
+            continue;
+        }
+
+        hurt_mobs.timers.remove(&event.id);
+        for (entity, mob) in mobs.iter() {
+            if mob.id == event.id {
+                debug!("Mob {} died", event.id);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
@@ -1,10 +1,18 @@
 use bevy::prelude::*;
 
+mod blocky;
 mod fox;
+mod hurt;
+mod pig;
 mod spawn;
+mod zombie;
 
+pub use blocky::*;
 pub use fox::*;
+pub use hurt::*;
+pub use pig::*;
 pub use spawn::*;
+pub use zombie::*;
 
 #[derive(Debug, Component, Clone)]
 pub struct MobRoot {
@@ -26,6 +34,7 @@ pub struct TargetedMobData {
     #[allow(dead_code)]
     pub name: String,
     pub id: u128,
+    #[allow(dead_code)]
     pub entity: Entity,
 }
 
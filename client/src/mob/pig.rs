@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use super::{spawn_blocky_mob, ModelPart};
+
+const PINK: Color = Color::srgb(0.94, 0.65, 0.65);
+const SNOUT: Color = Color::srgb(0.85, 0.5, 0.52);
+
+const LEG_SIZE: Vec3 = Vec3::new(0.2, 0.3, 0.2);
+
+pub fn setup_pig(
+    id: u128,
+    spawn_pos: Vec3,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+) {
+    let mut parts = vec![
+        ModelPart {
+            size: Vec3::new(0.6, 0.5, 0.9),
+            center: Vec3::new(0.0, 0.55, 0.0),
+            color: PINK,
+        },
+        ModelPart {
+            size: Vec3::splat(0.5),
+            center: Vec3::new(0.0, 0.7, 0.6),
+            color: PINK,
+        },
+        ModelPart {
+            size: Vec3::new(0.25, 0.15, 0.06),
+            center: Vec3::new(0.0, 0.62, 0.88),
+            color: SNOUT,
+        },
+    ];
+    for (x, z) in [(-0.18, -0.3), (0.18, -0.3), (-0.18, 0.3), (0.18, 0.3)] {
+        parts.push(ModelPart {
+            size: LEG_SIZE,
+            center: Vec3::new(x, LEG_SIZE.y / 2.0, z),
+            color: PINK,
+        });
+    }
+
+    let pig = spawn_blocky_mob((id, "Pig"), &parts, spawn_pos, commands, meshes, materials);
+    info!("Spawned pig: {:?}", pig);
+}
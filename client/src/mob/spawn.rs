@@ -1,15 +1,18 @@
 use bevy::prelude::*;
-use shared::messages::mob::MobUpdateEvent;
+use shared::messages::mob::{MobDespawnEvent, MobUpdateEvent};
+use shared::world::MobKind;
 
-use crate::{mob::setup_fox, player::CurrentPlayerMarker, world::RenderDistance};
+use crate::{player::CurrentPlayerMarker, world::RenderDistance};
 
-use super::MobRoot;
+use super::{setup_fox, setup_pig, setup_zombie, MobRoot};
 
 pub fn spawn_mobs_system(
     mut ev_update: EventReader<MobUpdateEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
     mut mobs: Query<(Entity, &MobRoot, &mut Transform), Without<CurrentPlayerMarker>>,
     player_pos: Query<&Transform, With<CurrentPlayerMarker>>,
     render_distance: Res<RenderDistance>,
@@ -29,11 +32,17 @@ pub fn spawn_mobs_system(
             }
         }
 
-        if event.mob.kind == shared::world::MobKind::Fox
-            && event.mob.position.distance(player_pos) < render_distance.distance as f32 * 5.0
-        {
-            info!("Spawning fox at {:?}", position);
-            setup_fox(id, position, &mut commands, &asset_server, &mut graphs);
+        if event.mob.position.distance(player_pos) >= render_distance.distance as f32 * 5.0 {
+            continue;
+        }
+
+        info!("Spawning {:?} at {:?}", event.mob.kind, position);
+        match event.mob.kind {
+            MobKind::Fox => setup_fox(id, position, &mut commands, &asset_server, &mut graphs),
+            MobKind::Pig => setup_pig(id, position, &mut commands, &mut meshes, &mut materials),
+            MobKind::Zombie => {
+                setup_zombie(id, position, &mut commands, &mut meshes, &mut materials)
+            }
         }
     }
 
@@ -44,3 +53,18 @@ pub fn spawn_mobs_system(
         }
     }
 }
+
+pub fn despawn_mobs_system(
+    mut ev_despawn: EventReader<MobDespawnEvent>,
+    mut commands: Commands,
+    mobs: Query<(Entity, &MobRoot)>,
+) {
+    for event in ev_despawn.read() {
+        for (entity, mob) in mobs.iter() {
+            if mob.id == event.id {
+                debug!("Despawning mob {}", event.id);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
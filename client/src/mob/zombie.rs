@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use super::{spawn_blocky_mob, ModelPart};
+
+const SKIN: Color = Color::srgb(0.36, 0.58, 0.3);
+const SHIRT: Color = Color::srgb(0.1, 0.55, 0.58);
+const PANTS: Color = Color::srgb(0.22, 0.22, 0.55);
+
+pub fn setup_zombie(
+    id: u128,
+    spawn_pos: Vec3,
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+) {
+    let mut parts = vec![
+        ModelPart {
+            size: Vec3::new(0.5, 0.7, 0.25),
+            center: Vec3::new(0.0, 1.1, 0.0),
+            color: SHIRT,
+        },
+        ModelPart {
+            size: Vec3::splat(0.45),
+            center: Vec3::new(0.0, 1.675, 0.0),
+            color: SKIN,
+        },
+    ];
+    for side in [-1.0, 1.0] {
+        parts.push(ModelPart {
+            size: Vec3::new(0.25, 0.75, 0.25),
+            center: Vec3::new(side * 0.125, 0.375, 0.0),
+            color: PANTS,
+        });
+        // Arms stretched forward
+        parts.push(ModelPart {
+            size: Vec3::new(0.2, 0.2, 0.7),
+            center: Vec3::new(side * 0.35, 1.35, 0.3),
+            color: SKIN,
+        });
+    }
+
+    let zombie = spawn_blocky_mob(
+        (id, "Zombie"),
+        &parts,
+        spawn_pos,
+        commands,
+        meshes,
+        materials,
+    );
+    info!("Spawned zombie: {:?}", zombie);
+}
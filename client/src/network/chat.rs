@@ -1,23 +1,40 @@
 use bevy::prelude::*;
 use shared::messages::ChatConversation;
 
+/// Number of messages kept on the client
+const CACHED_CHAT_MAX_MESSAGES: usize = 100;
+
 #[derive(Resource, Default, Debug)]
 pub struct CachedChatConversation {
-    pub _last_update: u64,
+    pub last_update: u64,
     pub data: Option<ChatConversation>,
+    /// Messages received but not displayed yet, the last ones of `data`
+    pub unrendered: usize,
 }
 
-pub fn _update_cached_chat_state(
+/// Appends the messages received from the server to the cached conversation
+pub fn update_cached_chat_state(
     chat_state: &mut ResMut<CachedChatConversation>,
-    new_state: ChatConversation,
+    new_messages: ChatConversation,
 ) {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_millis() as u64;
 
-    chat_state._last_update = now;
-    chat_state.data = Some(new_state);
+    chat_state.last_update = now;
+    chat_state.unrendered += new_messages.messages.len();
+    let conversation = chat_state
+        .data
+        .get_or_insert_with(ChatConversation::default);
+    conversation.messages.extend(new_messages.messages);
+
+    let len = conversation.messages.len();
+    if len > CACHED_CHAT_MAX_MESSAGES {
+        conversation
+            .messages
+            .drain(..len - CACHED_CHAT_MAX_MESSAGES);
+    }
 
     trace!("new CachedChatConversation: {:?}", &chat_state);
 }
@@ -4,18 +4,21 @@ use bevy_renet::netcode::{
 };
 use bevy_renet::{renet::RenetClient, RenetClientPlugin};
 use rand::Rng;
-use shared::messages::mob::MobUpdateEvent;
+use shared::messages::mob::{MobDespawnEvent, MobHitEvent, MobUpdateEvent};
+use shared::players::Inventory;
 use shared::{get_shared_renet_config, GameServerConfig, STC_AUTH_CHANNEL};
 
 use crate::menus::solo::SelectedWorld;
 use crate::network::world::update_world_from_network;
 use crate::network::CachedChatConversation;
 use crate::world::time::ClientTime;
+use crate::world::weather::ClientWeather;
 use crate::world::WorldRenderRequestUpdateEvent;
 use crate::PlayerNameSupplied;
 use shared::messages::{
-    AuthRegisterRequest, ItemStackUpdateEvent, PlayerId, PlayerSpawnEvent, PlayerUpdateEvent,
-    ServerToClientMessage,
+    AuthRegisterRequest, FallingBlockUpdateEvent, ItemStackUpdateEvent, PlayerGameModeUpdate,
+    PlayerHealthUpdate, PlayerId, PlayerSpawnEvent, PlayerUpdateEvent, ServerToClientMessage,
+    TimeUpdate,
 };
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -116,6 +119,7 @@ pub fn launch_local_server_system(
         debug!("Obtained UDP socket: {}", addr);
 
         let world_name_clone = world_name.clone();
+        let game_mode = selected_world.game_mode;
         let game_folder_path = paths.clone().game_folder_path;
         //
         thread::spawn(move || {
@@ -124,6 +128,7 @@ pub fn launch_local_server_system(
                 GameServerConfig {
                     world_name: world_name_clone,
                     is_solo: true,
+                    new_world_game_mode: game_mode,
                 },
                 game_folder_path,
             );
@@ -137,24 +142,39 @@ pub fn launch_local_server_system(
 
 pub fn poll_network_messages(
     mut client: ResMut<RenetClient>,
-    // mut chat_state: ResMut<CachedChatConversation>,
-    // client_time: ResMut<ClientTime>,
+    mut chat_state: ResMut<CachedChatConversation>,
+    mut client_time: ResMut<ClientTime>,
+    mut client_weather: ResMut<ClientWeather>,
     mut world: ResMut<ClientWorldMap>,
+    mut inventory: ResMut<Inventory>,
     mut ev_render: EventWriter<WorldRenderRequestUpdateEvent>,
     mut ev_player_spawn: EventWriter<PlayerSpawnEvent>,
     mut ev_mob_update: EventWriter<MobUpdateEvent>,
+    mut ev_mob_despawn: EventWriter<MobDespawnEvent>,
+    mut ev_mob_hit: EventWriter<MobHitEvent>,
     mut ev_item_stacks_update: EventWriter<ItemStackUpdateEvent>,
+    mut ev_falling_blocks_update: EventWriter<FallingBlockUpdateEvent>,
     mut ev_player_update: EventWriter<PlayerUpdateEvent>,
+    mut ev_player_health: EventWriter<PlayerHealthUpdate>,
+    mut ev_player_game_mode: EventWriter<PlayerGameModeUpdate>,
 ) {
-    // poll_reliable_ordered_messages(&mut client, &mut chat_state);
     update_world_from_network(
         &mut client,
         &mut world,
+        &mut chat_state,
+        &mut inventory,
         &mut ev_render,
         &mut ev_player_spawn,
         &mut ev_mob_update,
+        &mut ev_mob_despawn,
+        &mut ev_mob_hit,
         &mut ev_item_stacks_update,
+        &mut ev_falling_blocks_update,
         &mut ev_player_update,
+        &mut ev_player_health,
+        &mut ev_player_game_mode,
+        &mut client_time,
+        &mut client_weather,
     );
 }
 
@@ -241,7 +261,10 @@ pub fn establish_authenticated_connection_to_server(
                 target.username = Some(message.username);
                 target.session_token = Some(message.session_token);
                 target.state = TargetServerState::ConnectionEstablished;
-                client_time.0 = message.tick;
+                client_time.apply_update(TimeUpdate {
+                    time: message.tick,
+                    day_length: message.day_length,
+                });
                 // TODO: handle clock sync using the timestamp_ms field
                 // it will become very important if the lantency is high
                 for player in message.players {
@@ -1,10 +1,16 @@
+use crate::network::{update_cached_chat_state, CachedChatConversation};
+use crate::world::time::ClientTime;
+use crate::world::weather::ClientWeather;
 use crate::world::ClientChunk;
 use bevy::prelude::*;
 use bevy_renet::renet::RenetClient;
 use shared::messages::{
-    mob::MobUpdateEvent, ItemStackUpdateEvent, PlayerSpawnEvent, PlayerUpdateEvent,
-    ServerToClientMessage,
+    mob::{MobDespawnEvent, MobHitEvent, MobUpdateEvent},
+    FallingBlockUpdateEvent, ItemStackUpdateEvent, PlayerGameModeUpdate, PlayerHealthUpdate,
+    PlayerSpawnEvent, PlayerUpdateEvent, ServerToClientMessage,
 };
+use shared::players::Inventory;
+use shared::world::{to_global_pos, ChunkLight, WorldMap};
 use shared::STC_AUTH_CHANNEL;
 
 use crate::world::ClientWorldMap;
@@ -16,11 +22,20 @@ use super::SendGameMessageExtension;
 pub fn update_world_from_network(
     client: &mut ResMut<RenetClient>,
     world: &mut ResMut<ClientWorldMap>,
+    chat_state: &mut ResMut<CachedChatConversation>,
+    inventory: &mut ResMut<Inventory>,
     ev_render: &mut EventWriter<WorldRenderRequestUpdateEvent>,
     ev_player_spawn: &mut EventWriter<PlayerSpawnEvent>,
     ev_mob_update: &mut EventWriter<MobUpdateEvent>,
+    ev_mob_despawn: &mut EventWriter<MobDespawnEvent>,
+    ev_mob_hit: &mut EventWriter<MobHitEvent>,
     ev_item_stacks_update: &mut EventWriter<ItemStackUpdateEvent>,
+    ev_falling_blocks_update: &mut EventWriter<FallingBlockUpdateEvent>,
     ev_player_update: &mut EventWriter<PlayerUpdateEvent>,
+    ev_player_health: &mut EventWriter<PlayerHealthUpdate>,
+    ev_player_game_mode: &mut EventWriter<PlayerGameModeUpdate>,
+    client_time: &mut ResMut<ClientTime>,
+    client_weather: &mut ResMut<ClientWeather>,
 ) {
     while let Some(Ok(msg)) = client.receive_game_message_except_channel(STC_AUTH_CHANNEL) {
         // truncate the message to 1000 characters
@@ -34,8 +49,28 @@ pub fn update_world_from_network(
                 );
 
                 for (pos, chunk) in world_update.new_map {
+                    let mut light = ChunkLight::default();
+                    if let Some(previous) = world.map.get(&pos) {
+                        // Only the light around the blocks that changed has to be updated
+                        if previous.light.is_lit {
+                            light = previous.light.clone();
+                            let changed: Vec<IVec3> = previous
+                                .map
+                                .keys()
+                                .chain(chunk.map.keys())
+                                .filter(|local_pos| {
+                                    previous.map.get(*local_pos).map(|block| block.id)
+                                        != chunk.map.get(*local_pos).map(|block| block.id)
+                                })
+                                .map(|local_pos| to_global_pos(&pos, local_pos))
+                                .collect();
+                            world.light_updates.extend(changed);
+                        }
+                    }
+
                     let chunk = ClientChunk {
                         map: chunk.map,
+                        block_entities: chunk.block_entities,
                         entity: {
                             if let Some(c) = world.map.get(&pos) {
                                 c.entity
@@ -43,21 +78,15 @@ pub fn update_world_from_network(
                                 None
                             }
                         },
+                        light,
                     };
 
                     world.map.insert(pos, chunk.clone());
                     ev_render.send(WorldRenderRequestUpdateEvent::ChunkToReload(pos));
                 }
 
-                for (id, mob) in world_update.mobs {
-                    debug!("ServerMob received: {:?}", mob);
-                    ev_mob_update.send(MobUpdateEvent { id, mob });
-                }
-
                 ev_item_stacks_update.send_batch(world_update.item_stacks);
-
-                // get current time
-                // client_time.0 = world_update.time;
+                ev_falling_blocks_update.send_batch(world_update.falling_blocks);
             }
             ServerToClientMessage::PlayerSpawn(spawn_event) => {
                 info!("Received SINGLE spawn event {:?}", spawn_event);
@@ -67,11 +96,64 @@ pub fn update_world_from_network(
                 info!("Received mob update event {:?}", update_event);
                 ev_mob_update.send(update_event);
             }
+            ServerToClientMessage::MobDespawn(despawn_event) => {
+                debug!("Received mob despawn event {:?}", despawn_event);
+                ev_mob_despawn.send(despawn_event);
+            }
+            ServerToClientMessage::MobHit(hit_event) => {
+                debug!("Received mob hit event {:?}", hit_event);
+                ev_mob_hit.send(hit_event);
+            }
             ServerToClientMessage::PlayerUpdate(update) => {
                 ev_player_update.send(update);
             }
+            ServerToClientMessage::BlockUpdates(updates) => {
+                debug!("Received {} block updates", updates.len());
+                for update in updates {
+                    // Updates of a block entity alone do not need a new mesh
+                    let changed =
+                        world.get_block_by_coordinates(&update.position).copied() != update.block;
+
+                    match update.block {
+                        Some(block) => world.set_block(&update.position, block),
+                        None => {
+                            world.remove_block_by_coordinates(&update.position);
+                        }
+                    }
+                    world.set_block_entity(&update.position, update.block_entity);
+
+                    if changed {
+                        ev_render.send(WorldRenderRequestUpdateEvent::BlockToReload(
+                            update.position,
+                        ));
+                    }
+                }
+            }
             ServerToClientMessage::AuthRegisterResponse(_) => {}
-            ServerToClientMessage::ChatConversation(_) => {}
+            ServerToClientMessage::ChatConversation(conversation) => {
+                debug!("Received {} chat messages", conversation.messages.len());
+                update_cached_chat_state(chat_state, conversation);
+            }
+            ServerToClientMessage::InventoryUpdate(update) => {
+                debug!("Received inventory update");
+                inventory.apply_update(update);
+            }
+            ServerToClientMessage::PlayerHealth(update) => {
+                debug!("Received health update {:?}", update);
+                ev_player_health.send(update);
+            }
+            ServerToClientMessage::PlayerGameMode(update) => {
+                info!("Game mode changed to {}", update.game_mode);
+                ev_player_game_mode.send(update);
+            }
+            ServerToClientMessage::TimeUpdate(update) => {
+                trace!("Received time update {:?}", update);
+                client_time.apply_update(update);
+            }
+            ServerToClientMessage::WeatherUpdate(update) => {
+                debug!("Received weather update {:?}", update);
+                client_weather.apply_update(update);
+            }
         }
     }
 }
@@ -2,19 +2,30 @@ use crate::constants::{CUBE_SIZE, INTERACTION_DISTANCE};
 use crate::mob::{MobMarker, TargetedMob, TargetedMobData};
 use crate::network::SendGameMessageExtension;
 use crate::ui::hud::hotbar::Hotbar;
-use crate::ui::hud::UIMode;
-use crate::world::{raycast, ClientWorldMap};
+use crate::ui::hud::{InventoryRoot, OpenedContainer, UIMode};
+use crate::world::{raycast, ClientWorldMap, FaceDirection};
 use crate::world::{FaceDirectionExt, WorldRenderRequestUpdateEvent};
 use bevy::color::palettes::css::{self, WHITE};
 use bevy::math::NormedVectorSpace;
 use bevy::prelude::*;
 use bevy_renet::renet::RenetClient;
 use shared::messages::ClientToServerMessage;
-use shared::players::{Inventory, Player};
-use shared::world::{BlockData, ItemStack, ItemType, WorldMap};
+use shared::players::{Inventory, InventoryAction, Player};
+use shared::world::{
+    is_supported, toggle_door, BlockData, BlockDirection, BlockShape, ItemId, ItemType, WorldMap,
+    DOOR_UPPER, TORCH_ON_WALL,
+};
+use shared::TICKS_PER_SECOND;
 
 use super::{CurrentPlayerMarker, ViewMode};
 
+/// Block the player is breaking, and the time spent on it not yet turned into breaking steps
+#[derive(Default)]
+pub struct BreakingState {
+    position: Option<IVec3>,
+    elapsed: f32,
+}
+
 // Function to handle block placement and breaking
 pub fn handle_block_interactions(
     queries: (
@@ -23,6 +34,7 @@ pub fn handle_block_interactions(
         Query<&Transform, (With<Camera>, Without<CurrentPlayerMarker>)>,
         Query<&Hotbar>,
         Query<&MobMarker>,
+        Query<&mut Visibility, With<InventoryRoot>>,
     ),
     resources: (
         ResMut<ClientWorldMap>,
@@ -32,13 +44,15 @@ pub fn handle_block_interactions(
         ResMut<RenetClient>,
         Res<ViewMode>,
         ResMut<TargetedMob>,
+        ResMut<OpenedContainer>,
     ),
+    (mut breaking, time): (Local<BreakingState>, Res<Time>),
     mut ev_render: EventWriter<WorldRenderRequestUpdateEvent>,
     mut ray_cast: MeshRayCast,
-    mut commands: Commands,
     mut gizmos: Gizmos,
 ) {
-    let (player_query, mut p_transform, camera_query, hotbar, mob_query) = queries;
+    let (player_query, mut p_transform, camera_query, hotbar, mob_query, mut inventory_root) =
+        queries;
     let (
         mut world_map,
         mouse_input,
@@ -47,11 +61,12 @@ pub fn handle_block_interactions(
         mut client,
         view_mode,
         mut targeted_mob,
+        mut opened_container,
     ) = resources;
 
     let player = player_query.single().clone();
 
-    if *ui_mode == UIMode::Opened {
+    if *ui_mode == UIMode::Opened || player.is_dead() || !player.game_mode.can_interact() {
         return;
     }
 
@@ -64,9 +79,12 @@ pub fn handle_block_interactions(
 
     bounce_ray(ray, &mut ray_cast);
 
-    if let Some((entity, _)) = ray_cast.cast_ray(ray, &RayCastSettings::default()).first() {
-        let mob = mob_query.get(*entity);
-        if let Ok(mob) = mob {
+    if let Some((entity, hit)) = ray_cast.cast_ray(ray, &RayCastSettings::default()).first() {
+        let mob = mob_query
+            .get(*entity)
+            .ok()
+            .filter(|_| hit.distance <= INTERACTION_DISTANCE);
+        if let Some(mob) = mob {
             targeted_mob.target = Some(TargetedMobData {
                 entity: *entity,
                 id: mob.id,
@@ -79,16 +97,12 @@ pub fn handle_block_interactions(
         targeted_mob.target = None;
     }
 
-    if mouse_input.just_pressed(MouseButton::Left) && targeted_mob.target.is_some() {
-        // TODO: Attack the targeted
-
-        let target = targeted_mob.target.as_ref().unwrap();
-
-        commands.entity(target.entity).despawn_recursive();
-
-        targeted_mob.target = None;
-
-        return;
+    if mouse_input.just_pressed(MouseButton::Left) {
+        if let Some(target) = targeted_mob.target.as_ref() {
+            // The server checks the reach and cooldown, and tells every client about the hit
+            client.send_game_message(ClientToServerMessage::AttackMob { id: target.id });
+            return;
+        }
     }
 
     if let Some(res) = maybe_block {
@@ -100,35 +114,111 @@ pub fn handle_block_interactions(
             WHITE,
         );
 
+        // Creative players break one block per click, instead of holding the button
+        let instant_break = player.game_mode.breaks_instantly();
+        let is_breaking = if instant_break {
+            mouse_input.just_pressed(MouseButton::Left)
+        } else {
+            mouse_input.pressed(MouseButton::Left)
+        };
+
         // Handle left-click for breaking blocks
-        if mouse_input.pressed(MouseButton::Left) {
+        if is_breaking {
             let pos = res.position;
             let block_pos = Vec3::new(pos.x as f32, pos.y as f32, pos.z as f32);
             // Check if block is close enough to the player
             if (block_pos - p_transform.single_mut().translation).norm() < INTERACTION_DISTANCE {
-                // Remove the hit block
-                let res = world_map.try_to_break_block(&pos);
+                // Breaking advances once per tick, the server checks the block took long enough
+                let steps = if instant_break {
+                    1
+                } else {
+                    if breaking.position != Some(pos) {
+                        *breaking = BreakingState {
+                            position: Some(pos),
+                            elapsed: 0.0,
+                        };
+                        client.send_game_message(ClientToServerMessage::StartBreaking {
+                            position: pos,
+                        });
+                    }
+                    breaking.elapsed += time.delta_secs();
+                    let steps = (breaking.elapsed * TICKS_PER_SECOND as f32) as u32;
+                    breaking.elapsed -= steps as f32 / TICKS_PER_SECOND as f32;
+                    steps
+                };
 
-                if let Some((block, destroyed)) = res {
+                // Remove the hit block
+                let tool = inventory
+                    .inner
+                    .get(&hotbar.single().selected)
+                    .map(|stack| stack.item_id);
+                for _ in 0..steps {
+                    let Some((_, destroyed)) =
+                        world_map.try_to_break_block(&pos, tool, instant_break)
+                    else {
+                        break;
+                    };
                     ev_render.send(WorldRenderRequestUpdateEvent::BlockToReload(pos));
 
                     if destroyed {
-                        // If block has corresponding item, add it to inventory
-                        for (item_id, nb) in block.id.get_drops(1) {
-                            inventory.add_item_to_inventory(ItemStack {
-                                item_id,
-                                item_type: item_id.get_default_type(),
-                                nb,
-                            });
-                        }
-
+                        // Drops are added to the inventory by the server
                         client.send_game_message(ClientToServerMessage::BlockInteraction {
                             position: pos,
                             block_type: None,
+                            hotbar_slot: hotbar.single().selected,
                         });
+                        *breaking = BreakingState::default();
+                        break;
                     }
                 }
             }
+        } else {
+            *breaking = BreakingState::default();
+        }
+
+        // Handle middle-click for picking blocks, creative players can get any block this way
+        if mouse_input.just_pressed(MouseButton::Middle) && player.game_mode.has_unlimited_blocks()
+        {
+            let item_id = world_map
+                .get_block_by_coordinates(&res.position)
+                .and_then(|block| ItemId::from_block(block.id));
+
+            if let Some(item_id) = item_id {
+                let action = InventoryAction::PickItem {
+                    slot: hotbar.single().selected,
+                    item_id,
+                };
+                inventory.apply_action(action);
+                client.send_game_message(ClientToServerMessage::InventoryAction(action));
+            }
+        }
+
+        // Right-clicking a door opens or closes it, instead of placing a block
+        if mouse_input.just_pressed(MouseButton::Right)
+            && res.block.id.get_shape() == BlockShape::Door
+        {
+            let block_pos = res.position.as_vec3();
+            if (block_pos - p_transform.single().translation).norm() <= INTERACTION_DISTANCE {
+                for pos in toggle_door(world_map.as_mut(), &res.position) {
+                    ev_render.send(WorldRenderRequestUpdateEvent::BlockToReload(pos));
+                }
+                client.send_game_message(ClientToServerMessage::UseBlock {
+                    position: res.position,
+                });
+            }
+            return;
+        }
+
+        // Right-clicking a container opens it in the inventory, instead of placing a block
+        if mouse_input.just_pressed(MouseButton::Right)
+            && world_map.get_block_entity(&res.position).is_some()
+        {
+            let block_pos = res.position.as_vec3();
+            if (block_pos - p_transform.single().translation).norm() <= INTERACTION_DISTANCE {
+                opened_container.position = Some(res.position);
+                *inventory_root.single_mut() = Visibility::Visible;
+            }
+            return;
         }
 
         // Handle right-click for placing blocks
@@ -163,9 +253,8 @@ pub fn handle_block_interactions(
                 && (distance.x> (CUBE_SIZE + player.width) / 2. || distance.z > (CUBE_SIZE + player.width ) / 2. || distance.y > (CUBE_SIZE + player.height) / 2.)
             {
                 // Try to get item currently selected in player hotbar
-                if let Some(&item) = inventory.inner.get(&hotbar.single().selected) {
-                    inventory.remove_item_from_stack(hotbar.single().selected, 1);
-
+                let hotbar_slot = hotbar.single().selected;
+                if let Some(&item) = inventory.inner.get(&hotbar_slot) {
                     // Check if the item has a block counterpart
                     if let ItemType::Block(block_id) = item.item_type {
                         let block_pos = IVec3::new(
@@ -173,16 +262,65 @@ pub fn handle_block_interactions(
                             block_to_create_pos.y as i32,
                             block_to_create_pos.z as i32,
                         );
-                        let block =
-                            BlockData::new(block_id, false, shared::world::BlockDirection::Front);
 
-                        world_map.set_block(&block_pos, block);
+                        // Blocks face the player, slabs and stairs placed against the underside
+                        // or the upper half of a block are upside down
+                        let direction = BlockDirection::facing(*camera_transform.forward());
+                        let flipped = block_id.get_shape().can_be_flipped()
+                            && match face_dir {
+                                FaceDirection::MinusY => true,
+                                FaceDirection::PlusY => false,
+                                _ => res.point.y > collision_pos.y as f32,
+                            };
+                        let mut block = BlockData::new(block_id, flipped, direction);
+
+                        // Torches hang on the side they are placed against, they cannot hang from a ceiling
+                        if block_id.get_shape() == BlockShape::Torch {
+                            match face_dir {
+                                FaceDirection::MinusY => return,
+                                FaceDirection::PlusY => {}
+                                _ => {
+                                    block.state = TORCH_ON_WALL;
+                                    block.direction = BlockDirection::facing(face.as_vec3());
+                                }
+                            }
+                            if !is_supported(world_map.as_ref(), &block_pos, &block) {
+                                return;
+                            }
+                        }
+
+                        // Doors need room for their upper half
+                        let door_top = (block_id.get_shape() == BlockShape::Door)
+                            .then_some(block_pos + IVec3::Y);
+                        if door_top
+                            .is_some_and(|top| world_map.get_block_by_coordinates(&top).is_some())
+                        {
+                            return;
+                        }
 
+                        // Predicted locally, the server sends the authoritative inventory back
+                        if !player.game_mode.has_unlimited_blocks() {
+                            inventory.remove_item_from_stack(hotbar_slot, 1);
+                        }
+
+                        world_map.set_block(&block_pos, block);
                         ev_render.send(WorldRenderRequestUpdateEvent::BlockToReload(block_pos));
 
+                        if let Some(top) = door_top {
+                            world_map.set_block(
+                                &top,
+                                BlockData {
+                                    state: DOOR_UPPER,
+                                    ..block
+                                },
+                            );
+                            ev_render.send(WorldRenderRequestUpdateEvent::BlockToReload(top));
+                        }
+
                         client.send_game_message(ClientToServerMessage::BlockInteraction {
                             position: block_pos,
                             block_type: Some(block),
+                            hotbar_slot,
                         });
                     }
                 }
@@ -8,7 +8,7 @@ use crate::{
 use bevy::color::palettes::css::ORANGE;
 use bevy::prelude::*;
 use shared::{
-    messages::{PlayerSpawnEvent, PlayerUpdateEvent},
+    messages::{PlayerGameModeUpdate, PlayerHealthUpdate, PlayerSpawnEvent, PlayerUpdateEvent},
     players::{movement::simulate_player_movement, Player},
 };
 
@@ -225,3 +225,28 @@ pub fn update_players_system(
         }
     }
 }
+
+pub fn update_player_health_system(
+    mut players: Query<&mut Player, With<CurrentPlayerMarker>>,
+    mut ev_health: EventReader<PlayerHealthUpdate>,
+) {
+    for event in ev_health.read() {
+        for mut player in players.iter_mut() {
+            if let Some(source) = event.source {
+                debug!("Took damage from {:?}", source);
+            }
+            player.health = event.health;
+        }
+    }
+}
+
+pub fn update_player_game_mode_system(
+    mut players: Query<&mut Player, With<CurrentPlayerMarker>>,
+    mut ev_game_mode: EventReader<PlayerGameModeUpdate>,
+) {
+    for event in ev_game_mode.read() {
+        for mut player in players.iter_mut() {
+            player.set_game_mode(event.game_mode);
+        }
+    }
+}
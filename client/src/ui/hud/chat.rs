@@ -113,7 +113,7 @@ pub fn setup_chat(
 
 pub fn render_chat(
     resources: (
-        Res<CachedChatConversation>,
+        ResMut<CachedChatConversation>,
         Res<AssetServer>,
         ResMut<RenetClient>,
         Res<ButtonInput<KeyCode>>,
@@ -134,12 +134,11 @@ pub fn render_chat(
             Without<ChatRoot>,
         >,
     ),
-    mut last_render_ts: Local<u64>,
     mut event: EventReader<TextInputSubmitEvent>,
     mut commands: Commands,
     _paths: Res<GameFolderPaths>,
 ) {
-    let (cached_conv, asset_server, mut client, keyboard_input, key_map, ui_mode) = resources;
+    let (mut cached_conv, asset_server, mut client, keyboard_input, key_map, ui_mode) = resources;
     let (mut text_query, mut visibility_query, parent_query, mut animation_query) = queries;
     let (entity_check, mut inactive, mut value) = text_query.single_mut();
 
@@ -190,13 +189,11 @@ pub fn render_chat(
         }
     }
 
+    // Counting the new messages, as several of them can share the same timestamp
+    let unrendered = std::mem::take(&mut cached_conv.unrendered);
     if let Some(conv) = &cached_conv.data {
-        for message in &conv.messages {
-            // If message too old, don't render
-            if message.timestamp <= *last_render_ts {
-                continue;
-            }
-            *last_render_ts = message.timestamp;
+        let rendered = conv.messages.len().saturating_sub(unrendered);
+        for message in conv.messages.iter().skip(rendered) {
             let msg = commands
                 .spawn((
                     MessageAnimator {
@@ -222,9 +219,9 @@ pub fn render_chat(
         }
         // Prevents too much messages from building up on screen
         if children.len() > CHAT_MAX_MESSAGES {
-            for i in children.len()..CHAT_MAX_MESSAGES {
-                commands.entity(parent).remove_children(&[children[i]]);
-                commands.entity(children[i]).despawn();
+            for child in children.iter().take(children.len() - CHAT_MAX_MESSAGES) {
+                commands.entity(parent).remove_children(&[*child]);
+                commands.entity(*child).despawn();
             }
         }
     }
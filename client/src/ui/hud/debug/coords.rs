@@ -1,10 +1,14 @@
 use crate::player::CurrentPlayerMarker;
+use crate::world::ClientWorldMap;
 use bevy::prelude::*;
-use shared::world::block_to_chunk_coord;
+use shared::world::{block_to_chunk_coord, get_light_level};
 
 #[derive(Component)]
 pub struct CoordsText;
 
+#[derive(Component)]
+pub struct LightText;
+
 pub fn coords_text_update_system(
     player: Query<&Transform, With<CurrentPlayerMarker>>,
     query: Query<Entity, With<CoordsText>>,
@@ -24,3 +28,26 @@ pub fn coords_text_update_system(
         );
     }
 }
+
+/// Light of the block the player stands in
+pub fn light_text_update_system(
+    player: Query<&Transform, With<CurrentPlayerMarker>>,
+    query: Query<Entity, With<LightText>>,
+    mut writer: TextUiWriter,
+    world_map: Res<ClientWorldMap>,
+) {
+    let position = player.single().translation.round().as_ivec3();
+    let text = match get_light_level(world_map.as_ref(), &position) {
+        Some(light) => format!(
+            "Light: {} (sky {}, block {})",
+            light.brightest(),
+            light.sky,
+            light.block
+        ),
+        None => "Light: N/A".into(),
+    };
+
+    for entity in query.iter() {
+        *writer.text(entity, 0) = text.clone();
+    }
+}
@@ -1,4 +1,5 @@
 use crate::world::time::ClientTime;
+use crate::world::weather::ClientWeather;
 use crate::world::ClientWorldMap;
 use bevy::prelude::*;
 
@@ -29,8 +30,10 @@ pub fn time_text_update_system(
     query: Query<Entity, With<TimeText>>,
     mut writer: TextUiWriter,
     time_resource: Res<ClientTime>,
+    weather: Res<ClientWeather>,
 ) {
     for entity in query.iter() {
-        *writer.text(entity, 0) = format!("Time: {}", time_resource.0);
+        *writer.text(entity, 0) =
+            format!("Time: {} ({})", time_resource.ticks as u64, weather.weather);
     }
 }
@@ -1,7 +1,7 @@
 use super::loaded_stats::TimeText;
 use super::loaded_stats::{BlocksNumberText, ChunksNumberText};
 use super::targeted_block::BlockText;
-use super::{CoordsText, FpsText};
+use super::{CoordsText, FpsText, LightText};
 use crate::input::data::GameAction;
 use crate::input::keyboard::get_action_keys;
 use crate::{GameState, KeyMap};
@@ -97,6 +97,7 @@ pub fn setup_hud(mut commands: Commands) {
         )
     };
     let coords_text = commands.spawn((CoordsText, default_text_bundle())).id();
+    let light_text = commands.spawn((LightText, default_text_bundle())).id();
     let blocks_number_text = commands
         .spawn((BlocksNumberText, default_text_bundle()))
         .id();
@@ -117,6 +118,7 @@ pub fn setup_hud(mut commands: Commands) {
     commands.entity(root).add_children(&[
         text_fps,
         coords_text,
+        light_text,
         blocks_number_text,
         chunks_number_text,
         block_text,
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use shared::players::{constants::MAX_HEALTH, GameMode, Player};
+
+use crate::{
+    constants::{HOTBAR_BORDER, HOTBAR_CELL_SIZE, HOTBAR_PADDING, MAX_HOTBAR_SLOTS},
+    player::CurrentPlayerMarker,
+    GameState,
+};
+
+#[derive(Component)]
+pub struct HealthBar;
+
+/// Red part of the health bar, its width follows the player's health
+#[derive(Component)]
+pub struct HealthBarFill;
+
+pub fn setup_health_bar(mut commands: Commands) {
+    // Same width as the hotbar, right above it
+    let width = MAX_HOTBAR_SLOTS as f32 * HOTBAR_CELL_SIZE;
+
+    commands
+        .spawn((
+            HealthBar,
+            StateScoped(GameState::Game),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(70. + HOTBAR_CELL_SIZE + HOTBAR_PADDING + HOTBAR_BORDER),
+                width: Val::Px(width),
+                height: Val::Px(10.),
+                margin: UiRect::horizontal(Val::Auto),
+                border: UiRect::all(Val::Px(2.)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
+            BorderColor(Color::BLACK),
+            GlobalZIndex(1),
+        ))
+        .with_children(|bar| {
+            bar.spawn((
+                HealthBarFill,
+                Node {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+            ));
+        });
+}
+
+pub fn update_health_bar(
+    player: Query<&Player, (With<CurrentPlayerMarker>, Changed<Player>)>,
+    mut bar: Query<&mut Visibility, With<HealthBar>>,
+    mut fill: Query<&mut Node, With<HealthBarFill>>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    // Only survival players can lose health
+    let visibility = if player.game_mode == GameMode::Survival {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut vis in bar.iter_mut() {
+        vis.set_if_neq(visibility);
+    }
+
+    for mut node in fill.iter_mut() {
+        node.width = Val::Percent(100. * player.health as f32 / MAX_HEALTH as f32);
+    }
+}
@@ -0,0 +1,87 @@
+use super::{
+    update_inventory_cell, ContainerCell, ContainerSection, InventoryRoot, OpenedContainer,
+};
+use crate::constants::INTERACTION_DISTANCE;
+use crate::network::SendGameMessageExtension;
+use crate::player::CurrentPlayerMarker;
+use crate::world::{ClientWorldMap, MaterialResource};
+use bevy::prelude::*;
+use bevy_renet::renet::RenetClient;
+use shared::messages::ClientToServerMessage;
+use shared::players::{Inventory, InventoryAction};
+
+/// Shows the slots of the opened container, and sends clicks on them to the server\
+/// The container is closed along with the inventory, or when it gets out of reach
+pub fn render_container(
+    (mut text_query, mut atlas_query, mut cell_query, mut section_query, root_query, player_query): (
+        Query<&mut Text>,
+        Query<(&mut ImageNode, &mut Visibility), Without<InventoryRoot>>,
+        Query<(&Interaction, &mut BorderColor, &ContainerCell, &Children)>,
+        Query<&mut Node, With<ContainerSection>>,
+        Query<&Visibility, With<InventoryRoot>>,
+        Query<&Transform, With<CurrentPlayerMarker>>,
+    ),
+    (mouse_input, mut opened, mut inventory, mut world_map, materials): (
+        Res<ButtonInput<MouseButton>>,
+        ResMut<OpenedContainer>,
+        ResMut<Inventory>,
+        ResMut<ClientWorldMap>,
+        Res<MaterialResource>,
+    ),
+    mut client: ResMut<RenetClient>,
+) {
+    if let Some(position) = opened.position {
+        let in_reach = player_query.get_single().is_ok_and(|transform| {
+            transform.translation.distance(position.as_vec3()) <= INTERACTION_DISTANCE
+        });
+        if *root_query.single() == Visibility::Hidden
+            || !in_reach
+            || world_map.get_block_entity(&position).is_none()
+        {
+            opened.position = None;
+        }
+    }
+
+    let mut section = section_query.single_mut();
+    let Some(position) = opened.position else {
+        section.display = Display::None;
+        return;
+    };
+    section.display = Display::Flex;
+
+    let Some(entity) = world_map.get_block_entity_mut(&position) else {
+        return;
+    };
+
+    for (interaction, mut border_color, cell, children) in cell_query.iter_mut() {
+        let stack = entity.items().get(&cell.id).copied();
+
+        let mut txt = text_query.get_mut(children[0]).unwrap();
+        let (mut stack_img, mut stack_vis) = atlas_query.get_mut(children[1]).unwrap();
+
+        if let Some(atlas) = &mut stack_img.texture_atlas {
+            update_inventory_cell(&stack, &mut txt, &mut stack_vis, atlas, &materials);
+        }
+
+        if *interaction == Interaction::None {
+            border_color.0 = Color::srgb(0.3, 0.3, 0.3);
+            continue;
+        }
+
+        let action = if mouse_input.just_pressed(MouseButton::Left) {
+            Some(InventoryAction::LeftClick(cell.id))
+        } else if mouse_input.just_pressed(MouseButton::Right) {
+            Some(InventoryAction::RightClick(cell.id))
+        } else {
+            None
+        };
+
+        if let Some(action) = action {
+            // Applied locally right away, the server sends back the authoritative container
+            entity.apply_action(&mut inventory, action);
+            client.send_game_message(ClientToServerMessage::ContainerAction { position, action });
+        } else {
+            border_color.0 = Color::WHITE;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{update_inventory_cell, CreativeItemCell, CreativeSection};
+use crate::network::SendGameMessageExtension;
+use crate::player::CurrentPlayerMarker;
+use crate::ui::hud::hotbar::Hotbar;
+use crate::world::MaterialResource;
+use bevy::prelude::*;
+use bevy_renet::renet::RenetClient;
+use shared::messages::ClientToServerMessage;
+use shared::players::{Inventory, InventoryAction, Player};
+use shared::world::ItemStack;
+
+/// Lists every item to players with unlimited blocks\
+/// Clicking one fills the selected hotbar slot with a full stack of it, as pick-block does
+pub fn render_creative_items(
+    (mut text_query, mut atlas_query, mut cell_query, mut section_query, player_query, hotbar): (
+        Query<&mut Text>,
+        Query<(&mut ImageNode, &mut Visibility)>,
+        Query<(&Interaction, &mut BorderColor, &CreativeItemCell, &Children)>,
+        Query<&mut Node, With<CreativeSection>>,
+        Query<&Player, With<CurrentPlayerMarker>>,
+        Query<&Hotbar>,
+    ),
+    (mouse_input, mut inventory, materials): (
+        Res<ButtonInput<MouseButton>>,
+        ResMut<Inventory>,
+        Res<MaterialResource>,
+    ),
+    mut client: ResMut<RenetClient>,
+) {
+    let mut section = section_query.single_mut();
+    let creative = player_query
+        .get_single()
+        .is_ok_and(|player| player.game_mode.has_unlimited_blocks());
+    if !creative {
+        section.display = Display::None;
+        return;
+    }
+    section.display = Display::Flex;
+
+    for (interaction, mut border_color, cell, children) in cell_query.iter_mut() {
+        let stack = Some(ItemStack {
+            item_id: cell.item_id,
+            item_type: cell.item_id.get_default_type(),
+            nb: 1,
+        });
+
+        let mut txt = text_query.get_mut(children[0]).unwrap();
+        let (mut stack_img, mut stack_vis) = atlas_query.get_mut(children[1]).unwrap();
+
+        if let Some(atlas) = &mut stack_img.texture_atlas {
+            update_inventory_cell(&stack, &mut txt, &mut stack_vis, atlas, &materials);
+        }
+        // The count of a picker item means nothing
+        txt.clear();
+
+        if *interaction == Interaction::None {
+            border_color.0 = Color::srgb(0.3, 0.3, 0.3);
+            continue;
+        }
+
+        if mouse_input.just_pressed(MouseButton::Left) {
+            let action = InventoryAction::PickItem {
+                slot: hotbar.single().selected,
+                item_id: cell.item_id,
+            };
+            inventory.apply_action(action);
+            client.send_game_message(ClientToServerMessage::InventoryAction(action));
+        } else {
+            border_color.0 = Color::WHITE;
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::{add_item_floating_stack, remove_item_floating_stack, UIMode};
+use super::UIMode;
 use crate::constants::MAX_HOTBAR_SLOTS;
 use crate::input::data::GameAction;
 use crate::input::keyboard::is_action_just_pressed;
+use crate::network::SendGameMessageExtension;
 use crate::ui::hud::hotbar::Hotbar;
 use crate::ui::hud::{FloatingStack, InventoryCell, InventoryRoot};
 use crate::world::MaterialResource;
@@ -18,8 +19,11 @@ use bevy::prelude::{
 use bevy::sprite::TextureAtlas;
 use bevy::ui::{BorderColor, Interaction};
 use bevy::window::PrimaryWindow;
-use shared::players::Inventory;
-use shared::world::{ItemId, ItemStack};
+use bevy_renet::renet::RenetClient;
+use shared::crafting::{apply_inventory_action, RecipeRegistry};
+use shared::messages::{ChatMessageRequest, ClientToServerMessage};
+use shared::players::{Inventory, InventoryAction};
+use shared::world::ItemId;
 
 pub fn render_inventory_hotbar(
     (
@@ -33,21 +37,23 @@ pub fn render_inventory_hotbar(
     ): (
         Query<&mut Text>,
         Query<(&mut ImageNode, &mut Visibility), Without<InventoryRoot>>,
-        Query<(&mut Node, &mut FloatingStack, &Children), With<FloatingStack>>,
+        Query<(&mut Node, &Children), With<FloatingStack>>,
         Query<(&Interaction, &mut BorderColor, &InventoryCell, &Children), With<InventoryCell>>,
         Query<&mut Visibility, With<InventoryRoot>>,
         Query<&Window, With<PrimaryWindow>>,
         Query<&mut Hotbar>,
     ),
-    (keyboard_input, mouse_input, key_map, mut inventory, materials, ui_mode): (
+    (keyboard_input, mouse_input, key_map, mut inventory, materials, ui_mode, recipes): (
         Res<ButtonInput<KeyCode>>,
         Res<ButtonInput<MouseButton>>,
         Res<KeyMap>,
         ResMut<Inventory>,
         Res<MaterialResource>,
         Res<UIMode>,
+        Res<RecipeRegistry>,
     ),
     mut scroll: EventReader<MouseWheel>,
+    mut client: ResMut<RenetClient>,
 ) {
     let mut vis = visibility_query.single_mut();
 
@@ -61,26 +67,15 @@ pub fn render_inventory_hotbar(
     }
 
     if is_action_just_pressed(GameAction::DebugGetBlock, &keyboard_input, &key_map) {
-        debug!("Blocks given to user");
-        inventory.add_item_to_inventory(ItemStack {
-            item_id: ItemId::Glass,
-            item_type: ItemId::Glass.get_default_type(),
-            nb: 64,
-        });
-
-        inventory.add_item_to_inventory(ItemStack {
-            item_id: ItemId::Poppy,
-            item_type: ItemId::Poppy.get_default_type(),
-            nb: 64,
-        });
-        inventory.add_item_to_inventory(ItemStack {
-            item_id: ItemId::Dandelion,
-            item_type: ItemId::Dandelion.get_default_type(),
-            nb: 64,
-        });
+        debug!("Blocks requested from server");
+        for item_id in [ItemId::Glass, ItemId::Poppy, ItemId::Dandelion] {
+            client.send_game_message(ClientToServerMessage::ChatMessage(ChatMessageRequest {
+                content: format!("/give {:?} 64", item_id),
+            }));
+        }
     }
 
-    let (mut style, mut floating_stack, children) = floating_stack_query.single_mut();
+    let (mut style, children) = floating_stack_query.single_mut();
     let mut txt = text_query.get_mut(children[0]).unwrap();
     let (mut stack_img, mut stack_vis) = atlas_query.get_mut(children[1]).unwrap();
 
@@ -102,7 +97,7 @@ pub fn render_inventory_hotbar(
     hotbar_query.single_mut().selected = stack_scrolling.rem_euclid(MAX_HOTBAR_SLOTS as i32) as u32;
     if let Some(atlas) = &mut stack_img.texture_atlas {
         update_inventory_cell(
-            &floating_stack.items,
+            &inventory.floating_stack,
             &mut txt,
             &mut stack_vis,
             atlas,
@@ -140,93 +135,18 @@ pub fn render_inventory_hotbar(
             continue;
         }
         // Means we have an interaction with the cell, but which type of interaction ?
-        let floating_items = floating_stack.items;
-        // Using variables to avoid E0502 errors -_-
-        let stack_exists = stack.is_some();
-        let floating_exists = floating_items.is_some();
-        // In case LMB pressed :
-        if mouse_input.just_pressed(MouseButton::Left) {
-            // Transfer items from inventory cell to floating stack
-            if stack_exists
-                && floating_exists
-                && stack.unwrap().item_id == floating_items.unwrap().item_id
-                && stack.unwrap().nb < stack.unwrap().item_id.get_max_stack()
-            {
-                let stack = stack.unwrap();
-                let floating_items = floating_items.unwrap();
-                inventory.add_item_to_stack(
-                    cell.id,
-                    remove_item_floating_stack(
-                        &mut floating_stack,
-                        stack.item_id.get_max_stack() - stack.nb,
-                    ),
-                    floating_items.item_id,
-                    stack.item_type,
-                );
-            } else {
-                if stack_exists {
-                    let stack = stack.unwrap();
-                    floating_stack.items = Some(stack);
-                    // If no exchange is made with floating stack, clear cell
-                    if !floating_exists {
-                        inventory.inner.remove(&cell.id);
-                    }
-                }
-                // Transfer items from floating stack to inventory cell
-                if floating_exists {
-                    let floating_items = floating_items.unwrap();
-                    inventory.inner.insert(cell.id, floating_items);
-                    // If no exchange is made with cell, clear floating stack
-                    if !stack_exists {
-                        floating_stack.items = None;
-                    }
-                }
-            }
-        }
-        // Welcome to nesting hell
-        else if mouse_input.just_pressed(MouseButton::Right) {
-            // If floating stack exists : remove 1 item from floating stack
-            if floating_exists {
-                let floating_items = floating_items.unwrap();
-                if stack_exists {
-                    let stack = stack.unwrap();
-                    if floating_items.item_id == stack.item_id && floating_items.nb > 0 {
-                        // Get added nb of items into inventory -> removes them from floating stack
-                        remove_item_floating_stack(
-                            &mut floating_stack,
-                            inventory.add_item_to_stack(
-                                cell.id,
-                                1,
-                                floating_items.item_id,
-                                floating_items.item_type,
-                            ),
-                        );
-                    }
-                } else if floating_items.nb > 0 {
-                    // Get added nb of items into inventory -> removes them from floating stack
-                    remove_item_floating_stack(
-                        &mut floating_stack,
-                        inventory.add_item_to_stack(
-                            cell.id,
-                            1,
-                            floating_items.item_id,
-                            floating_items.item_type,
-                        ),
-                    );
-                }
-            }
-            // Else if hovering a stack : cut hovered stack in half (rounded up), and push it to floating stack
-            else if stack_exists {
-                let stack = stack.unwrap();
-                let nb = (stack.nb + 1) / 2;
-                // Get removed nb of items removed from inventory -> adds them into the floating stack
-                add_item_floating_stack(
-                    &mut floating_stack,
-                    inventory.remove_item_from_stack(cell.id, nb),
-                    stack.item_id,
-                    stack.item_type,
-                );
-            }
+        let action = if mouse_input.just_pressed(MouseButton::Left) {
+            Some(InventoryAction::LeftClick(cell.id))
+        } else if mouse_input.just_pressed(MouseButton::Right) {
+            Some(InventoryAction::RightClick(cell.id))
+        } else {
+            None
+        };
+
+        if let Some(action) = action {
+            // Applied locally right away, the server sends back the authoritative inventory
+            apply_inventory_action(&mut inventory, action, &recipes);
+            client.send_game_message(ClientToServerMessage::InventoryAction(action));
         } else {
             border_color.0 = Color::WHITE;
         }
@@ -1,7 +1,8 @@
 use bevy::{
     log::debug,
-    prelude::{Component, Query, ResMut, Resource, Visibility, With},
+    prelude::{Component, IVec3, Query, ResMut, Resource, Visibility, With},
 };
+use shared::world::ItemId;
 
 /// All UI dialogs toggling mouse visibility MUST use this in their bundle list\
 /// They must also possess the `visibility` attribute\
@@ -24,12 +25,36 @@ pub struct InventoryCell {
     pub id: u32,
 }
 
-/// The current selected stack, not considered in the player's inventory
+/// Slot of the opened container, `id` being its slot in the block entity
 #[derive(Component)]
-pub struct FloatingStack {
-    pub items: Option<ItemStack>,
+pub struct ContainerCell {
+    pub id: u32,
+}
+
+/// Title and slots of the opened container, above the player's inventory
+#[derive(Component)]
+pub struct ContainerSection;
+
+/// Item of the creative picker, clicking it fills the selected hotbar slot
+#[derive(Component)]
+pub struct CreativeItemCell {
+    pub item_id: ItemId,
 }
 
+/// Every item obtainable in creative mode, below the player's inventory
+#[derive(Component)]
+pub struct CreativeSection;
+
+/// Block whose container is displayed in the inventory, `None` when only the inventory is opened
+#[derive(Resource, Default)]
+pub struct OpenedContainer {
+    pub position: Option<IVec3>,
+}
+
+/// Displays the stack held by the cursor, see `Inventory::floating_stack`
+#[derive(Component)]
+pub struct FloatingStack;
+
 #[derive(PartialEq, Eq, Clone, Copy, Resource)]
 pub enum UIMode {
     Opened,
@@ -59,12 +84,13 @@ pub fn set_ui_mode(
     *ui_mode = UIMode::Closed;
 }
 
+mod container;
+mod creative;
 mod display;
-pub mod items;
 mod setup;
 
 use bevy_simple_text_input::TextInputInactive;
+pub use container::*;
+pub use creative::*;
 pub use display::*;
-use items::*;
 pub use setup::*;
-use shared::world::ItemStack;
@@ -1,10 +1,21 @@
 use super::UiDialog;
 use crate::constants::{HOTBAR_BORDER, HOTBAR_CELL_SIZE, HOTBAR_PADDING, MAX_HOTBAR_SLOTS};
-use crate::ui::hud::{FloatingStack, InventoryCell, InventoryDialog, InventoryRoot};
-use crate::world::MaterialResource;
+use crate::ui::hud::{
+    ContainerCell, ContainerSection, CreativeItemCell, CreativeSection, FloatingStack,
+    InventoryCell, InventoryDialog, InventoryRoot,
+};
+use crate::world::{AtlasWrapper, MaterialResource};
 use crate::GameState;
 use bevy::{prelude::*, ui::FocusPolicy};
-use shared::MAX_INVENTORY_SLOTS;
+use shared::crafting::{
+    RecipeRegistry, CRAFTING_GRID_SIZE, CRAFTING_GRID_START, CRAFTING_RESULT_SLOT,
+};
+use shared::world::{ItemId, CHEST_SLOTS};
+use shared::{GameFolderPaths, MAX_INVENTORY_SLOTS};
+
+pub fn load_recipes(mut commands: Commands, paths: Res<GameFolderPaths>) {
+    commands.insert_resource(RecipeRegistry::load(&paths));
+}
 
 pub fn setup_inventory(mut commands: Commands, materials_resource: Res<MaterialResource>) {
     let atlas = materials_resource.items.as_ref().unwrap();
@@ -51,6 +62,43 @@ pub fn setup_inventory(mut commands: Commands, materials_resource: Res<MaterialR
         ))
         .id();
 
+    // Only displayed while a container is opened, see `render_container`
+    let container_section = commands
+        .spawn((
+            ContainerSection,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                Text::new("Chest"),
+                TextFont {
+                    font_size: 24.,
+                    ..default()
+                },
+                Node {
+                    align_content: AlignContent::Center,
+                    ..default()
+                },
+            ));
+            builder
+                .spawn(Node {
+                    display: Display::Grid,
+                    grid_template_columns: RepeatedGridTrack::auto(9),
+                    margin: UiRect::all(Val::Px(10.)),
+                    ..default()
+                })
+                .with_children(|builder| {
+                    for i in 0..CHEST_SLOTS {
+                        spawn_cell(builder, ContainerCell { id: i }, atlas);
+                    }
+                });
+        })
+        .id();
+
     let inventory_title = commands
         .spawn((
             Text::new("Inventory"),
@@ -78,65 +126,113 @@ pub fn setup_inventory(mut commands: Commands, materials_resource: Res<MaterialR
         ))
         .with_children(|builder| {
             for i in MAX_HOTBAR_SLOTS..MAX_INVENTORY_SLOTS {
-                builder
-                    .spawn((
-                        InventoryCell { id: i },
-                        (
-                            Button,
-                            BorderColor(Color::srgb(0.3, 0.3, 0.3)),
-                            FocusPolicy::Block,
-                            Node {
-                                width: Val::Px(HOTBAR_CELL_SIZE),
-                                height: Val::Px(HOTBAR_CELL_SIZE),
-                                margin: UiRect::ZERO,
-                                position_type: PositionType::Relative,
-                                padding: UiRect::all(Val::Px(HOTBAR_PADDING)),
-                                border: UiRect::all(Val::Px(HOTBAR_BORDER)),
-                                ..default()
-                            },
-                        ),
-                    ))
-                    .with_children(|btn| {
-                        btn.spawn((
-                            Text::new("Test"),
-                            TextFont::from_font_size(15.0),
-                            Node {
-                                position_type: PositionType::Absolute,
-                                ..default()
-                            },
-                            ZIndex(1),
-                        ));
-                        btn.spawn((
-                            ImageNode::from_atlas_image(
-                                atlas.texture.clone_weak(),
-                                atlas
-                                    .sources
-                                    .handle(
-                                        atlas.layout.clone_weak(),
-                                        if let Some(handle) = atlas.handles.get("Dirt").as_ref() {
-                                            handle.id()
-                                        } else {
-                                            AssetId::default()
-                                        },
-                                    )
-                                    .unwrap_or_default(),
-                            ),
-                            Node {
-                                width: Val::Px(
-                                    HOTBAR_CELL_SIZE - 2. * (HOTBAR_PADDING + HOTBAR_BORDER),
-                                ),
-                                position_type: PositionType::Relative,
-                                ..default()
-                            },
-                        ));
-                    });
+                spawn_inventory_cell(builder, i, atlas);
+            }
+        })
+        .id();
+
+    let crafting_title = commands
+        .spawn((
+            Text::new("Crafting"),
+            TextFont {
+                font_size: 24.,
+                ..default()
+            },
+            Node {
+                align_content: AlignContent::Center,
+                ..default()
+            },
+        ))
+        .id();
+
+    let crafting_grid = commands
+        .spawn(Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::auto(CRAFTING_GRID_SIZE as u16),
+            margin: UiRect::all(Val::Px(10.)),
+            ..default()
+        })
+        .with_children(|builder| {
+            for i in CRAFTING_GRID_START..CRAFTING_RESULT_SLOT {
+                spawn_inventory_cell(builder, i, atlas);
             }
         })
         .id();
 
+    let crafting_arrow = commands
+        .spawn((
+            Text::new("->"),
+            TextFont {
+                font_size: 24.,
+                ..default()
+            },
+            Node {
+                margin: UiRect::horizontal(Val::Px(10.)),
+                ..default()
+            },
+        ))
+        .id();
+
+    let crafting_result = commands
+        .spawn(Node::default())
+        .with_children(|builder| {
+            spawn_inventory_cell(builder, CRAFTING_RESULT_SLOT, atlas);
+        })
+        .id();
+
+    let crafting_row = commands
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .id();
+
+    commands
+        .entity(crafting_row)
+        .add_children(&[crafting_grid, crafting_arrow, crafting_result]);
+
+    // Only displayed to players with unlimited blocks, see `render_creative_items`
+    let creative_section = commands
+        .spawn((
+            CreativeSection,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                Text::new("Items"),
+                TextFont {
+                    font_size: 24.,
+                    ..default()
+                },
+                Node {
+                    align_content: AlignContent::Center,
+                    ..default()
+                },
+            ));
+            builder
+                .spawn(Node {
+                    display: Display::Grid,
+                    grid_template_columns: RepeatedGridTrack::auto(9),
+                    margin: UiRect::all(Val::Px(10.)),
+                    ..default()
+                })
+                .with_children(|builder| {
+                    for item_id in ItemId::ALL {
+                        spawn_cell(builder, CreativeItemCell { item_id }, atlas);
+                    }
+                });
+        })
+        .id();
+
     let floating_stack = commands
         .spawn((
-            FloatingStack { items: None },
+            FloatingStack,
             (
                 Node {
                     width: Val::Px(20.),
@@ -185,11 +281,74 @@ pub fn setup_inventory(mut commands: Commands, materials_resource: Res<MaterialR
         })
         .id();
 
-    commands
-        .entity(dialog)
-        .add_children(&[inventory_title, inventory_grid]);
+    commands.entity(dialog).add_children(&[
+        container_section,
+        inventory_title,
+        inventory_grid,
+        crafting_title,
+        crafting_row,
+        creative_section,
+    ]);
 
     commands
         .entity(root)
         .add_children(&[dialog, floating_stack]);
 }
+
+fn spawn_inventory_cell(builder: &mut ChildBuilder, id: u32, atlas: &AtlasWrapper) {
+    spawn_cell(builder, InventoryCell { id }, atlas);
+}
+
+/// Slot button showing a stack, `marker` tells which inventory it belongs to
+fn spawn_cell(builder: &mut ChildBuilder, marker: impl Component, atlas: &AtlasWrapper) {
+    builder
+        .spawn((
+            marker,
+            (
+                Button,
+                BorderColor(Color::srgb(0.3, 0.3, 0.3)),
+                FocusPolicy::Block,
+                Node {
+                    width: Val::Px(HOTBAR_CELL_SIZE),
+                    height: Val::Px(HOTBAR_CELL_SIZE),
+                    margin: UiRect::ZERO,
+                    position_type: PositionType::Relative,
+                    padding: UiRect::all(Val::Px(HOTBAR_PADDING)),
+                    border: UiRect::all(Val::Px(HOTBAR_BORDER)),
+                    ..default()
+                },
+            ),
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new("Test"),
+                TextFont::from_font_size(15.0),
+                Node {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ZIndex(1),
+            ));
+            btn.spawn((
+                ImageNode::from_atlas_image(
+                    atlas.texture.clone_weak(),
+                    atlas
+                        .sources
+                        .handle(
+                            atlas.layout.clone_weak(),
+                            if let Some(handle) = atlas.handles.get("Dirt").as_ref() {
+                                handle.id()
+                            } else {
+                                AssetId::default()
+                            },
+                        )
+                        .unwrap_or_default(),
+                ),
+                Node {
+                    width: Val::Px(HOTBAR_CELL_SIZE - 2. * (HOTBAR_PADDING + HOTBAR_BORDER)),
+                    position_type: PositionType::Relative,
+                    ..default()
+                },
+            ));
+        });
+}
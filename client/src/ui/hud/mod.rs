@@ -1,5 +1,6 @@
 pub mod chat;
 pub mod debug;
+pub mod health;
 pub mod hotbar;
 pub mod inventory;
 pub mod reticle;
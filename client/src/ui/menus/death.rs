@@ -0,0 +1,110 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+use bevy_renet::renet::RenetClient;
+use shared::messages::ClientToServerMessage;
+use shared::players::Player;
+
+use crate::network::SendGameMessageExtension;
+use crate::player::CurrentPlayerMarker;
+use crate::ui::hud::UiDialog;
+use crate::GameState;
+
+#[derive(Component)]
+pub struct DeathScreen;
+
+#[derive(Component)]
+pub struct RespawnButton;
+
+pub fn setup_death_screen(mut commands: Commands, assets: Res<AssetServer>) {
+    let font = assets.load("./fonts/RustCraftRegular-Bmg3.otf");
+
+    commands
+        .spawn((
+            DeathScreen,
+            UiDialog,
+            Name::new("DeathScreen"),
+            StateScoped(GameState::Game),
+            BackgroundColor(Color::srgba(0.5, 0., 0., 0.5)),
+            Node {
+                width: Val::Vw(100.),
+                height: Val::Vh(100.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(40.),
+                ..Default::default()
+            },
+            FocusPolicy::Block,
+            Visibility::Hidden,
+            // Above the pause menu, the game cannot be resumed until the player respawns
+            GlobalZIndex(6),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new("You died!"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 40.,
+                    font_smoothing: default(),
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            root.spawn((
+                RespawnButton,
+                Button,
+                Node {
+                    min_width: Val::Vw(30.),
+                    border: UiRect::all(Val::Px(3.)),
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect::all(Val::Px(7.)),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                BorderColor(Color::BLACK),
+            ))
+            .with_children(|btn| {
+                btn.spawn((
+                    Text::new("Respawn"),
+                    TextFont {
+                        font,
+                        font_size: 20.,
+                        font_smoothing: default(),
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        });
+}
+
+pub fn render_death_screen(
+    player: Query<&Player, With<CurrentPlayerMarker>>,
+    mut screen: Query<&mut Visibility, With<DeathScreen>>,
+    mut button: Query<(&Interaction, &mut BorderColor), With<RespawnButton>>,
+    mut client: ResMut<RenetClient>,
+) {
+    let (Ok(player), Ok(mut visibility)) = (player.get_single(), screen.get_single_mut()) else {
+        return;
+    };
+
+    let expected = if player.is_dead() {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    visibility.set_if_neq(expected);
+
+    if !player.is_dead() {
+        return;
+    }
+
+    for (interaction, mut border) in button.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                client.send_game_message(ClientToServerMessage::Respawn);
+            }
+            Interaction::Hovered => border.0 = Color::WHITE,
+            Interaction::None => border.0 = Color::BLACK,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod death;
 pub mod home;
 pub mod multi;
 pub mod pause;
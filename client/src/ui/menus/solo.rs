@@ -24,6 +24,7 @@ use bevy_simple_text_input::TextInputTextFont;
 use bevy_simple_text_input::{
     TextInputInactive, TextInputPlaceholder, TextInputSettings, TextInputValue,
 };
+use shared::players::GameMode;
 use shared::world::get_game_folder;
 use shared::GameFolderPaths;
 use std::io;
@@ -44,6 +45,8 @@ pub struct WorldList {
 #[derive(Component)]
 pub enum MultiplayerButtonAction {
     Add,
+    /// Cycles through the game modes of the worlds created from this menu
+    ToggleGameMode,
     Load(Entity),
     Delete(Entity),
 }
@@ -51,14 +54,24 @@ pub enum MultiplayerButtonAction {
 #[derive(Component)]
 pub struct WorldNameInput;
 
+#[derive(Component)]
+pub struct GameModeText;
+
 #[derive(Resource, Default, Debug, Clone)]
 pub struct SelectedWorld {
     pub name: Option<String>,
+    /// Only used if the world does not exist yet
+    pub game_mode: GameMode,
+}
+
+fn game_mode_label(game_mode: GameMode) -> String {
+    format!("Game mode: {}", game_mode)
 }
 
 pub fn solo_menu_setup(
     mut commands: Commands,
     assets_server: Res<AssetServer>,
+    selected_world: Res<SelectedWorld>,
     _paths: Res<GameFolderPaths>,
 ) {
     let background_image = load_background_image(&assets_server);
@@ -186,6 +199,30 @@ pub fn solo_menu_setup(
                         ),
                     ));
 
+                    wrapper
+                        .spawn((
+                            (
+                                Button,
+                                BorderColor(Color::BLACK),
+                                BackgroundColor(BACKGROUND_COLOR),
+                                {
+                                    let mut style = btn_style.clone();
+                                    style.grid_column = GridPlacement::span(2);
+                                    style
+                                },
+                                ImageNode::new(button_background_image.clone()),
+                            ),
+                            MultiplayerButtonAction::ToggleGameMode,
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                Text::new(game_mode_label(selected_world.game_mode)),
+                                txt_font.clone(),
+                                txt_color,
+                                GameModeText,
+                            ));
+                        });
+
                     wrapper
                         .spawn((
                             (
@@ -245,21 +282,36 @@ pub fn list_worlds(
 
     let paths = fs::read_dir(path).unwrap();
 
+    // Worlds are stored as folders, older saves as a single `<world>.ron` file
+    let mut world_names: Vec<String> = Vec::new();
     for path in paths {
-        let path_str = path.unwrap().file_name().into_string().unwrap();
-
-        if path_str.ends_with(".ron") {
-            add_world_item(
-                path_str.replace(".ron", ""),
-                &mut commands,
-                &assets,
-                &mut list,
-                list_entity,
-                &mut world_map,
-                &game_paths,
-            );
+        let entry = path.unwrap();
+        let path_str = entry.file_name().into_string().unwrap();
+
+        let world_name = if entry.path().is_dir() {
+            path_str
+        } else if path_str.ends_with(".ron") {
+            path_str.replace(".ron", "")
+        } else {
+            continue;
+        };
+
+        if !world_names.contains(&world_name) {
+            world_names.push(world_name);
         }
     }
+
+    for world_name in world_names {
+        add_world_item(
+            world_name,
+            &mut commands,
+            &assets,
+            &mut list,
+            list_entity,
+            &mut world_map,
+            &game_paths,
+        );
+    }
 }
 
 fn add_world_item(
@@ -379,10 +431,11 @@ fn generate_new_world_name(world_list: &WorldList) -> String {
 }
 
 pub fn solo_action(
-    (interaction_query, mut name_query, mut list_query): (
+    (interaction_query, mut name_query, mut list_query, mut game_mode_text): (
         Query<(&Interaction, &MultiplayerButtonAction), (Changed<Interaction>, With<Button>)>,
         Query<&mut TextInputValue, With<WorldNameInput>>,
         Query<(Entity, &mut WorldList), With<WorldList>>,
+        Query<&mut Text, With<GameModeText>>,
     ),
     (asset_server, mut menu_state, mut game_state, mut world_map, mut selected_world): (
         Res<AssetServer>,
@@ -430,6 +483,12 @@ pub fn solo_action(
                         debug!("Creating world");
                     }
                 }
+                MultiplayerButtonAction::ToggleGameMode => {
+                    selected_world.game_mode = selected_world.game_mode.next();
+                    for mut text in game_mode_text.iter_mut() {
+                        text.0 = game_mode_label(selected_world.game_mode);
+                    }
+                }
                 MultiplayerButtonAction::Load(world_entity) => {
                     if let Some(world) = list.worlds.get(&world_entity) {
                         // update ressource name
@@ -461,20 +520,23 @@ pub fn delete_save_files(
     world_name: &str,
     game_folder_path: &Res<GameFolderPaths>,
 ) -> Result<(), io::Error> {
-    // Delete `world_save.ron`
-    match fs::remove_file(format!(
-        "{}{}.ron",
-        get_game_folder(Some(game_folder_path))
-            .join(SAVE_PATH)
-            .display(),
-        world_name
-    )) {
+    let save_path: PathBuf = get_game_folder(Some(game_folder_path)).join(SAVE_PATH);
+
+    // Delete the world folder
+    match fs::remove_dir_all(save_path.join(world_name)) {
         Ok(_) => info!("Successfully deleted world"),
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
-            error!("world_save.ron not found, skipping.")
+            info!("World folder not found, skipping.")
         }
         Err(e) => error!("Failed to delete world: {}", e),
     }
 
+    // Delete the legacy `<world>.ron`, if any
+    match fs::remove_file(save_path.join(format!("{}.ron", world_name))) {
+        Ok(_) => info!("Successfully deleted legacy world save"),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => error!("Failed to delete legacy world save: {}", e),
+    }
+
     Ok(())
 }
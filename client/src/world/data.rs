@@ -1,7 +1,7 @@
 use bevy::math::bounding::Aabb3d;
 use bevy::prelude::*;
-use shared::world::BlockData;
-use shared::world::WorldMap;
+use shared::world::{BlockData, BlockEntity, ChunkLight, ItemId};
+use shared::world::{LightWorld, WorldMap};
 use std::collections::HashSet;
 use std::hash::Hash;
 
@@ -26,8 +26,13 @@ pub enum GlobalMaterial {
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 pub struct ClientChunk {
     pub map: HashMap<IVec3, BlockData>, // Maps block positions within a chunk to block IDs
+    /// Keyed by local position, like `map`
+    #[serde(default)]
+    pub block_entities: HashMap<IVec3, BlockEntity>,
     #[serde(skip)]
     pub entity: Option<Entity>,
+    #[serde(skip)]
+    pub light: ChunkLight,
 }
 
 #[derive(Resource, Default, Clone, Serialize, Deserialize)]
@@ -36,6 +41,19 @@ pub struct ClientWorldMap {
     pub map: HashMap<IVec3, crate::world::ClientChunk>, // Maps global chunk positions to chunks
     pub total_blocks_count: u64,
     pub total_chunks_count: u64,
+    /// Changed blocks whose light has to be updated
+    #[serde(skip)]
+    pub light_updates: Vec<IVec3>,
+}
+
+impl LightWorld for ClientWorldMap {
+    fn get_chunk_light(&self, chunk_pos: &IVec3) -> Option<&ChunkLight> {
+        self.map.get(chunk_pos).map(|chunk| &chunk.light)
+    }
+
+    fn get_chunk_light_mut(&mut self, chunk_pos: &IVec3) -> Option<&mut ChunkLight> {
+        self.map.get_mut(chunk_pos).map(|chunk| &mut chunk.light)
+    }
 }
 
 impl WorldMap for ClientWorldMap {
@@ -71,6 +89,8 @@ impl WorldMap for ClientWorldMap {
         let local_block_pos: IVec3 = to_local_pos(global_block_pos);
 
         chunk_map.map.remove(&local_block_pos);
+        chunk_map.block_entities.remove(&local_block_pos);
+        self.light_updates.push(*global_block_pos);
 
         Some(kind)
     }
@@ -88,6 +108,7 @@ impl WorldMap for ClientWorldMap {
         let sub_z: i32 = ((z % CHUNK_SIZE) + CHUNK_SIZE) % CHUNK_SIZE;
 
         chunk.map.insert(IVec3::new(sub_x, sub_y, sub_z), block);
+        self.light_updates.push(*position);
     }
 
     fn check_collision_box(&self, hitbox: &Aabb3d) -> bool {
@@ -95,8 +116,9 @@ impl WorldMap for ClientWorldMap {
         for x in (hitbox.min.x.round() as i32)..=(hitbox.max.x.round() as i32) {
             for y in (hitbox.min.y.round() as i32)..=(hitbox.max.y.round() as i32) {
                 for z in (hitbox.min.z.round() as i32)..=(hitbox.max.z.round() as i32) {
-                    if let Some(block) = self.get_block_by_coordinates(&IVec3::new(x, y, z)) {
-                        if block.id.has_hitbox() {
+                    let position = IVec3::new(x, y, z);
+                    if let Some(block) = self.get_block_by_coordinates(&position) {
+                        if block.collides_with(&position, hitbox) {
                             return true;
                         }
                     }
@@ -107,12 +129,13 @@ impl WorldMap for ClientWorldMap {
     }
 
     fn check_collision_point(&self, point: &Vec3) -> bool {
-        if let Some(block) = self.get_block_by_coordinates(&IVec3::new(
+        let position = IVec3::new(
             point.x.round() as i32,
             point.y.round() as i32,
             point.z.round() as i32,
-        )) {
-            block.id.has_hitbox()
+        );
+        if let Some(block) = self.get_block_by_coordinates(&position) {
+            block.collides_with(&position, &Aabb3d::new(*point, Vec3::ZERO))
         } else {
             false
         }
@@ -138,7 +161,14 @@ impl WorldMap for ClientWorldMap {
 }
 
 impl ClientWorldMap {
-    pub fn try_to_break_block(&mut self, position: &IVec3) -> Option<(BlockData, bool)> {
+    /// Advances the breaking of a block, `tool` being the item held by the player\
+    /// With `instant`, any breakable block is destroyed right away
+    pub fn try_to_break_block(
+        &mut self,
+        position: &IVec3,
+        tool: Option<ItemId>,
+        instant: bool,
+    ) -> Option<(BlockData, bool)> {
         let block: &BlockData = self.get_block_by_coordinates(position)?;
         let kind: BlockData = *block;
 
@@ -154,17 +184,44 @@ impl ClientWorldMap {
 
         let data = block.take()?;
 
-        data.breaking_progress += 1;
+        data.breaking_progress = data.breaking_progress.saturating_add(1);
 
         // info!("Block breaking progress: {}", data.breaking_progress);
-        if kind.id.get_break_time() != 100 {
-            if data.breaking_progress >= 6 * kind.id.get_break_time() {
+        if let Some(steps) = kind.id.get_break_steps(tool) {
+            if instant || data.breaking_progress as u32 >= steps {
                 chunk_map.map.remove(&local_block_pos);
+                self.light_updates.push(*position);
                 return Some((kind, true));
             }
         }
         Some((kind, false))
     }
+
+    pub fn get_block_entity(&self, position: &IVec3) -> Option<&BlockEntity> {
+        self.map
+            .get(&global_block_to_chunk_pos(position))?
+            .block_entities
+            .get(&to_local_pos(position))
+    }
+
+    pub fn get_block_entity_mut(&mut self, position: &IVec3) -> Option<&mut BlockEntity> {
+        self.map
+            .get_mut(&global_block_to_chunk_pos(position))?
+            .block_entities
+            .get_mut(&to_local_pos(position))
+    }
+
+    /// `None` removes the entity
+    pub fn set_block_entity(&mut self, position: &IVec3, entity: Option<BlockEntity>) {
+        let Some(chunk) = self.map.get_mut(&global_block_to_chunk_pos(position)) else {
+            return;
+        };
+        let local_pos = to_local_pos(position);
+        match entity {
+            Some(entity) => chunk.block_entities.insert(local_pos, entity),
+            None => chunk.block_entities.remove(&local_pos),
+        };
+    }
 }
 
 #[derive(Default, Debug)]
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use shared::world::{light_chunk, update_light};
+use std::collections::HashSet;
+
+use crate::world::{ClientWorldMap, WorldRenderRequestUpdateEvent};
+
+/// Limits the time spent lighting chunks when a lot of them are received at once\
+/// Chunks over the limit are lit on the next frames
+const MAX_CHUNKS_LIT_PER_FRAME: usize = 16;
+
+/// Lights the received chunks and updates the light around changed blocks\
+/// Chunks whose light changed are rendered again, unlit chunks are not rendered at all
+pub fn light_system(
+    mut world_map: ResMut<ClientWorldMap>,
+    mut ev_render: EventWriter<WorldRenderRequestUpdateEvent>,
+) {
+    let world_map = world_map.as_mut();
+    let mut changed: HashSet<IVec3> = HashSet::new();
+
+    let unlit: Vec<IVec3> = world_map
+        .map
+        .iter()
+        .filter(|(_, chunk)| !chunk.light.is_lit)
+        .map(|(chunk_pos, _)| *chunk_pos)
+        .take(MAX_CHUNKS_LIT_PER_FRAME)
+        .collect();
+    for chunk_pos in unlit.iter() {
+        changed.extend(light_chunk(world_map, chunk_pos));
+    }
+
+    let updates: HashSet<IVec3> = world_map.light_updates.drain(..).collect();
+    for position in updates.iter() {
+        changed.extend(update_light(world_map, position));
+    }
+
+    ev_render.send_batch(
+        changed
+            .into_iter()
+            .map(WorldRenderRequestUpdateEvent::ChunkToReload),
+    );
+}
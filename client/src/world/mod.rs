@@ -1,8 +1,10 @@
 pub mod celestial;
 pub mod data;
+pub mod light;
 pub mod raycast;
 pub mod rendering;
 pub mod time;
+pub mod weather;
 
 pub use data::*;
 pub use raycast::*;
@@ -1,7 +1,10 @@
 use crate::player::ViewMode;
 
 use super::ClientWorldMap;
-use bevy::{math::bounding::Aabb3d, prelude::*};
+use bevy::{
+    math::bounding::{Aabb3d, BoundingVolume},
+    prelude::*,
+};
 use shared::world::{BlockData, WorldMap};
 
 #[derive(Debug, Clone, Copy)]
@@ -37,6 +40,8 @@ pub struct RaycastResponse {
     pub position: IVec3,
     pub face: FaceDirection,
     pub bbox: Aabb3d,
+    /// Where the ray hit the block
+    pub point: Vec3,
 }
 
 pub fn raycast(
@@ -110,12 +115,14 @@ fn raycast_from_source_position_and_direction(
             current_position.z.round() as i32,
         );
         if let Some(block) = world_map.get_block_by_coordinates(&pos_ivec3) {
-            let bbox = block.id.get_interaction_box(&pos_ivec3);
+            let boxes = block.get_interaction_boxes(&pos_ivec3);
+            let bbox = boxes.iter().skip(1).fold(boxes[0], |bbox, b| bbox.merge(b));
             let pos = current_position.into();
 
-            // Check if our hit is outside of the interaction box
-            if bbox.closest_point(pos) != pos {
+            // Check if our hit is outside of the interaction boxes
+            if !boxes.iter().any(|b| b.closest_point(pos) == pos) {
                 // If it is, ignore the hit
+                previous_position = current_position;
                 continue;
             }
 
@@ -127,6 +134,7 @@ fn raycast_from_source_position_and_direction(
                 position: pos_ivec3,
                 face,
                 bbox,
+                point: current_position,
             });
         }
         previous_position = current_position;
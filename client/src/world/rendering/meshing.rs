@@ -1,4 +1,3 @@
-use std::f32::consts::PI;
 use std::{collections::HashMap, time::Instant};
 
 use crate::world::{ClientChunk, ClientWorldMap};
@@ -7,10 +6,18 @@ use bevy::{
     prelude::*,
     render::mesh::{Indices, PrimitiveTopology},
 };
-use shared::world::{to_global_pos, BlockDirection, BlockId, BlockTransparency, WorldMap};
+use shared::world::{
+    get_light_level, to_global_pos, BlockData, BlockId, BlockTransparency, WorldMap,
+    MAX_LIGHT_LEVEL,
+};
 
 use super::voxel::{Face, FaceDirection, VoxelShape};
 
+/// Brightness of faces in complete darkness, so caves are not pitch black
+const MIN_BRIGHTNESS: f32 = 0.05;
+/// Brightness kept for each light level lost
+const LIGHT_FALLOFF: f32 = 0.8;
+
 #[derive(Copy, Clone, Debug)]
 pub struct UvCoords {
     pub u0: f32,
@@ -97,14 +104,21 @@ pub(crate) fn generate_chunk_mesh(
                 uv_coords = uv_map.get("_Default").unwrap();
             }
 
-            let color_multiplier = 1.0 - block.breaking_progress as f32 / 60.0;
-
             let alpha = match visibility {
                 BlockTransparency::Liquid => 0.5,
                 _ => 1.0,
             };
 
-            if should_render_face(world_map, global_block_pos, &face.direction, &visibility) {
+            if should_render_face(
+                world_map,
+                global_block_pos,
+                block,
+                &face.direction,
+                &visibility,
+            ) {
+                let color_multiplier = (1.0 - block.breaking_progress as f32 / 20.0).max(0.0)
+                    * face_brightness(world_map, global_block_pos, block, &face.direction);
+
                 render_face(
                     &mut local_vertices,
                     &mut local_indices,
@@ -120,18 +134,33 @@ pub(crate) fn generate_chunk_mesh(
             }
         }
 
+        // Flowing water gets lower as it goes further from its source
+        let height = if visibility == BlockTransparency::Liquid {
+            block.water_height()
+        } else {
+            1.
+        };
+
         let local_vertices: Vec<[f32; 3]> = local_vertices
             .iter()
             .map(|v| {
-                let v = rotate_vertices(v, &block.direction);
-                [
-                    v[0] + x,
-                    if block.flipped { 1. - v[1] } else { v[1] } + y,
-                    v[2] + z,
-                ]
+                let v = block.orient(Vec3::new(v[0], v[1] * height, v[2]));
+                [v.x + x, v.y + y, v.z + z]
             })
             .collect();
 
+        let local_normals: Vec<[f32; 3]> = local_normals
+            .iter()
+            .map(|n| block.orient_vector(Vec3::from_array(*n)).to_array())
+            .collect();
+
+        // Flipping mirrors the block, triangles must be turned around to keep facing outwards
+        if block.flipped {
+            for triangle in local_indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+
         if visibility == BlockTransparency::Liquid {
             liquid_mesh_creator.vertices.extend(local_vertices);
             liquid_mesh_creator.indices.extend(local_indices);
@@ -199,7 +228,11 @@ pub(crate) fn is_block_surrounded(
         if let Some(block) = world_map.get_block_by_coordinates(&neighbor_pos) {
             let vis = block.id.get_visibility();
             match vis {
-                BlockTransparency::Solid => {}
+                BlockTransparency::Solid => {
+                    if !block.id.is_full_cube() {
+                        return false;
+                    }
+                }
                 BlockTransparency::Decoration => return false,
                 BlockTransparency::Liquid => {
                     if vis != *block_visibility {
@@ -220,21 +253,6 @@ pub(crate) fn is_block_surrounded(
     true
 }
 
-pub fn rotate_vertices(v: &[f32; 3], direction: &BlockDirection) -> [f32; 3] {
-    let angle = match *direction {
-        BlockDirection::Front => 0.,
-        BlockDirection::Right => -PI / 2.,
-        BlockDirection::Left => PI / 2.,
-        BlockDirection::Back => PI,
-    };
-
-    [
-        angle.cos() * v[0] + angle.sin() * v[2],
-        v[1],
-        (-angle).sin() * v[0] + angle.cos() * v[2],
-    ]
-}
-
 fn render_face(
     local_vertices: &mut Vec<[f32; 3]>,
     local_indices: &mut Vec<u32>,
@@ -271,18 +289,17 @@ fn render_face(
         // !!! DO NOT REMOVE THE FLOAT OFFSET !!!
         // It removes seams between blocks in chunk meshes
         [
-            (uv[0] + uv_coords.u0 + 0.001).min(uv_coords.u1 - 0.001),
-            (uv[1] + uv_coords.v0 + 0.001).min(uv_coords.v1 - 0.001),
+            (uv_coords.u0 + uv[0] * (uv_coords.u1 - uv_coords.u0))
+                .clamp(uv_coords.u0 + 0.001, uv_coords.u1 - 0.001),
+            (uv_coords.v0 + uv[1] * (uv_coords.v1 - uv_coords.v0))
+                .clamp(uv_coords.v0 + 0.001, uv_coords.v1 - 0.001),
         ]
     }));
 }
 
-fn should_render_face(
-    world_map: &ClientWorldMap,
-    global_block_pos: &IVec3,
-    direction: &FaceDirection,
-    block_visibility: &BlockTransparency,
-) -> bool {
+/// Position of the block a face looks at, relative to its own block\
+/// `None` for faces inside the block
+fn face_offset(block: &BlockData, direction: &FaceDirection) -> Option<IVec3> {
     let offset = match *direction {
         FaceDirection::Front => IVec3::new(0, 0, -1),
         FaceDirection::Back => IVec3::new(0, 0, 1),
@@ -290,13 +307,41 @@ fn should_render_face(
         FaceDirection::Bottom => IVec3::new(0, -1, 0),
         FaceDirection::Left => IVec3::new(-1, 0, 0),
         FaceDirection::Right => IVec3::new(1, 0, 0),
-        FaceDirection::Inset => return true,
+        FaceDirection::Inset => return None,
+    };
+    // Faces turn along with the block
+    Some(block.orient_vector(offset.as_vec3()).round().as_ivec3())
+}
+
+/// Faces are lit by the block they look at, faces inside the block by the block itself\
+/// Faces next to chunks that are not lit yet are fully bright until these are
+fn face_brightness(
+    world_map: &ClientWorldMap,
+    global_block_pos: &IVec3,
+    block: &BlockData,
+    direction: &FaceDirection,
+) -> f32 {
+    let position = *global_block_pos + face_offset(block, direction).unwrap_or(IVec3::ZERO);
+    let level =
+        get_light_level(world_map, &position).map_or(MAX_LIGHT_LEVEL, |light| light.brightest());
+    MIN_BRIGHTNESS + (1. - MIN_BRIGHTNESS) * LIGHT_FALLOFF.powi((MAX_LIGHT_LEVEL - level) as i32)
+}
+
+fn should_render_face(
+    world_map: &ClientWorldMap,
+    global_block_pos: &IVec3,
+    block: &BlockData,
+    direction: &FaceDirection,
+    block_visibility: &BlockTransparency,
+) -> bool {
+    let Some(offset) = face_offset(block, direction) else {
+        return true;
     };
 
-    if let Some(block) = world_map.get_block_by_coordinates(&(*global_block_pos + offset)) {
-        let vis = block.id.get_visibility();
+    if let Some(neighbour) = world_map.get_block_by_coordinates(&(*global_block_pos + offset)) {
+        let vis = neighbour.id.get_visibility();
         match vis {
-            BlockTransparency::Solid => false,
+            BlockTransparency::Solid => !neighbour.id.is_full_cube(),
             BlockTransparency::Decoration => true,
             BlockTransparency::Transparent | BlockTransparency::Liquid => *block_visibility != vis,
         }
@@ -156,7 +156,8 @@ pub fn world_render_system(
         for pos in chunks_to_reload {
             if let Some(chunk) = world_map.map.get(&pos) {
                 // If chunk is empty, ignore it
-                if chunk.map.is_empty() {
+                // Unlit chunks are rendered once lit
+                if chunk.map.is_empty() || !chunk.light.is_lit {
                     continue;
                 }
 
@@ -1,5 +1,5 @@
 use crate::constants::GRASS_COLOR;
-use shared::world::{BlockData, BlockId};
+use shared::world::{BlockData, BlockId, TORCH_ON_WALL};
 
 /// Specifies which position in the voxel this face occupies
 ///
@@ -97,10 +97,105 @@ impl VoxelShape {
 
                 shape
             }
+            BlockId::OakSlab | BlockId::OakStairs => Self::from_shape(block, "OakPlanks"),
+            BlockId::OakDoor => Self::from_shape(block, "OakDoor"),
+            BlockId::SnowLayer => Self::from_shape(block, "Snow"),
+            BlockId::Torch => {
+                if block.state & TORCH_ON_WALL != 0 {
+                    Self::from_shape(block, "WallTorch")
+                } else {
+                    Self::from_shape(block, "Torch")
+                }
+            }
             _ => Self::full_cube(block),
         }
     }
 
+    /// Builds the faces of every box of the block shape, textures are cut to the size of each face\
+    /// Faces inside of the block are `Inset`, since no neighbour can hide them
+    pub fn from_shape(block: &BlockData, texture: &str) -> Self {
+        let mut faces = vec![];
+        for (min, max) in block.get_shape_boxes() {
+            let ([x0, y0, z0], [x1, y1, z1]) = (min.to_array(), max.to_array());
+            let on_side = |side: FaceDirection, coord: f32, bound: f32| {
+                if coord == bound {
+                    side
+                } else {
+                    FaceDirection::Inset
+                }
+            };
+
+            let sides = [
+                (
+                    on_side(FaceDirection::Top, y1, 1.),
+                    [[x0, y1, z1], [x1, y1, z1], [x1, y1, z0], [x0, y1, z0]],
+                    [0, 1, 2, 2, 3, 0],
+                    [0., 1., 0.],
+                    [[x0, 1. - z1], [x1, 1. - z1], [x1, 1. - z0], [x0, 1. - z0]],
+                ),
+                (
+                    on_side(FaceDirection::Bottom, y0, 0.),
+                    [[x0, y0, z0], [x1, y0, z0], [x1, y0, z1], [x0, y0, z1]],
+                    [0, 1, 2, 2, 3, 0],
+                    [0., -1., 0.],
+                    [[1. - x0, z0], [1. - x1, z0], [1. - x1, z1], [1. - x0, z1]],
+                ),
+                (
+                    on_side(FaceDirection::Front, z0, 0.),
+                    [[x1, y1, z0], [x0, y1, z0], [x0, y0, z0], [x1, y0, z0]],
+                    [0, 3, 2, 2, 1, 0],
+                    [0., 0., -1.],
+                    [
+                        [1. - x1, 1. - y1],
+                        [1. - x0, 1. - y1],
+                        [1. - x0, 1. - y0],
+                        [1. - x1, 1. - y0],
+                    ],
+                ),
+                (
+                    on_side(FaceDirection::Back, z1, 1.),
+                    [[x1, y1, z1], [x0, y1, z1], [x0, y0, z1], [x1, y0, z1]],
+                    [0, 1, 2, 2, 3, 0],
+                    [0., 0., 1.],
+                    [[x1, 1. - y1], [x0, 1. - y1], [x0, 1. - y0], [x1, 1. - y0]],
+                ),
+                (
+                    on_side(FaceDirection::Left, x0, 0.),
+                    [[x0, y1, z1], [x0, y1, z0], [x0, y0, z0], [x0, y0, z1]],
+                    [3, 0, 1, 1, 2, 3],
+                    [-1., 0., 0.],
+                    [[z1, 1. - y1], [z0, 1. - y1], [z0, 1. - y0], [z1, 1. - y0]],
+                ),
+                (
+                    on_side(FaceDirection::Right, x1, 1.),
+                    [[x1, y1, z0], [x1, y1, z1], [x1, y0, z1], [x1, y0, z0]],
+                    [0, 1, 2, 2, 3, 0],
+                    [1., 0., 0.],
+                    [
+                        [1. - z0, 1. - y1],
+                        [1. - z1, 1. - y1],
+                        [1. - z1, 1. - y0],
+                        [1. - z0, 1. - y0],
+                    ],
+                ),
+            ];
+
+            for (direction, vertices, indices, normal, uvs) in sides {
+                faces.push(Face {
+                    texture: texture.into(),
+                    direction,
+                    vertices: vertices.to_vec(),
+                    indices: indices.to_vec(),
+                    normals: vec![normal; 4],
+                    colors: vec![[1.0, 1.0, 1.0, 1.0]; 4],
+                    uvs: uvs.to_vec(),
+                });
+            }
+        }
+
+        VoxelShape { faces }
+    }
+
     pub fn full_cube(block: &BlockData) -> Self {
         VoxelShape {
             faces: vec![
@@ -1,10 +1,57 @@
 use bevy::prelude::*;
-use serde::{Deserialize, Serialize};
+use shared::messages::TimeUpdate;
+use shared::{DEFAULT_DAY_LENGTH, TICKS_PER_SECOND};
 
-#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
-pub struct ClientTime(pub u64);
+/// Time further than this from the server is not caught up with smoothly, but set right away\
+/// Happens when joining, or when the time is changed with a command
+const MAX_TIME_DRIFT: f64 = (5 * TICKS_PER_SECOND) as f64;
 
-pub fn time_update_system(mut time: ResMut<ClientTime>) {
-    time.0 += 1;
-    // NOTE: time should eventually be periodically synced with the server to avoid drift using a NTP-like protocol
+/// Time of day, owned by the server and interpolated between its updates
+#[derive(Resource, Debug, Clone)]
+pub struct ClientTime {
+    /// Server ticks, with the fraction of tick elapsed since the last one
+    pub ticks: f64,
+    /// Ticks in a full day
+    pub day_length: u64,
+    /// Where the server should be now, extrapolated from its last update
+    server_ticks: f64,
+}
+
+impl Default for ClientTime {
+    fn default() -> Self {
+        Self {
+            ticks: 0.,
+            day_length: DEFAULT_DAY_LENGTH,
+            server_ticks: 0.,
+        }
+    }
+}
+
+impl ClientTime {
+    pub fn apply_update(&mut self, update: TimeUpdate) {
+        self.server_ticks = update.time as f64;
+        self.day_length = update.day_length;
+    }
+
+    /// Progress of the current day, from 0 to 1
+    pub fn day_progress(&self) -> f32 {
+        let day_length = self.day_length.max(1) as f64;
+        ((self.ticks % day_length) / day_length) as f32
+    }
+}
+
+/// Advances the time at the server's tick rate, slowly correcting the drift from the server
+pub fn time_update_system(mut client_time: ResMut<ClientTime>, time: Res<Time>) {
+    let delta = time.delta_secs_f64();
+    let elapsed = delta * TICKS_PER_SECOND as f64;
+
+    client_time.server_ticks += elapsed;
+    let ticks = client_time.ticks + elapsed;
+    let drift = client_time.server_ticks - ticks;
+
+    client_time.ticks = if drift.abs() > MAX_TIME_DRIFT {
+        client_time.server_ticks
+    } else {
+        ticks + drift * delta.min(1.)
+    };
 }
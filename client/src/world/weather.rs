@@ -0,0 +1,215 @@
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::view::NoFrustumCulling;
+use rand::Rng;
+use shared::messages::WeatherUpdate;
+use shared::world::{get_light_level, BlockTransparency, Weather, WorldMap, MAX_LIGHT_LEVEL};
+
+use crate::camera::CameraController;
+use crate::world::ClientWorldMap;
+use crate::GameState;
+
+/// Horizontal distance around the camera where precipitation is simulated
+const PRECIPITATION_RADIUS: f32 = 16.0;
+/// Drops appear at most this high above the camera
+const PRECIPITATION_HEIGHT: f32 = 12.0;
+/// Drops falling this far below the camera disappear
+const PRECIPITATION_DEPTH: f32 = 8.0;
+/// Spawning stops after this many positions covered by a roof, so it stays cheap indoors
+const MAX_SPAWN_ATTEMPTS_PER_FRAME: usize = 200;
+
+const RAIN_SPEED: f32 = 14.0;
+const SNOW_SPEED: f32 = 1.5;
+/// Snowflakes drift sideways up to this speed
+const SNOW_DRIFT: f32 = 0.5;
+
+/// Width and height of the quad drawn for a drop
+const RAIN_SIZE: Vec2 = Vec2::new(0.03, 0.6);
+const SNOW_SIZE: Vec2 = Vec2::new(0.08, 0.08);
+
+const RAIN_COLOR: [f32; 4] = [0.6, 0.7, 0.9, 0.5];
+const SNOW_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.9];
+
+/// Weather received from the server
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct ClientWeather {
+    pub weather: Weather,
+    /// Whether the precipitation around the player is snow rather than rain
+    pub snowing: bool,
+}
+
+impl ClientWeather {
+    pub fn apply_update(&mut self, update: WeatherUpdate) {
+        self.weather = update.weather;
+        self.snowing = update.snowing;
+    }
+
+    /// Number of drops kept around the camera
+    fn target_drops(&self) -> usize {
+        match self.weather {
+            Weather::Clear => 0,
+            _ if self.snowing => 1000,
+            Weather::Rain => 1500,
+            Weather::Thunder => 2500,
+        }
+    }
+}
+
+struct Particle {
+    position: Vec3,
+    velocity: Vec3,
+    is_snow: bool,
+}
+
+/// Rain and snow around the camera, simulated on the CPU\
+/// Every drop is drawn by the single mesh of the entity, rebuilt each frame
+#[derive(Component, Default)]
+pub struct Precipitation {
+    drops: Vec<Particle>,
+}
+
+pub fn setup_precipitation(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Precipitation::default(),
+        StateScoped(GameState::Game),
+        NotShadowCaster,
+        NotShadowReceiver,
+        NoFrustumCulling,
+        Transform::default(),
+        Visibility::Hidden,
+        Mesh3d(meshes.add(build_mesh(&[], Vec3::X, Vec3::Y))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        })),
+    ));
+}
+
+/// Drops only appear under the open sky, and stop on the first block they meet
+fn is_exposed(world_map: &ClientWorldMap, position: &Vec3) -> bool {
+    let position = position.round().as_ivec3();
+    get_light_level(world_map, &position).is_some_and(|level| level.sky == MAX_LIGHT_LEVEL)
+}
+
+fn is_blocked(world_map: &ClientWorldMap, position: &Vec3) -> bool {
+    world_map
+        .get_block_by_coordinates(&position.round().as_ivec3())
+        .is_some_and(|block| block.id.get_visibility() != BlockTransparency::Decoration)
+}
+
+fn spawn_drop(rng: &mut impl Rng, center: Vec3, is_snow: bool) -> Particle {
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let distance = PRECIPITATION_RADIUS * rng.gen::<f32>().sqrt();
+    let position = center
+        + Vec3::new(
+            angle.cos() * distance,
+            rng.gen_range(-PRECIPITATION_DEPTH..PRECIPITATION_HEIGHT),
+            angle.sin() * distance,
+        );
+
+    let velocity = if is_snow {
+        Vec3::new(
+            rng.gen_range(-SNOW_DRIFT..SNOW_DRIFT),
+            -SNOW_SPEED,
+            rng.gen_range(-SNOW_DRIFT..SNOW_DRIFT),
+        )
+    } else {
+        Vec3::new(0., -RAIN_SPEED, 0.)
+    };
+
+    Particle {
+        position,
+        velocity,
+        is_snow,
+    }
+}
+
+pub fn precipitation_system(
+    mut query: Query<(&mut Precipitation, &Mesh3d, &mut Visibility)>,
+    camera_query: Query<&GlobalTransform, With<CameraController>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    world_map: Res<ClientWorldMap>,
+    weather: Res<ClientWeather>,
+    time: Res<Time>,
+) {
+    let Ok((mut precipitation, mesh, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+    let center = camera.translation();
+    let delta = time.delta_secs();
+
+    precipitation.drops.retain_mut(|drop| {
+        drop.position += drop.velocity * delta;
+        let offset = drop.position - center;
+        offset.y > -PRECIPITATION_DEPTH
+            && offset.xz().length() < PRECIPITATION_RADIUS
+            && !is_blocked(&world_map, &drop.position)
+    });
+
+    // Drops already falling finish their fall when the weather clears
+    let target = weather.target_drops();
+    let mut rng = rand::thread_rng();
+    let mut attempts = 0;
+    while precipitation.drops.len() < target && attempts < MAX_SPAWN_ATTEMPTS_PER_FRAME {
+        attempts += 1;
+        let drop = spawn_drop(&mut rng, center, weather.snowing);
+        if is_exposed(&world_map, &drop.position) && !is_blocked(&world_map, &drop.position) {
+            precipitation.drops.push(drop);
+        }
+    }
+
+    *visibility = if precipitation.drops.is_empty() {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    };
+
+    if let Some(mesh) = meshes.get_mut(&mesh.0) {
+        *mesh = build_mesh(&precipitation.drops, *camera.right(), *camera.up());
+    }
+}
+
+/// Quads facing the camera, rain streaks stay vertical
+fn build_mesh(drops: &[Particle], camera_right: Vec3, camera_up: Vec3) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(drops.len() * 4);
+    let mut colors: Vec<[f32; 4]> = Vec::with_capacity(drops.len() * 4);
+    let mut indices: Vec<u32> = Vec::with_capacity(drops.len() * 6);
+
+    for drop in drops.iter() {
+        let (size, up, color) = if drop.is_snow {
+            (SNOW_SIZE, camera_up, SNOW_COLOR)
+        } else {
+            (RAIN_SIZE, Vec3::Y, RAIN_COLOR)
+        };
+        let right = camera_right * size.x / 2.;
+        let up = up * size.y / 2.;
+
+        let start = positions.len() as u32;
+        for corner in [-right - up, right - up, right + up, -right + up] {
+            positions.push((drop.position + corner).to_array());
+            colors.push(color);
+        }
+        indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
@@ -0,0 +1,170 @@
+use shared::messages::PlayerId;
+use shared::world::ItemId;
+
+use crate::init::ServerLobby;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    Integer,
+    Float,
+    Word,
+    /// Name of a connected player
+    Player,
+    /// Item name, as written in `ItemId`
+    Item,
+    /// Everything until the end of the line
+    Text,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub optional: bool,
+}
+
+impl ArgSpec {
+    pub fn required(name: &'static str, kind: ArgKind) -> Self {
+        Self {
+            name,
+            kind,
+            optional: false,
+        }
+    }
+
+    pub fn optional(name: &'static str, kind: ArgKind) -> Self {
+        Self {
+            name,
+            kind,
+            optional: true,
+        }
+    }
+
+    pub fn usage(&self) -> String {
+        if self.optional {
+            format!("[{}]", self.name)
+        } else {
+            format!("<{}>", self.name)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ArgValue {
+    Integer(i64),
+    Float(f32),
+    Word(String),
+    Player(PlayerId),
+    Item(ItemId),
+    Text(String),
+}
+
+/// Parsed arguments of a command, in the same order as its `ArgSpec`s.\
+/// Optional arguments that were not given are `None`.
+#[derive(Debug, Default)]
+pub struct CommandArgs {
+    values: Vec<Option<ArgValue>>,
+}
+
+impl CommandArgs {
+    pub fn integer(&self, index: usize) -> Option<i64> {
+        match self.values.get(index)? {
+            Some(ArgValue::Integer(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn float(&self, index: usize) -> Option<f32> {
+        match self.values.get(index)? {
+            Some(ArgValue::Float(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn word(&self, index: usize) -> Option<&str> {
+        match self.values.get(index)? {
+            Some(ArgValue::Word(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn player(&self, index: usize) -> Option<PlayerId> {
+        match self.values.get(index)? {
+            Some(ArgValue::Player(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn item(&self, index: usize) -> Option<ItemId> {
+        match self.values.get(index)? {
+            Some(ArgValue::Item(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, index: usize) -> Option<&str> {
+        match self.values.get(index)? {
+            Some(ArgValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_args(
+    specs: &[ArgSpec],
+    raw_args: &str,
+    lobby: &ServerLobby,
+) -> Result<CommandArgs, String> {
+    let mut args = CommandArgs::default();
+    let mut rest = raw_args.trim();
+
+    for spec in specs {
+        let token = if spec.kind == ArgKind::Text {
+            std::mem::take(&mut rest)
+        } else {
+            let (token, remaining) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            rest = remaining.trim_start();
+            token
+        };
+
+        if token.is_empty() {
+            if spec.optional {
+                args.values.push(None);
+                continue;
+            }
+            return Err(format!("Missing argument: {}", spec.name));
+        }
+
+        args.values.push(Some(parse_value(spec, token, lobby)?));
+    }
+
+    if !rest.is_empty() {
+        return Err(format!("Too many arguments: {}", rest));
+    }
+
+    Ok(args)
+}
+
+fn parse_value(spec: &ArgSpec, token: &str, lobby: &ServerLobby) -> Result<ArgValue, String> {
+    match spec.kind {
+        ArgKind::Integer => token
+            .parse()
+            .map(ArgValue::Integer)
+            .map_err(|_| format!("{} must be an integer, got {}", spec.name, token)),
+        ArgKind::Float => token
+            .parse()
+            .map(ArgValue::Float)
+            .map_err(|_| format!("{} must be a number, got {}", spec.name, token)),
+        ArgKind::Word => Ok(ArgValue::Word(token.to_string())),
+        ArgKind::Player => lobby
+            .players
+            .iter()
+            .find(|(_, player)| player.name == token)
+            .map(|(id, _)| ArgValue::Player(*id))
+            .ok_or_else(|| format!("Unknown player: {}", token)),
+        ArgKind::Item => ron::de::from_str::<ItemId>(token)
+            .map(ArgValue::Item)
+            .map_err(|_| format!("Unknown item: {}", token)),
+        ArgKind::Text => Ok(ArgValue::Text(token.to_string())),
+    }
+}
@@ -0,0 +1,401 @@
+use bevy::prelude::*;
+use shared::messages::{PlayerGameModeUpdate, ServerToClientMessage};
+use shared::players::GameMode;
+use shared::world::{ItemStack, Weather};
+use shared::MAX_INVENTORY_SLOTS;
+
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::data::MAX_RANDOM_TICK_SPEED;
+
+use crate::world::stacks::spawn_item_stack;
+
+use super::{
+    ArgKind, ArgSpec, Command, CommandArgs, CommandContext, CommandRegistry, CommandResult,
+    PermissionLevel, COMMAND_PREFIX,
+};
+
+pub fn register_builtin_commands(registry: &mut CommandRegistry) {
+    registry.register(Command {
+        name: "help",
+        description: "Lists commands, or shows how to use one",
+        args: vec![ArgSpec::optional("command", ArgKind::Word)],
+        permission: PermissionLevel::Player,
+        handler: help,
+    });
+    registry.register(Command {
+        name: "tp",
+        description: "Teleports a player to a position",
+        args: vec![
+            ArgSpec::required("x", ArgKind::Float),
+            ArgSpec::required("y", ArgKind::Float),
+            ArgSpec::required("z", ArgKind::Float),
+            ArgSpec::optional("player", ArgKind::Player),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: tp,
+    });
+    registry.register(Command {
+        name: "give",
+        description: "Gives items to a player",
+        args: vec![
+            ArgSpec::required("item", ArgKind::Item),
+            ArgSpec::optional("count", ArgKind::Integer),
+            ArgSpec::optional("player", ArgKind::Player),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: give,
+    });
+    registry.register(Command {
+        name: "time",
+        description: "Shows the time, or moves it forward with set (time of day) or add",
+        args: vec![
+            ArgSpec::optional("set|add", ArgKind::Word),
+            ArgSpec::optional("ticks", ArgKind::Integer),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: time,
+    });
+    registry.register(Command {
+        name: "gamemode",
+        description: "Changes the game mode of a player",
+        args: vec![
+            ArgSpec::required("survival|creative|spectator", ArgKind::Word),
+            ArgSpec::optional("player", ArgKind::Player),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: gamemode,
+    });
+    registry.register(Command {
+        name: "defaultgamemode",
+        description: "Shows or changes the game mode of players joining for the first time",
+        args: vec![ArgSpec::optional(
+            "survival|creative|spectator",
+            ArgKind::Word,
+        )],
+        permission: PermissionLevel::Operator,
+        handler: default_gamemode,
+    });
+    registry.register(Command {
+        name: "randomtickspeed",
+        description: "Shows or changes how many blocks get a random tick in each chunk every tick",
+        args: vec![ArgSpec::optional("speed", ArgKind::Integer)],
+        permission: PermissionLevel::Operator,
+        handler: random_tick_speed,
+    });
+    registry.register(Command {
+        name: "daylength",
+        description: "Shows or changes how many ticks a full day lasts",
+        args: vec![ArgSpec::optional("ticks", ArgKind::Integer)],
+        permission: PermissionLevel::Operator,
+        handler: day_length,
+    });
+    registry.register(Command {
+        name: "weather",
+        description: "Shows the weather, or changes it for some ticks",
+        args: vec![
+            ArgSpec::optional("clear|rain|thunder", ArgKind::Word),
+            ArgSpec::optional("ticks", ArgKind::Integer),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: weather,
+    });
+    registry.register(Command {
+        name: "kick",
+        description: "Disconnects a player from the server",
+        args: vec![
+            ArgSpec::required("player", ArgKind::Player),
+            ArgSpec::optional("reason", ArgKind::Text),
+        ],
+        permission: PermissionLevel::Operator,
+        handler: kick,
+    });
+    registry.register(Command {
+        name: "save",
+        description: "Saves the world",
+        args: vec![],
+        permission: PermissionLevel::Operator,
+        handler: save,
+    });
+    registry.register(Command {
+        name: "seed",
+        description: "Shows the world seed",
+        args: vec![],
+        permission: PermissionLevel::Operator,
+        handler: seed,
+    });
+    registry.register(Command {
+        name: "list",
+        description: "Lists connected players",
+        args: vec![],
+        permission: PermissionLevel::Player,
+        handler: list,
+    });
+}
+
+fn help(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    if let Some(name) = args.word(0) {
+        let name = name.trim_start_matches(COMMAND_PREFIX);
+        let command = context
+            .registry
+            .get(name)
+            .ok_or_else(|| format!("Unknown command: {}", name))?;
+        return Ok(format!("{}\n{}", command.usage(), command.description));
+    }
+
+    let lines: Vec<String> = context
+        .registry
+        .iter()
+        .filter(|command| context.permission >= command.permission)
+        .map(|command| format!("{} : {}", command.usage(), command.description))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn tp(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let target = args.player(3).unwrap_or(context.sender);
+    let position = Vec3::new(
+        args.float(0).unwrap(),
+        args.float(1).unwrap(),
+        args.float(2).unwrap(),
+    );
+
+    let player = context
+        .world_map
+        .players
+        .get_mut(&target)
+        .ok_or_else(|| "Player is not in the world".to_string())?;
+    player.position = position;
+    player.velocity = Vec3::ZERO;
+
+    Ok(format!(
+        "Teleported {} to {:.1} {:.1} {:.1}",
+        player.name, position.x, position.y, position.z
+    ))
+}
+
+fn give(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let item_id = args.item(0).unwrap();
+    let count = args.integer(1).unwrap_or(1);
+    if count <= 0 {
+        return Err("count must be positive".to_string());
+    }
+    // Enough to fill a whole inventory
+    let max_count = item_id.get_max_stack() * MAX_INVENTORY_SLOTS;
+    if count > max_count as i64 {
+        return Err(format!("count must be at most {}", max_count));
+    }
+    let target = args.player(2).unwrap_or(context.sender);
+
+    let player = context
+        .world_map
+        .players
+        .get_mut(&target)
+        .ok_or_else(|| "Player is not in the world".to_string())?;
+    let (name, position) = (player.name.clone(), player.position);
+
+    let stack = ItemStack {
+        item_id,
+        item_type: item_id.get_default_type(),
+        nb: count as u32,
+    };
+    let mut remaining = player.inventory.add_item_to_inventory(stack);
+
+    // Items that do not fit are dropped at the player's feet, in full stacks
+    while remaining > 0 {
+        let nb = remaining.min(item_id.get_max_stack());
+        spawn_item_stack(context.world_map, ItemStack { nb, ..stack }, position);
+        remaining -= nb;
+    }
+
+    Ok(format!("Gave {} {:?} to {}", count, item_id, name))
+}
+
+/// Ticks from `time` to the next tick whose time of day is `time_of_day`
+fn ticks_until_time_of_day(time: u64, time_of_day: u64, day_length: u64) -> u64 {
+    let day_length = day_length.max(1);
+    (time_of_day % day_length + day_length - time % day_length) % day_length
+}
+
+fn time(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let action = match args.word(0) {
+        None => return Ok(format!("Current time: {}", context.time.0)),
+        Some(action) => action,
+    };
+
+    let ticks = args
+        .integer(1)
+        .ok_or_else(|| "Missing argument: ticks".to_string())?;
+    if ticks < 0 {
+        return Err("ticks must be positive".to_string());
+    }
+
+    // Scheduled ticks, cooldowns and chunk unloading rely on the time never going back
+    let ticks = ticks as u64;
+    let forward = match action {
+        "set" => ticks_until_time_of_day(context.time.0, ticks, context.settings.day_length),
+        "add" => ticks,
+        _ => return Err(format!("Unknown action: {}", action)),
+    };
+    context.time.0 = context
+        .time
+        .0
+        .checked_add(forward)
+        .ok_or_else(|| "Time would overflow".to_string())?;
+
+    Ok(format!("Time set to {}", context.time.0))
+}
+
+fn parse_game_mode(name: &str) -> Result<GameMode, String> {
+    GameMode::from_name(name).ok_or_else(|| format!("Unknown game mode: {}", name))
+}
+
+fn gamemode(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let game_mode = parse_game_mode(args.word(0).unwrap())?;
+    let target = args.player(1).unwrap_or(context.sender);
+
+    let player = context
+        .world_map
+        .players
+        .get_mut(&target)
+        .ok_or_else(|| "Player is not in the world".to_string())?;
+    player.set_game_mode(game_mode);
+
+    context.server.send_game_message(
+        target,
+        ServerToClientMessage::PlayerGameMode(PlayerGameModeUpdate { game_mode }),
+    );
+
+    Ok(format!("Set {} to {} mode", player.name, game_mode))
+}
+
+fn default_gamemode(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let Some(name) = args.word(0) else {
+        return Ok(format!(
+            "Default game mode: {}",
+            context.settings.default_game_mode
+        ));
+    };
+
+    context.settings.default_game_mode = parse_game_mode(name)?;
+    // Stored in the level file, which is only written on save
+    context.save_requested = true;
+
+    Ok(format!(
+        "Default game mode set to {}",
+        context.settings.default_game_mode
+    ))
+}
+
+fn random_tick_speed(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let Some(speed) = args.integer(0) else {
+        return Ok(format!(
+            "Random tick speed: {}",
+            context.settings.random_tick_speed
+        ));
+    };
+    if speed < 0 {
+        return Err("speed must be positive".to_string());
+    }
+    if speed > MAX_RANDOM_TICK_SPEED as i64 {
+        return Err(format!("speed must be at most {}", MAX_RANDOM_TICK_SPEED));
+    }
+
+    context.settings.random_tick_speed = speed as u32;
+    context.save_requested = true;
+
+    Ok(format!("Random tick speed set to {}", speed))
+}
+
+fn day_length(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let Some(ticks) = args.integer(0) else {
+        return Ok(format!("Day length: {} ticks", context.settings.day_length));
+    };
+    if ticks <= 0 {
+        return Err("ticks must be strictly positive".to_string());
+    }
+
+    // Sent to clients right away by `broadcast_time`
+    context.settings.day_length = ticks as u64;
+    context.save_requested = true;
+
+    Ok(format!("Day length set to {} ticks", ticks))
+}
+
+fn weather(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let Some(name) = args.word(0) else {
+        return Ok(format!(
+            "Weather: {}, changing in {} ticks",
+            context.weather.weather, context.weather.remaining_ticks
+        ));
+    };
+    let weather = Weather::from_name(name).ok_or_else(|| format!("Unknown weather: {}", name))?;
+
+    let duration = match args.integer(1) {
+        Some(ticks) if ticks <= 0 => return Err("ticks must be strictly positive".to_string()),
+        Some(ticks) => Some(ticks as u64),
+        None => None,
+    };
+
+    // Sent to clients by `sync_weather_system`
+    context.weather.set(weather, duration);
+
+    Ok(format!(
+        "Weather set to {} for {} ticks",
+        weather, context.weather.remaining_ticks
+    ))
+}
+
+fn kick(context: &mut CommandContext, args: &CommandArgs) -> CommandResult {
+    let target = args.player(0).unwrap();
+    let reason = args.text(1).unwrap_or("No reason given");
+
+    // The player is removed from the lobby and the world when the disconnection event is received
+    context.server.disconnect(target);
+
+    let name = context
+        .lobby
+        .players
+        .get(&target)
+        .map(|player| player.name.clone())
+        .unwrap_or_default();
+    info!("Player {} was kicked: {}", name, reason);
+
+    Ok(format!("Kicked {} ({})", name, reason))
+}
+
+fn save(context: &mut CommandContext, _args: &CommandArgs) -> CommandResult {
+    context.save_requested = true;
+    Ok("Saving the world...".to_string())
+}
+
+fn seed(context: &mut CommandContext, _args: &CommandArgs) -> CommandResult {
+    Ok(format!("Seed: {}", context.seed.0))
+}
+
+fn list(context: &mut CommandContext, _args: &CommandArgs) -> CommandResult {
+    let names: Vec<&str> = context
+        .lobby
+        .players
+        .values()
+        .map(|player| player.name.as_str())
+        .collect();
+    Ok(format!(
+        "{} player(s) online: {}",
+        names.len(),
+        names.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_of_day_is_reached_going_forward() {
+        assert_eq!(ticks_until_time_of_day(0, 300, 1200), 300);
+        assert_eq!(ticks_until_time_of_day(2500, 300, 1200), 200);
+        assert_eq!(ticks_until_time_of_day(2500, 50, 1200), 1150);
+        assert_eq!(ticks_until_time_of_day(2500, 100, 1200), 0);
+        assert_eq!(ticks_until_time_of_day(2500, 1300, 1200), 0);
+    }
+}
@@ -0,0 +1,189 @@
+mod args;
+mod builtin;
+mod permissions;
+
+pub use args::*;
+pub use permissions::*;
+
+use bevy::prelude::*;
+use bevy_renet::renet::{ClientId, RenetServer};
+use shared::messages::{ChatConversation, FullChatMessage, PlayerId, ServerToClientMessage};
+use shared::world::{ServerWorldMap, WorldSeed};
+use std::collections::BTreeMap;
+
+use crate::init::{ServerLobby, ServerTime};
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::data::WorldSettings;
+use crate::world::save::SaveRequestEvent;
+use crate::world::weather::WeatherState;
+
+pub const COMMAND_PREFIX: char = '/';
+
+const COMMAND_REPLY_AUTHOR: &str = "Server";
+
+/// Chat line starting with `COMMAND_PREFIX`, sent by a player
+#[derive(Event, Debug)]
+pub struct ChatCommandEvent {
+    pub client_id: ClientId,
+    pub line: String,
+}
+
+/// Message sent back to the player on success, or error message
+pub type CommandResult = Result<String, String>;
+
+pub type CommandHandler = fn(&mut CommandContext, &CommandArgs) -> CommandResult;
+
+/// Everything a command handler can read or update
+pub struct CommandContext<'a> {
+    pub sender: PlayerId,
+    pub permission: PermissionLevel,
+    pub registry: &'a CommandRegistry,
+    pub server: &'a mut RenetServer,
+    pub lobby: &'a mut ServerLobby,
+    pub world_map: &'a mut ServerWorldMap,
+    pub time: &'a mut ServerTime,
+    pub settings: &'a mut WorldSettings,
+    pub weather: &'a mut WeatherState,
+    pub seed: WorldSeed,
+    pub save_requested: bool,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: Vec<ArgSpec>,
+    pub permission: PermissionLevel,
+    pub handler: CommandHandler,
+}
+
+impl Command {
+    pub fn usage(&self) -> String {
+        let mut usage = format!("{}{}", COMMAND_PREFIX, self.name);
+        for arg in self.args.iter() {
+            usage.push(' ');
+            usage.push_str(&arg.usage());
+        }
+        usage
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CommandRegistry {
+    commands: BTreeMap<&'static str, Command>,
+}
+
+impl CommandRegistry {
+    pub fn register(&mut self, command: Command) {
+        self.commands.insert(command.name, command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.get(name)
+    }
+
+    /// All commands, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.values()
+    }
+}
+
+pub fn setup_commands(app: &mut App) {
+    let mut registry = CommandRegistry::default();
+    builtin::register_builtin_commands(&mut registry);
+
+    app.insert_resource(registry);
+    app.add_event::<ChatCommandEvent>();
+}
+
+pub fn execute_command(context: &mut CommandContext, line: &str) -> CommandResult {
+    let line = line.trim().trim_start_matches(COMMAND_PREFIX);
+    let (name, raw_args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+
+    let registry = context.registry;
+    let Some(command) = registry.get(name) else {
+        return Err(format!(
+            "Unknown command: {}. Type {}help to list commands",
+            name, COMMAND_PREFIX
+        ));
+    };
+
+    if context.permission < command.permission {
+        return Err(format!(
+            "You are not allowed to use {}{}",
+            COMMAND_PREFIX, command.name
+        ));
+    }
+
+    let args = parse_args(&command.args, raw_args, context.lobby)
+        .map_err(|e| format!("{}\nUsage: {}", e, command.usage()))?;
+
+    (command.handler)(context, &args)
+}
+
+pub fn send_command_reply(server: &mut RenetServer, client_id: ClientId, content: String) {
+    let timestamp: u64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    server.send_game_message(
+        client_id,
+        ServerToClientMessage::ChatConversation(ChatConversation {
+            messages: vec![FullChatMessage {
+                author: COMMAND_REPLY_AUTHOR.to_string(),
+                content,
+                timestamp,
+            }],
+        }),
+    );
+}
+
+pub fn handle_chat_commands_system(
+    mut events: EventReader<ChatCommandEvent>,
+    (registry, operators, seed): (Res<CommandRegistry>, Res<ServerOperators>, Res<WorldSeed>),
+    (mut server, mut lobby, mut world_map, mut time, mut settings, mut weather): (
+        ResMut<RenetServer>,
+        ResMut<ServerLobby>,
+        ResMut<ServerWorldMap>,
+        ResMut<ServerTime>,
+        ResMut<WorldSettings>,
+        ResMut<WeatherState>,
+    ),
+    mut ev_save: EventWriter<SaveRequestEvent>,
+) {
+    for event in events.read() {
+        let Some(sender) = lobby.players.get(&event.client_id) else {
+            continue;
+        };
+        info!("Command from {}: {}", sender.name, event.line);
+        let permission = operators.permission_level(&sender.name);
+
+        let mut context = CommandContext {
+            sender: event.client_id,
+            permission,
+            registry: &registry,
+            server: &mut server,
+            lobby: &mut lobby,
+            world_map: &mut world_map,
+            time: &mut time,
+            settings: &mut settings,
+            weather: &mut weather,
+            seed: *seed,
+            save_requested: false,
+        };
+
+        let reply = execute_command(&mut context, &event.line);
+
+        if context.save_requested {
+            ev_save.send(SaveRequestEvent);
+        }
+
+        let content = match reply {
+            Ok(content) => content,
+            Err(error) => error,
+        };
+        if !content.is_empty() {
+            send_command_reply(&mut server, event.client_id, content);
+        }
+    }
+}
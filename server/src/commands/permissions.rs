@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use shared::world::get_game_folder;
+use shared::GameFolderPaths;
+use std::collections::HashSet;
+use std::fs;
+
+const OPERATORS_FILE_NAME: &str = "ops.ron";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PermissionLevel {
+    Player,
+    Operator,
+}
+
+/// Players allowed to use operator commands, listed by name in `ops.ron`.\
+/// Players are matched by the name they join with, which the server does not
+/// authenticate: anyone able to connect can claim an operator's name, so only
+/// list operators on servers reachable by trusted players.\
+/// On a solo server, the local player is always an operator.
+#[derive(Resource, Default, Debug)]
+pub struct ServerOperators {
+    names: HashSet<String>,
+    everyone: bool,
+}
+
+impl ServerOperators {
+    pub fn load(game_folder_paths: &GameFolderPaths, is_solo: bool) -> Self {
+        let path = get_game_folder(Some(game_folder_paths)).join(OPERATORS_FILE_NAME);
+
+        let names = match fs::read_to_string(&path) {
+            Ok(contents) => match ron::de::from_str::<Vec<String>>(&contents) {
+                Ok(names) => names.into_iter().collect(),
+                Err(e) => {
+                    error!("Failed to read operators from {}: {}", path.display(), e);
+                    HashSet::new()
+                }
+            },
+            Err(_) => {
+                info!("No operators file found at {}", path.display());
+                HashSet::new()
+            }
+        };
+
+        Self {
+            names,
+            everyone: is_solo,
+        }
+    }
+
+    pub fn permission_level(&self, player_name: &str) -> PermissionLevel {
+        if self.everyone || self.names.contains(player_name) {
+            PermissionLevel::Operator
+        } else {
+            PermissionLevel::Player
+        }
+    }
+}
@@ -14,14 +14,18 @@ use bevy_renet::{
 };
 use serde::{Deserialize, Serialize};
 use shared::{
-    get_shared_renet_config, messages::PlayerId, GameFolderPaths, GameServerConfig,
-    TICKS_PER_SECOND,
+    crafting::RecipeRegistry, get_shared_renet_config, messages::PlayerId, GameFolderPaths,
+    GameServerConfig, TICKS_PER_SECOND,
 };
 use std::fmt::Debug;
 use std::time::{Duration, SystemTime};
 use std::{collections::HashMap, net::IpAddr};
 
-use crate::world::load_from_file::{load_world_map, load_world_seed, load_world_time};
+use crate::commands::ServerOperators;
+use crate::world::data::WorldSettings;
+use crate::world::generation::BiomeNoise;
+use crate::world::load_from_file::load_world_data;
+use crate::world::storage::WorldStorage;
 
 use std::net::{SocketAddr, UdpSocket};
 
@@ -93,13 +97,19 @@ pub fn init(socket: UdpSocket, config: GameServerConfig, game_folder_path: Strin
     app.add_plugins(bevy::log::LogPlugin::default());
 
     app.insert_resource(ServerLobby::default());
-    app.insert_resource(GameFolderPaths {
+    let game_folder_paths = GameFolderPaths {
         game_folder_path: game_folder_path.clone(),
         assets_folder_path: format!("{}/data", game_folder_path),
-    });
+    };
 
     let world_name = &config.world_name.clone();
+    let new_world_settings = WorldSettings {
+        default_game_mode: config.new_world_game_mode,
+        ..Default::default()
+    };
 
+    app.insert_resource(ServerOperators::load(&game_folder_paths, config.is_solo));
+    app.insert_resource(RecipeRegistry::load(&game_folder_paths));
     app.insert_resource(config);
 
     info!("Starting server on {}", socket.local_addr().unwrap());
@@ -109,39 +119,32 @@ pub fn init(socket: UdpSocket, config: GameServerConfig, game_folder_path: Strin
     setup_resources_and_events(&mut app);
 
     // Load world from files
-    let mut world_map = match load_world_map(world_name, &app) {
+    let mut storage = WorldStorage::new(&game_folder_paths, world_name);
+    let (world_data, mut world_map) = match load_world_data(
+        world_name,
+        &mut storage,
+        &game_folder_paths,
+        new_world_settings,
+    ) {
         Ok(world) => world,
         Err(e) => {
-            error!("Error loading world: {}. Generating a new one.", e);
-            panic!();
-        }
-    };
-
-    let world_seed = match load_world_seed(world_name, &app) {
-        Ok(seed) => {
-            info!("World seed loaded successfully: {}", seed.0);
-            seed
-        }
-        Err(e) => {
-            error!("Error loading seed: {}. Generating a new one.", e);
+            error!("Error loading world: {}", e);
             panic!();
         }
     };
-
-    let server_time = match load_world_time(world_name, &app) {
-        Ok(time) => time,
-        Err(e) => {
-            error!("Error loading time: {}. Defaulting to 0.", e);
-            0
-        }
-    };
+    info!("World seed loaded successfully: {}", world_data.seed.0);
 
     cleanup_all_players_from_world(&mut world_map);
 
     // Insert world_map and seed into ressources
+    app.insert_resource(game_folder_paths);
+    app.insert_resource(storage);
     app.insert_resource(world_map);
-    app.insert_resource(world_seed);
-    app.insert_resource(ServerTime(server_time));
+    app.insert_resource(BiomeNoise::new(world_data.seed.0));
+    app.insert_resource(world_data.seed);
+    app.insert_resource(ServerTime(world_data.time));
+    app.insert_resource(world_data.settings);
+    app.insert_resource(world_data.weather);
 
     dispatcher::register_systems(&mut app);
 
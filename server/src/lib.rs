@@ -1,3 +1,4 @@
+mod commands;
 mod init;
 mod mob;
 mod network;
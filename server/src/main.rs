@@ -2,8 +2,10 @@ use std::net::Ipv4Addr;
 
 use crate::init::acquire_socket_by_port;
 use clap::Parser;
+use shared::players::GameMode;
 use shared::GameServerConfig;
 
+mod commands;
 mod init;
 mod mob;
 mod network;
@@ -20,6 +22,14 @@ struct Args {
 
     #[arg(short, long, default_value = "../")]
     game_folder_path: String,
+
+    /// Game mode of new players, only used when creating the world
+    #[arg(long, default_value = "survival", value_parser = parse_game_mode)]
+    game_mode: GameMode,
+}
+
+fn parse_game_mode(name: &str) -> Result<GameMode, String> {
+    GameMode::from_name(name).ok_or_else(|| format!("unknown game mode: {}", name))
 }
 
 fn main() {
@@ -33,6 +43,7 @@ fn main() {
         GameServerConfig {
             world_name: args.world,
             is_solo: false,
+            new_world_game_mode: args.game_mode,
         },
         game_folder_path,
     );
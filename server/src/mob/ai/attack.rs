@@ -0,0 +1,47 @@
+use shared::messages::DamageSource;
+use shared::players::Player;
+use shared::world::{MobAction, MobTarget};
+
+use super::{Behavior, BehaviorContext};
+
+/// Chasing mobs give up once the player is this many times further than `range`
+const CHASE_HYSTERESIS: f32 = 1.5;
+
+fn can_be_attacked(player: &Player) -> bool {
+    player.game_mode.takes_damage_from(DamageSource::Mob)
+}
+
+/// Chases the closest player that can be hurt, the attack itself is dealt by the health systems
+pub struct AttackPlayer {
+    /// Distance at which the mob notices players, in blocks
+    pub range: f32,
+}
+
+impl AttackPlayer {
+    fn get_range(&self, is_running: bool) -> f32 {
+        if is_running {
+            self.range * CHASE_HYSTERESIS
+        } else {
+            self.range
+        }
+    }
+}
+
+impl Behavior for AttackPlayer {
+    fn can_run(&self, context: &BehaviorContext, is_running: bool) -> bool {
+        context
+            .find_player(self.get_range(is_running), can_be_attacked)
+            .is_some()
+    }
+
+    fn run(&self, context: &mut BehaviorContext, _is_starting: bool) {
+        let Some(player) = context
+            .find_player(self.get_range(true), can_be_attacked)
+            .map(|player| player.id)
+        else {
+            return;
+        };
+        context.mob.target = MobTarget::Player(player);
+        context.mob.action = MobAction::Attack;
+    }
+}
@@ -0,0 +1,34 @@
+use shared::world::{MobAction, MobTarget};
+
+use super::{Behavior, BehaviorContext};
+
+/// Running mobs keep fleeing until the player is this many times further than `range`
+const FLEE_HYSTERESIS: f32 = 2.0;
+
+/// Runs away from the players coming close
+pub struct FleePlayer {
+    /// Distance at which players scare the mob, in blocks
+    pub range: f32,
+}
+
+impl Behavior for FleePlayer {
+    fn can_run(&self, context: &BehaviorContext, is_running: bool) -> bool {
+        let range = if is_running {
+            self.range * FLEE_HYSTERESIS
+        } else {
+            self.range
+        };
+        context.find_player(range, |_| true).is_some()
+    }
+
+    fn run(&self, context: &mut BehaviorContext, _is_starting: bool) {
+        let Some(player) = context
+            .find_player(self.range * FLEE_HYSTERESIS, |_| true)
+            .map(|player| player.id)
+        else {
+            return;
+        };
+        context.mob.target = MobTarget::Player(player);
+        context.mob.action = MobAction::Flee;
+    }
+}
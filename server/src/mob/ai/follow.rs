@@ -0,0 +1,33 @@
+use bevy::math::{ops::atan2, Quat};
+use shared::world::{MobAction, MobTarget};
+
+use super::{Behavior, BehaviorContext};
+
+/// Walks after the closest player, stopping right next to them
+pub struct FollowPlayer {
+    /// Distance at which the mob notices players, in blocks
+    pub range: f32,
+    /// The mob stands still this close to the player, in blocks
+    pub stop_distance: f32,
+}
+
+impl Behavior for FollowPlayer {
+    fn can_run(&self, context: &BehaviorContext, _is_running: bool) -> bool {
+        context.find_player(self.range, |_| true).is_some()
+    }
+
+    fn run(&self, context: &mut BehaviorContext, _is_starting: bool) {
+        let Some(player) = context.find_player(self.range, |_| true) else {
+            return;
+        };
+        let (id, offset) = (player.id, player.position - context.mob.position);
+
+        context.mob.target = MobTarget::Player(id);
+        if offset.length() < self.stop_distance {
+            context.mob.action = MobAction::Idle;
+            context.mob.rotation = Quat::from_rotation_y(atan2(offset.x, offset.z));
+        } else {
+            context.mob.action = MobAction::Walk;
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::math::Quat;
+use rand::Rng;
+use shared::world::{MobAction, MobTarget};
+
+use super::{Behavior, BehaviorContext};
+
+/// Ticks between two turns of the head
+const LOOK_INTERVAL: u64 = 20;
+
+/// Stands still and turns to random directions for a while
+pub struct LookAround {
+    /// Chance of starting to look around on each tick
+    pub chance: f64,
+    /// In ticks
+    pub duration: u64,
+}
+
+impl Behavior for LookAround {
+    fn can_run(&self, context: &BehaviorContext, is_running: bool) -> bool {
+        if is_running {
+            context.running_ticks() < self.duration
+        } else {
+            rand::thread_rng().gen_bool(self.chance)
+        }
+    }
+
+    fn run(&self, context: &mut BehaviorContext, is_starting: bool) {
+        context.mob.action = MobAction::Idle;
+        context.mob.target = MobTarget::None;
+
+        if is_starting || context.running_ticks().is_multiple_of(LOOK_INTERVAL) {
+            let yaw = rand::thread_rng().gen_range(0.0..std::f32::consts::TAU);
+            context.mob.rotation = Quat::from_rotation_y(yaw);
+        }
+    }
+}
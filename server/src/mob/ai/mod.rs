@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use shared::players::Player;
+use shared::world::{MobAction, MobId, MobKind, MobTarget, ServerMob, ServerWorldMap};
+use std::collections::HashMap;
+
+use crate::init::ServerTime;
+
+mod attack;
+mod flee;
+mod follow;
+mod look_around;
+mod wander;
+
+pub use attack::*;
+pub use flee::*;
+pub use follow::*;
+pub use look_around::*;
+pub use wander::*;
+
+/// What a mob knows while deciding what to do
+pub struct BehaviorContext<'a> {
+    pub mob: &'a mut ServerMob,
+    /// World as it was at the start of the tick, the mob itself included
+    pub world_map: &'a ServerWorldMap,
+    pub time: u64,
+    /// Tick at which the behaviour being run was started
+    pub started_at: u64,
+}
+
+impl BehaviorContext<'_> {
+    /// Ticks since the behaviour being run was started
+    pub fn running_ticks(&self) -> u64 {
+        self.time.saturating_sub(self.started_at)
+    }
+
+    /// Closest living player within `range` of the mob and accepted by `filter`
+    pub fn find_player(&self, range: f32, filter: impl Fn(&Player) -> bool) -> Option<&Player> {
+        self.world_map
+            .players
+            .values()
+            .filter(|player| !player.is_dead() && player.game_mode.can_interact())
+            .filter(|player| filter(player))
+            .map(|player| (player, player.position.distance(self.mob.position)))
+            .filter(|(_, distance)| *distance < range)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(player, _)| player)
+    }
+}
+
+/// A small piece of mob AI, such as wandering or attacking\
+/// The behaviours of a kind of mob are checked in priority order every tick,
+/// the first one that can run controls the mob
+pub trait Behavior: Send + Sync {
+    /// Whether the behaviour wants to control the mob, `is_running` tells if it already does
+    fn can_run(&self, context: &BehaviorContext, is_running: bool) -> bool;
+
+    /// Updates the action and target of the mob, `is_starting` is set on the first tick
+    fn run(&self, context: &mut BehaviorContext, is_starting: bool);
+}
+
+/// Behaviours of every kind of mob, from the highest priority to the lowest
+#[derive(Resource, Default)]
+pub struct MobBehaviors {
+    behaviors: HashMap<MobKind, Vec<Box<dyn Behavior>>>,
+}
+
+impl MobBehaviors {
+    pub fn register(&mut self, kind: MobKind, behavior: impl Behavior + 'static) {
+        self.behaviors
+            .entry(kind)
+            .or_default()
+            .push(Box::new(behavior));
+    }
+
+    fn get(&self, kind: &MobKind) -> &[Box<dyn Behavior>] {
+        self.behaviors
+            .get(kind)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Behaviour a mob is running
+#[derive(Debug, Default)]
+struct MobBrain {
+    /// Index of the behaviour among the ones of the mob kind
+    running: Option<usize>,
+    started_at: u64,
+}
+
+#[derive(Resource, Default)]
+pub struct MobBrains {
+    brains: HashMap<MobId, MobBrain>,
+}
+
+fn register_builtin_behaviors(behaviors: &mut MobBehaviors) {
+    behaviors.register(MobKind::Fox, FleePlayer { range: 6.0 });
+    behaviors.register(
+        MobKind::Fox,
+        Wander {
+            radius: 8,
+            chance: 0.02,
+        },
+    );
+    behaviors.register(
+        MobKind::Fox,
+        LookAround {
+            chance: 0.02,
+            duration: 60,
+        },
+    );
+
+    behaviors.register(
+        MobKind::Pig,
+        FollowPlayer {
+            range: 6.0,
+            stop_distance: 2.0,
+        },
+    );
+    behaviors.register(
+        MobKind::Pig,
+        Wander {
+            radius: 6,
+            chance: 0.01,
+        },
+    );
+    behaviors.register(
+        MobKind::Pig,
+        LookAround {
+            chance: 0.02,
+            duration: 80,
+        },
+    );
+
+    behaviors.register(MobKind::Zombie, AttackPlayer { range: 16.0 });
+    behaviors.register(
+        MobKind::Zombie,
+        Wander {
+            radius: 10,
+            chance: 0.01,
+        },
+    );
+    behaviors.register(
+        MobKind::Zombie,
+        LookAround {
+            chance: 0.01,
+            duration: 40,
+        },
+    );
+}
+
+pub fn setup_mob_ai(app: &mut App) {
+    let mut behaviors = MobBehaviors::default();
+    register_builtin_behaviors(&mut behaviors);
+
+    app.insert_resource(behaviors);
+    app.insert_resource(MobBrains::default());
+}
+
+/// Picks the behaviour controlling each mob and runs it
+pub fn mob_ai_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    behaviors: Res<MobBehaviors>,
+    mut brains: ResMut<MobBrains>,
+    time: Res<ServerTime>,
+) {
+    brains
+        .brains
+        .retain(|id, _| world_map.mobs.contains_key(id));
+
+    let mut mobs = world_map.mobs.clone();
+    for (id, mob) in mobs.iter_mut() {
+        let brain = brains.brains.entry(*id).or_default();
+        let kind_behaviors = behaviors.get(&mob.kind);
+
+        let mut context = BehaviorContext {
+            mob,
+            world_map: &world_map,
+            time: time.0,
+            started_at: brain.started_at,
+        };
+
+        let selected = kind_behaviors
+            .iter()
+            .enumerate()
+            .find(|(index, behavior)| behavior.can_run(&context, brain.running == Some(*index)))
+            .map(|(index, _)| index);
+
+        let is_starting = selected != brain.running;
+        if is_starting {
+            brain.running = selected;
+            brain.started_at = time.0;
+            context.started_at = time.0;
+        }
+
+        match selected {
+            Some(index) => kind_behaviors[index].run(&mut context, is_starting),
+            None => {
+                context.mob.action = MobAction::Idle;
+                context.mob.target = MobTarget::None;
+            }
+        }
+    }
+
+    world_map.mobs = mobs;
+}
@@ -0,0 +1,74 @@
+use bevy::math::{IVec3, Vec3Swizzles};
+use rand::Rng;
+use shared::world::{MobAction, MobTarget};
+use shared::TICKS_PER_SECOND;
+
+use crate::mob::pathfinding::{find_ground, get_cell_feet_position, get_feet_cell, MAX_DROP_DOWN};
+
+use super::{Behavior, BehaviorContext};
+
+/// Wandering mobs give up on a destination they could not reach after this many ticks
+const WANDER_TIMEOUT: u64 = 10 * TICKS_PER_SECOND;
+/// Mobs closer than this to their destination have reached it
+const WANDER_REACH: f32 = 1.0;
+/// Random destinations tried before giving up for the tick
+const WANDER_ATTEMPTS: usize = 10;
+
+/// Walks to random places around the mob from time to time
+pub struct Wander {
+    /// Largest horizontal distance of a destination, in blocks
+    pub radius: i32,
+    /// Chance of starting to wander on each tick
+    pub chance: f64,
+}
+
+impl Behavior for Wander {
+    fn can_run(&self, context: &BehaviorContext, is_running: bool) -> bool {
+        if !is_running {
+            return rand::thread_rng().gen_bool(self.chance);
+        }
+
+        match context.mob.target {
+            MobTarget::Position(destination) => {
+                context.mob.position.xz().distance(destination.xz()) > WANDER_REACH
+                    && context.running_ticks() < WANDER_TIMEOUT
+            }
+            _ => false,
+        }
+    }
+
+    fn run(&self, context: &mut BehaviorContext, is_starting: bool) {
+        if !is_starting {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let feet = get_feet_cell(&context.mob.position);
+        let height = context.mob.kind.get_height_in_blocks();
+
+        let destination = (0..WANDER_ATTEMPTS).find_map(|_| {
+            let offset = IVec3::new(
+                rng.gen_range(-self.radius..=self.radius),
+                MAX_DROP_DOWN,
+                rng.gen_range(-self.radius..=self.radius),
+            );
+            find_ground(
+                &context.world_map.chunks,
+                &(feet + offset),
+                height,
+                MAX_DROP_DOWN * 2,
+            )
+        });
+
+        match destination {
+            Some(cell) => {
+                context.mob.target = MobTarget::Position(get_cell_feet_position(&cell));
+                context.mob.action = MobAction::Walk;
+            }
+            None => {
+                context.mob.target = MobTarget::None;
+                context.mob.action = MobAction::Idle;
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy_renet::renet::RenetServer;
+use rand::Rng;
+use shared::messages::mob::MobHitEvent;
+use shared::messages::{PlayerId, ServerToClientMessage};
+use shared::world::{ItemStack, MobId, ServerMob, ServerWorldMap};
+use std::collections::HashMap;
+
+use crate::init::ServerTime;
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::stacks::spawn_item_stack;
+
+/// Players hit mobs whose hitbox is at most this far from their own position\
+/// A bit longer than the reach of the clients, which measure it from the camera
+const PLAYER_ATTACK_REACH: f32 = 6.0;
+/// Ticks between two hits of the same player
+const PLAYER_ATTACK_COOLDOWN: u64 = 10;
+const PLAYER_ATTACK_DAMAGE: u32 = 4;
+
+/// Velocity given to a hit mob, away from the player, in blocks per second
+const KNOCKBACK_SPEED: f32 = 6.0;
+const KNOCKBACK_UP_SPEED: f32 = 5.0;
+
+/// Sent when a player asks to hit a mob
+#[derive(Event, Debug)]
+pub struct MobAttackEvent {
+    pub player_id: PlayerId,
+    pub mob_id: MobId,
+}
+
+/// Last tick at which each player hit a mob
+#[derive(Resource, Default)]
+pub struct PlayerAttackCooldowns {
+    last_attack: HashMap<PlayerId, u64>,
+}
+
+/// Distance from `position` to the closest point of the hitbox of the mob
+fn get_distance_to_hitbox(position: &Vec3, mob: &ServerMob) -> f32 {
+    let size = mob.kind.get_hitbox_size();
+    let min = mob.position - Vec3::new(size.x, 0.0, size.z) / 2.0;
+    let max = mob.position + Vec3::new(size.x / 2.0, size.y, size.z / 2.0);
+    position.clamp(min, max).distance(*position)
+}
+
+/// Removes a dead mob from the world, dropping its loot where it stood
+fn kill_mob(world_map: &mut ServerWorldMap, id: &MobId) {
+    let Some(mob) = world_map.mobs.remove(id) else {
+        return;
+    };
+    info!("Mob {} ({:?}) died", id, mob.kind);
+
+    let mut rng = rand::thread_rng();
+    for (item_id, count) in mob.kind.get_loot_table() {
+        let nb = rng.gen_range(count);
+        if nb == 0 {
+            continue;
+        }
+        spawn_item_stack(
+            world_map,
+            ItemStack {
+                item_id,
+                item_type: item_id.get_default_type(),
+                nb,
+            },
+            mob.position + Vec3::Y * 0.5,
+        );
+    }
+}
+
+/// Applies the hits of the players on mobs, once checked against their reach and cooldown
+pub fn player_attack_mobs_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut server: ResMut<RenetServer>,
+    mut cooldowns: ResMut<PlayerAttackCooldowns>,
+    mut ev_attack: EventReader<MobAttackEvent>,
+    time: Res<ServerTime>,
+) {
+    let world_map = world_map.as_mut();
+
+    cooldowns
+        .last_attack
+        .retain(|id, _| world_map.players.contains_key(id));
+
+    for event in ev_attack.read() {
+        let Some(player) = world_map.players.get(&event.player_id) else {
+            continue;
+        };
+        if player.is_dead() || !player.game_mode.can_interact() {
+            continue;
+        }
+
+        let last_attack = cooldowns.last_attack.get(&event.player_id).copied();
+        if last_attack.is_some_and(|tick| time.0.saturating_sub(tick) < PLAYER_ATTACK_COOLDOWN) {
+            continue;
+        }
+
+        let player_position = player.position;
+        let Some(mob) = world_map.mobs.get_mut(&event.mob_id) else {
+            continue;
+        };
+        if get_distance_to_hitbox(&player_position, mob) > PLAYER_ATTACK_REACH {
+            debug!(
+                "Player {} tried to hit mob {} out of reach",
+                event.player_id, event.mob_id
+            );
+            continue;
+        }
+
+        cooldowns.last_attack.insert(event.player_id, time.0);
+
+        mob.health = mob.health.saturating_sub(PLAYER_ATTACK_DAMAGE);
+        let away = (mob.position - player_position)
+            .with_y(0.)
+            .normalize_or_zero();
+        mob.velocity = away * KNOCKBACK_SPEED + Vec3::Y * KNOCKBACK_UP_SPEED;
+        mob.on_ground = false;
+
+        debug!(
+            "Player {} hit mob {}, {} health left",
+            event.player_id, event.mob_id, mob.health
+        );
+        server.broadcast_game_message(ServerToClientMessage::MobHit(MobHitEvent {
+            id: event.mob_id,
+            health: mob.health,
+        }));
+
+        if mob.health == 0 {
+            kill_mob(world_map, &event.mob_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::world::{ItemId, MobAction, MobKind, MobTarget};
+
+    fn pig_at(position: Vec3) -> ServerMob {
+        ServerMob {
+            kind: MobKind::Pig,
+            target: MobTarget::None,
+            action: MobAction::Idle,
+            position,
+            rotation: Quat::IDENTITY,
+            velocity: Vec3::ZERO,
+            on_ground: true,
+            health: MobKind::Pig.get_max_health(),
+        }
+    }
+
+    #[test]
+    fn distance_is_measured_to_the_hitbox() {
+        let pig = pig_at(Vec3::new(0.0, 10.0, 0.0));
+
+        assert_eq!(
+            get_distance_to_hitbox(&Vec3::new(0.0, 10.5, 0.0), &pig),
+            0.0
+        );
+        assert!((get_distance_to_hitbox(&Vec3::new(3.45, 10.5, 0.0), &pig) - 3.0).abs() < 1e-4);
+        assert!((get_distance_to_hitbox(&Vec3::new(0.0, 12.9, 0.0), &pig) - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn killed_mobs_drop_their_loot() {
+        let mut world_map = ServerWorldMap::default();
+        world_map.mobs.insert(1, pig_at(Vec3::new(4.0, 10.0, 4.0)));
+
+        kill_mob(&mut world_map, &1);
+
+        assert!(world_map.mobs.is_empty());
+        let porkchops: u32 = world_map
+            .item_stacks
+            .iter()
+            .filter(|stack| stack.stack.item_id == ItemId::RawPorkchop)
+            .map(|stack| stack.stack.nb)
+            .sum();
+        assert!((1..=3).contains(&porkchops));
+    }
+}
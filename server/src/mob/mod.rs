@@ -1,33 +1,12 @@
-pub mod behavior;
+pub mod ai;
+pub mod combat;
+pub mod movement;
+pub mod pathfinding;
+pub mod physics;
+pub mod spawning;
 
-use bevy::prelude::*;
-use shared::world::{MobAction, MobKind, MobTarget, ServerMob, ServerWorldMap};
 use ulid::Ulid;
 
-use crate::init::ServerTime;
-
 fn create_new_mob_id() -> u128 {
     Ulid::new().0
 }
-
-pub fn manage_mob_spawning_system(mut world_map: ResMut<ServerWorldMap>, time: Res<ServerTime>) {
-    if time.0 == 100 {
-        debug!("Should spawn mob");
-
-        let id = create_new_mob_id();
-
-        let position = Vec3::new(0.0, 90.0, 0.0);
-
-        let mob = ServerMob {
-            kind: MobKind::Fox,
-            position,
-            target: MobTarget::Player(*world_map.players.keys().next().unwrap()),
-            action: MobAction::Walk,
-            rotation: Quat::IDENTITY,
-        };
-
-        info!("Spawning new mob on server: {:?}", mob);
-
-        world_map.mobs.insert(id, mob);
-    }
-}
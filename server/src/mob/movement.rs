@@ -0,0 +1,97 @@
+use bevy::{
+    math::{ops::atan2, Quat, Vec3},
+    time::{Fixed, Time},
+};
+use bevy_ecs::system::{Res, ResMut};
+use shared::world::{MobAction, MobId, MobTarget, ServerMob, ServerWorldMap};
+
+use super::pathfinding::{get_cell_feet_position, get_feet_cell, MobPaths};
+
+/// In blocks per second
+const WALK_SPEED: f32 = 2.0;
+/// Enough to climb a block, in blocks per second
+const JUMP_VELOCITY: f32 = 9.0;
+/// Mobs standing in the cell of a waypoint and closer than this to its middle have reached it
+const WAYPOINT_REACH: f32 = 0.2;
+/// Fleeing mobs stop once this far from their target
+const FLEE_DISTANCE: f32 = 15.0;
+
+fn get_target_position(world_map: &ServerWorldMap, target: &MobTarget) -> Option<Vec3> {
+    match *target {
+        MobTarget::Position(pos) => Some(pos),
+        MobTarget::None => None,
+        MobTarget::Player(id) => world_map.players.get(&id).map(|player| player.position),
+        MobTarget::Mob(id) => world_map.mobs.get(&id).map(|mob| mob.position),
+    }
+}
+
+/// Where the mob is heading, used as the goal of its path
+pub fn get_destination(world_map: &ServerWorldMap, mob: &ServerMob) -> Option<Vec3> {
+    let target = get_target_position(world_map, &mob.target)?;
+
+    match mob.action {
+        MobAction::Walk | MobAction::Attack => Some(target),
+        MobAction::Flee => {
+            let away = (mob.position - target).with_y(0.).normalize_or_zero();
+            if mob.position.distance(target) < FLEE_DISTANCE && away != Vec3::ZERO {
+                Some(mob.position + away * FLEE_DISTANCE)
+            } else {
+                None
+            }
+        }
+        MobAction::Idle => None,
+    }
+}
+
+/// Steers the mob along its path, jumping to climb onto higher cells\
+/// Returns the horizontal direction the mob walks in
+fn follow_path(mob: &mut ServerMob, paths: &mut MobPaths, id: &MobId, delta: f32) -> Option<Vec3> {
+    let path = paths.paths.get_mut(id)?;
+    let feet = get_feet_cell(&mob.position);
+
+    while let Some(next) = path.waypoints.front() {
+        let horizontal = (get_cell_feet_position(next) - mob.position).with_y(0.);
+        if feet == *next && horizontal.length() < WAYPOINT_REACH {
+            path.waypoints.pop_front();
+            continue;
+        }
+
+        // Slows down right before the waypoint instead of going past it
+        let speed = WALK_SPEED.min(horizontal.length() / delta);
+        let direction = horizontal.normalize_or_zero();
+        mob.velocity.x = direction.x * speed;
+        mob.velocity.z = direction.z * speed;
+
+        if next.y > feet.y && mob.on_ground {
+            mob.velocity.y = JUMP_VELOCITY;
+        }
+        return (direction != Vec3::ZERO).then_some(direction);
+    }
+    None
+}
+
+/// Moves the mobs towards the destination chosen by their behaviours
+pub fn mob_movement_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut paths: ResMut<MobPaths>,
+    delta: Res<Time<Fixed>>,
+) {
+    let mut mobs = world_map.mobs.clone();
+    let delta = delta.delta_secs();
+
+    for (mob_id, mob) in mobs.iter_mut() {
+        // Mobs cannot steer in the air, so knockbacks and jumps keep their momentum
+        if !mob.on_ground {
+            continue;
+        }
+
+        if let Some(dir) = follow_path(mob, &mut paths, mob_id, delta) {
+            mob.rotation = Quat::from_rotation_y(atan2(dir.x, dir.z));
+        } else {
+            mob.velocity.x = 0.0;
+            mob.velocity.z = 0.0;
+        }
+    }
+
+    world_map.mobs = mobs;
+}
@@ -0,0 +1,371 @@
+use bevy::prelude::*;
+use shared::world::{
+    global_block_to_chunk_pos, BlockTransparency, MobId, ServerChunkWorldMap, ServerWorldMap,
+    WorldMap, HORIZONTAL_NEIGHBOURS,
+};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::init::ServerTime;
+
+use super::movement::get_destination;
+
+/// Mobs climb at most this many blocks at once
+pub const MAX_STEP_UP: i32 = 1;
+/// Mobs jump down at most this many blocks at once
+pub const MAX_DROP_DOWN: i32 = 3;
+/// Mobs in the air look this far down for the ground they will land on
+const MAX_FALL_SEARCH: i32 = 64;
+
+/// Cells explored by a single search, the path to the closest cell reached is used past it
+const MAX_SEARCH_NODES: usize = 1024;
+/// Cells explored by all the searches of a tick, the other mobs search on the next ticks\
+/// The next tick starts with the first mob left out, so every mob gets its turn
+const MAX_PATHFINDING_NODES_PER_TICK: usize = 4096;
+/// Paths are searched again when their goal moves further than this
+const REPATH_DISTANCE: i32 = 2;
+/// Paths are searched again this often, in case the world changed
+const REPATH_INTERVAL: u64 = 40;
+
+/// Costs of the moves between cells, a straight step costs `STEP_COST`
+const STEP_COST: u32 = 10;
+const STEP_UP_COST: u32 = 5;
+const DROP_COST_PER_BLOCK: u32 = 2;
+
+/// Cells a mob walks through to reach its destination, from the next one to the last one
+#[derive(Debug, Clone)]
+pub struct MobPath {
+    pub goal: IVec3,
+    pub waypoints: VecDeque<IVec3>,
+    /// Tick at which the path was searched
+    pub computed_at: u64,
+}
+
+#[derive(Resource, Default)]
+pub struct MobPaths {
+    pub paths: HashMap<MobId, MobPath>,
+    /// First mob that could not search its path in the last tick, it goes first in the next one
+    pub next_search: Option<MobId>,
+}
+
+/// Cells of chunks that are not loaded are neither passable nor ground, so that searches stay in loaded chunks
+fn is_loaded(world: &ServerChunkWorldMap, cell: &IVec3) -> bool {
+    world.map.contains_key(&global_block_to_chunk_pos(cell))
+}
+
+/// Whether a mob can be inside the cell
+fn is_passable(world: &ServerChunkWorldMap, cell: &IVec3) -> bool {
+    is_loaded(world, cell)
+        && world.get_block_by_coordinates(cell).is_none_or(|block| {
+            !block.id.has_hitbox() && block.id.get_visibility() != BlockTransparency::Liquid
+        })
+}
+
+/// Whether a mob `height` blocks tall can stand with its feet in the cell
+pub fn is_walkable(world: &ServerChunkWorldMap, cell: &IVec3, height: i32) -> bool {
+    let ground = *cell - IVec3::Y;
+    let has_ground = is_loaded(world, &ground)
+        && world
+            .get_block_by_coordinates(&ground)
+            .is_some_and(|block| block.id.has_hitbox());
+    has_ground && (0..height).all(|dy| is_passable(world, &(*cell + IVec3::Y * dy)))
+}
+
+/// Cell a mob standing at `position` has its feet in, mobs stand at the bottom of their cell
+pub fn get_feet_cell(position: &Vec3) -> IVec3 {
+    (*position + Vec3::Y * 0.5).round().as_ivec3()
+}
+
+/// Position of the feet of a mob standing in the middle of the cell
+pub fn get_cell_feet_position(cell: &IVec3) -> Vec3 {
+    cell.as_vec3() - Vec3::Y * 0.5
+}
+
+/// First walkable cell at or below `cell`, looking at most `max_depth` blocks down
+pub fn find_ground(
+    world: &ServerChunkWorldMap,
+    cell: &IVec3,
+    height: i32,
+    max_depth: i32,
+) -> Option<IVec3> {
+    (0..=max_depth)
+        .map(|dy| *cell - IVec3::Y * dy)
+        .find(|cell| is_walkable(world, cell, height))
+}
+
+/// Cells reachable from `cell` in a single move, with the cost of the move
+fn get_moves(world: &ServerChunkWorldMap, cell: &IVec3, height: i32) -> Vec<(IVec3, u32)> {
+    let mut moves = Vec::new();
+    let has_headroom =
+        |up: i32| (height..height + up).all(|dy| is_passable(world, &(*cell + IVec3::Y * dy)));
+
+    for offset in HORIZONTAL_NEIGHBOURS.iter() {
+        let next = *cell + *offset;
+
+        if is_walkable(world, &next, height) {
+            moves.push((next, STEP_COST));
+            continue;
+        }
+
+        // Climbing needs room above the mob before it moves forward
+        let step_up = (1..=MAX_STEP_UP)
+            .find(|up| has_headroom(*up) && is_walkable(world, &(next + IVec3::Y * *up), height));
+        if let Some(up) = step_up {
+            moves.push((next + IVec3::Y * up, STEP_COST + STEP_UP_COST * up as u32));
+            continue;
+        }
+
+        // Dropping down needs the whole mob to fit in front of it first
+        if !(0..height).all(|dy| is_passable(world, &(next + IVec3::Y * dy))) {
+            continue;
+        }
+        for down in 1..=MAX_DROP_DOWN {
+            let below = next - IVec3::Y * down;
+            if is_walkable(world, &below, height) {
+                moves.push((below, STEP_COST + DROP_COST_PER_BLOCK * down as u32));
+                break;
+            }
+            if !is_passable(world, &below) {
+                break;
+            }
+        }
+    }
+    moves
+}
+
+fn estimate_cost(from: &IVec3, to: &IVec3) -> u32 {
+    STEP_COST * ((from.x - to.x).unsigned_abs() + (from.z - to.z).unsigned_abs())
+}
+
+/// Result of an A* search over the walkable cells
+pub struct PathSearch {
+    /// Cells to walk through, ending on the goal or on the closest cell reached if it could not be reached
+    pub waypoints: VecDeque<IVec3>,
+    pub explored_nodes: usize,
+}
+
+/// Searches a path for a mob `height` blocks tall, exploring at most `max_nodes` cells
+pub fn find_path(
+    world: &ServerChunkWorldMap,
+    start: &IVec3,
+    goal: &IVec3,
+    height: i32,
+    max_nodes: usize,
+) -> PathSearch {
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec3, IVec3> = HashMap::new();
+    let mut costs: HashMap<IVec3, u32> = HashMap::new();
+
+    let mut closest = (estimate_cost(start, goal), *start);
+    let mut explored_nodes = 0;
+
+    costs.insert(*start, 0);
+    open.push(Reverse((estimate_cost(start, goal), start.to_array())));
+
+    while let Some(Reverse((_, cell))) = open.pop() {
+        let cell = IVec3::from(cell);
+        if cell == *goal {
+            closest = (0, cell);
+            break;
+        }
+        if explored_nodes >= max_nodes {
+            break;
+        }
+        explored_nodes += 1;
+
+        let cost = costs[&cell];
+        for (next, move_cost) in get_moves(world, &cell, height) {
+            let next_cost = cost + move_cost;
+            if costs.get(&next).is_some_and(|known| *known <= next_cost) {
+                continue;
+            }
+            costs.insert(next, next_cost);
+            came_from.insert(next, cell);
+
+            let estimate = estimate_cost(&next, goal);
+            if estimate < closest.0 {
+                closest = (estimate, next);
+            }
+            open.push(Reverse((next_cost + estimate, next.to_array())));
+        }
+    }
+
+    let mut waypoints = VecDeque::new();
+    let mut cell = closest.1;
+    while cell != *start {
+        waypoints.push_front(cell);
+        cell = came_from[&cell];
+    }
+
+    PathSearch {
+        waypoints,
+        explored_nodes,
+    }
+}
+
+/// Searches new paths for the mobs whose path is missing, outdated or blocked
+pub fn mob_pathfinding_system(
+    world_map: Res<ServerWorldMap>,
+    mut paths: ResMut<MobPaths>,
+    time: Res<ServerTime>,
+) {
+    paths.paths.retain(|id, _| world_map.mobs.contains_key(id));
+
+    // Mobs are taken in the order of their ids, starting with the one left out last tick
+    let mut ids: Vec<MobId> = world_map.mobs.keys().copied().collect();
+    ids.sort_unstable();
+    let first = paths
+        .next_search
+        .take()
+        .map_or(0, |next| ids.partition_point(|id| *id < next));
+    ids.rotate_left(first);
+
+    let mut budget = MAX_PATHFINDING_NODES_PER_TICK;
+    for id in ids.iter() {
+        let mob = &world_map.mobs[id];
+        let Some(destination) = get_destination(&world_map, mob) else {
+            paths.paths.remove(id);
+            continue;
+        };
+
+        let chunks = &world_map.chunks;
+        let height = mob.kind.get_height_in_blocks();
+        let goal_cell = get_feet_cell(&destination);
+        let goal = find_ground(chunks, &goal_cell, height, MAX_DROP_DOWN).unwrap_or(goal_cell);
+
+        let is_up_to_date = paths.paths.get(id).is_some_and(|path| {
+            let moved = (path.goal - goal).abs().max_element() > REPATH_DISTANCE;
+            let blocked = path
+                .waypoints
+                .front()
+                .is_some_and(|next| !is_walkable(chunks, next, height));
+            let expired = time.0.saturating_sub(path.computed_at) >= REPATH_INTERVAL;
+            !moved && !blocked && !expired
+        });
+        if is_up_to_date {
+            continue;
+        }
+        if budget < MAX_SEARCH_NODES {
+            paths.next_search.get_or_insert(*id);
+            continue;
+        }
+
+        let Some(start) = find_ground(
+            chunks,
+            &get_feet_cell(&mob.position),
+            height,
+            MAX_FALL_SEARCH,
+        ) else {
+            continue;
+        };
+
+        let search = find_path(chunks, &start, &goal, height, MAX_SEARCH_NODES);
+        budget -= search.explored_nodes;
+
+        // Mobs in the air land on the ground below them first
+        let mut waypoints = search.waypoints;
+        if start != get_feet_cell(&mob.position) {
+            waypoints.push_front(start);
+        }
+
+        trace!(
+            "Path of mob {} to {:?}: {} waypoints, {} cells explored",
+            id,
+            goal,
+            waypoints.len(),
+            search.explored_nodes
+        );
+        paths.paths.insert(
+            *id,
+            MobPath {
+                goal,
+                waypoints,
+                computed_at: time.0,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::world::{BlockData, BlockDirection, BlockId, ServerChunk};
+
+    /// A single loaded chunk, whose bottom layer is stone
+    fn flat_world() -> ServerChunkWorldMap {
+        let mut chunk = ServerChunk::default();
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.map.insert(IVec3::new(x, 0, z), stone());
+            }
+        }
+        let mut world = ServerChunkWorldMap::default();
+        world.map.insert(IVec3::ZERO, chunk);
+        world
+    }
+
+    fn stone() -> BlockData {
+        BlockData::new(BlockId::Stone, false, BlockDirection::Front)
+    }
+
+    /// Two blocks high, too high to climb
+    fn build_wall(world: &mut ServerChunkWorldMap, cells: impl Iterator<Item = IVec3>) {
+        let chunk = world.map.get_mut(&IVec3::ZERO).unwrap();
+        for cell in cells {
+            chunk.map.insert(cell, stone());
+            chunk.map.insert(cell + IVec3::Y, stone());
+        }
+    }
+
+    #[test]
+    fn walks_straight_to_the_goal() {
+        let world = flat_world();
+        let goal = IVec3::new(10, 1, 1);
+        let search = find_path(&world, &IVec3::new(1, 1, 1), &goal, 2, MAX_SEARCH_NODES);
+
+        assert_eq!(search.waypoints.len(), 9);
+        assert_eq!(search.waypoints.back(), Some(&goal));
+    }
+
+    #[test]
+    fn goes_around_walls() {
+        let mut world = flat_world();
+        build_wall(&mut world, (0..=8).map(|z| IVec3::new(5, 1, z)));
+
+        let goal = IVec3::new(10, 1, 1);
+        let search = find_path(&world, &IVec3::new(1, 1, 1), &goal, 2, MAX_SEARCH_NODES);
+
+        assert_eq!(search.waypoints.back(), Some(&goal));
+        assert!(search.waypoints.len() > 9);
+        assert!(search
+            .waypoints
+            .iter()
+            .all(|cell| !(cell.x == 5 && cell.z <= 8)));
+    }
+
+    #[test]
+    fn stops_next_to_unreachable_goals() {
+        let mut world = flat_world();
+        let ring = (8..=12).flat_map(|x| (0..=4).map(move |z| IVec3::new(x, 1, z)));
+        build_wall(
+            &mut world,
+            ring.filter(|cell| cell.x == 8 || cell.x == 12 || cell.z == 0 || cell.z == 4),
+        );
+
+        let goal = IVec3::new(10, 1, 2);
+        let search = find_path(&world, &IVec3::new(1, 1, 1), &goal, 2, MAX_SEARCH_NODES);
+
+        assert_ne!(search.waypoints.back(), Some(&goal));
+        assert!(search.explored_nodes < MAX_SEARCH_NODES);
+    }
+
+    #[test]
+    fn stays_in_loaded_chunks() {
+        let world = flat_world();
+        let goal = IVec3::new(24, 1, 1);
+        let search = find_path(&world, &IVec3::new(1, 1, 1), &goal, 2, MAX_SEARCH_NODES);
+
+        assert_eq!(search.waypoints.back(), Some(&IVec3::new(15, 1, 1)));
+        assert!(!is_walkable(&world, &IVec3::new(16, 1, 1), 2));
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use shared::physics::{simulate_physics, PhysicsBody, PhysicsProperties};
+use shared::world::{world_position_to_chunk_position, MobKind, ServerWorldMap};
+
+/// In blocks per second squared
+const MOB_GRAVITY: f32 = -30.0;
+/// In blocks per second
+const MOB_MAX_FALL_SPEED: f32 = 60.0;
+/// Horizontal velocity kept every tick while on the ground
+const MOB_GROUND_FRICTION: f32 = 0.6;
+
+fn get_mob_physics(kind: &MobKind) -> PhysicsProperties {
+    PhysicsProperties {
+        size: kind.get_hitbox_size(),
+        gravity: MOB_GRAVITY,
+        max_fall_speed: MOB_MAX_FALL_SPEED,
+        ground_friction: MOB_GROUND_FRICTION,
+    }
+}
+
+/// Moves the mobs by their velocity, making them fall and collide with the blocks
+pub fn mob_physics_system(mut world_map: ResMut<ServerWorldMap>, delta: Res<Time<Fixed>>) {
+    let world_map = world_map.as_mut();
+    let delta = delta.delta_secs();
+
+    for mob in world_map.mobs.values_mut() {
+        // Mobs in unloaded chunks would fall through the world
+        if !world_map
+            .chunks
+            .map
+            .contains_key(&world_position_to_chunk_position(mob.position))
+        {
+            continue;
+        }
+
+        // Mobs stand on their position, while the hitbox is centred on the simulated one
+        let properties = get_mob_physics(&mob.kind);
+        let offset = Vec3::Y * properties.size.y / 2.0;
+        let mut body = PhysicsBody {
+            position: mob.position + offset,
+            velocity: mob.velocity,
+            on_ground: mob.on_ground,
+        };
+        simulate_physics(&mut body, &properties, &world_map.chunks, delta);
+
+        mob.position = body.position - offset;
+        mob.velocity = body.velocity;
+        mob.on_ground = body.on_ground;
+    }
+}
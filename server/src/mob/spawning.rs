@@ -0,0 +1,338 @@
+use bevy::prelude::*;
+use bevy_renet::renet::RenetServer;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use shared::messages::mob::MobDespawnEvent;
+use shared::messages::ServerToClientMessage;
+use shared::world::{
+    get_light_level, BiomeType, BlockId, MobAction, MobKind, MobTarget, ServerMob, ServerWorldMap,
+    WorldMap, MAX_LIGHT_LEVEL,
+};
+use shared::{CHUNK_SIZE, TICKS_PER_SECOND};
+use std::ops::RangeInclusive;
+
+use crate::init::ServerTime;
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::broadcast_world::BROADCAST_RENDER_DISTANCE;
+use crate::world::data::WorldSettings;
+use crate::world::generation::BiomeNoise;
+
+use super::create_new_mob_id;
+use super::pathfinding::{get_cell_feet_position, is_walkable};
+
+/// Ticks between two spawning attempts around each player
+const SPAWN_INTERVAL: u64 = TICKS_PER_SECOND;
+/// Mobs appear between these horizontal distances from a player, within the chunks
+/// generated around them\
+/// Positions in chunks that are not loaded yet are skipped, see `find_spawn_cell`
+const MAX_SPAWN_DISTANCE: f32 = (BROADCAST_RENDER_DISTANCE * CHUNK_SIZE) as f32;
+const MIN_SPAWN_DISTANCE: f32 = MAX_SPAWN_DISTANCE / 2.0;
+/// Blocks above and below the player searched for the ground
+const SPAWN_HEIGHT_RANGE: i32 = 16;
+/// Mobs of a group appear this far at most from each other
+const GROUP_SPREAD: i32 = 3;
+
+/// No mob appears around a player who already has this many mobs within `MOB_CAP_DISTANCE`
+const MOB_CAP: usize = 8;
+const MOB_CAP_DISTANCE: f32 = 64.0;
+/// Mobs this far from every player disappear
+const DESPAWN_DISTANCE: f32 = 96.0;
+
+/// Sky light levels lost at night
+const NIGHT_SKY_DARKENING: u8 = 11;
+
+/// Part of the day a mob can appear in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnTime {
+    Day,
+    Night,
+    Any,
+}
+
+/// Where and when a kind of mob appears naturally
+#[derive(Debug, Clone)]
+pub struct SpawnRule {
+    pub kind: MobKind,
+    pub biomes: Vec<BiomeType>,
+    /// Blocks the mob can appear on
+    pub surface_blocks: Vec<BlockId>,
+    /// Light of the spawn position, the sky being darker at night
+    pub light: RangeInclusive<u8>,
+    pub time: SpawnTime,
+    /// Chance of the rule being picked, relative to the other rules
+    pub weight: u32,
+    pub group_size: RangeInclusive<u32>,
+}
+
+/// Natural spawning rules of every kind of mob
+#[derive(Resource, Default)]
+pub struct SpawnRules {
+    rules: Vec<SpawnRule>,
+}
+
+impl SpawnRules {
+    pub fn register(&mut self, rule: SpawnRule) {
+        self.rules.push(rule);
+    }
+
+    fn pick(&self, rng: &mut impl Rng) -> Option<&SpawnRule> {
+        self.rules.choose_weighted(rng, |rule| rule.weight).ok()
+    }
+}
+
+fn register_builtin_spawn_rules(rules: &mut SpawnRules) {
+    rules.register(SpawnRule {
+        kind: MobKind::Fox,
+        biomes: vec![
+            BiomeType::Forest,
+            BiomeType::Plains,
+            BiomeType::FlowerPlains,
+            BiomeType::IcePlain,
+        ],
+        surface_blocks: vec![BlockId::Grass, BlockId::Snow, BlockId::SnowLayer],
+        light: 9..=MAX_LIGHT_LEVEL,
+        time: SpawnTime::Any,
+        weight: 10,
+        group_size: 1..=2,
+    });
+    rules.register(SpawnRule {
+        kind: MobKind::Pig,
+        biomes: vec![
+            BiomeType::Forest,
+            BiomeType::Plains,
+            BiomeType::FlowerPlains,
+        ],
+        surface_blocks: vec![BlockId::Grass],
+        light: 9..=MAX_LIGHT_LEVEL,
+        time: SpawnTime::Any,
+        weight: 10,
+        group_size: 2..=4,
+    });
+    rules.register(SpawnRule {
+        kind: MobKind::Zombie,
+        biomes: vec![
+            BiomeType::Plains,
+            BiomeType::Forest,
+            BiomeType::MediumMountain,
+            BiomeType::HighMountainGrass,
+            BiomeType::Desert,
+            BiomeType::IcePlain,
+            BiomeType::FlowerPlains,
+        ],
+        surface_blocks: vec![
+            BlockId::Grass,
+            BlockId::Dirt,
+            BlockId::Stone,
+            BlockId::Sand,
+            BlockId::Snow,
+            BlockId::SnowLayer,
+        ],
+        light: 0..=7,
+        time: SpawnTime::Night,
+        weight: 15,
+        group_size: 1..=1,
+    });
+}
+
+pub fn setup_mob_spawning(app: &mut App) {
+    let mut rules = SpawnRules::default();
+    register_builtin_spawn_rules(&mut rules);
+
+    app.insert_resource(rules);
+}
+
+/// The sun rises halfway through the day, like on the clients
+fn is_day(time: u64, day_length: u64) -> bool {
+    time % day_length.max(1) >= day_length / 2
+}
+
+/// Light a mob appearing at `cell` would be in, `None` if it is not known yet
+fn get_spawn_light(world_map: &ServerWorldMap, cell: &IVec3, is_day: bool) -> Option<u8> {
+    let light = get_light_level(&world_map.chunks, cell)?;
+    let sky = if is_day {
+        light.sky
+    } else {
+        light.sky.saturating_sub(NIGHT_SKY_DARKENING)
+    };
+    Some(sky.max(light.block))
+}
+
+/// Whether the rule lets its mob appear standing in `cell`
+fn is_valid_spawn(
+    world_map: &ServerWorldMap,
+    rule: &SpawnRule,
+    cell: &IVec3,
+    (biomes, is_day): (&BiomeNoise, bool),
+) -> bool {
+    let height = rule.kind.get_height_in_blocks();
+    if !is_walkable(&world_map.chunks, cell, height) {
+        return false;
+    }
+
+    let on_surface = world_map
+        .chunks
+        .get_block_by_coordinates(&(*cell - IVec3::Y))
+        .is_some_and(|block| rule.surface_blocks.contains(&block.id));
+    let in_light =
+        get_spawn_light(world_map, cell, is_day).is_some_and(|light| rule.light.contains(&light));
+    let in_biome = rule.biomes.contains(&biomes.get_biome_at(cell.x, cell.z));
+
+    on_surface && in_light && in_biome
+}
+
+/// Ground cell of the column around `around`, the highest one when there are several\
+/// Cells in chunks that are not loaded are never walkable, so they are ignored
+fn find_spawn_cell(world_map: &ServerWorldMap, around: &IVec3, height: i32) -> Option<IVec3> {
+    (-SPAWN_HEIGHT_RANGE..=SPAWN_HEIGHT_RANGE)
+        .rev()
+        .map(|dy| *around + IVec3::Y * dy)
+        .find(|cell| is_walkable(&world_map.chunks, cell, height))
+}
+
+/// Tries to spawn a group of mobs around every player, following the spawn rules
+pub fn mob_spawning_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    rules: Res<SpawnRules>,
+    (time, settings, biomes): (Res<ServerTime>, Res<WorldSettings>, Res<BiomeNoise>),
+) {
+    if !time.0.is_multiple_of(SPAWN_INTERVAL) {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let is_day = is_day(time.0, settings.day_length);
+
+    let players: Vec<Vec3> = world_map
+        .players
+        .values()
+        .filter(|player| !player.is_dead())
+        .map(|player| player.position)
+        .collect();
+
+    for player in players.iter() {
+        let nearby = world_map
+            .mobs
+            .values()
+            .filter(|mob| mob.position.distance(*player) < MOB_CAP_DISTANCE)
+            .count();
+        if nearby >= MOB_CAP {
+            continue;
+        }
+
+        let Some(rule) = rules.pick(&mut rng) else {
+            return;
+        };
+        if (rule.time == SpawnTime::Day && !is_day) || (rule.time == SpawnTime::Night && is_day) {
+            continue;
+        }
+
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let distance = rng.gen_range(MIN_SPAWN_DISTANCE..MAX_SPAWN_DISTANCE);
+        let center = *player + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance;
+        let center = center.round().as_ivec3();
+
+        let height = rule.kind.get_height_in_blocks();
+        let Some(origin) = find_spawn_cell(&world_map, &center, height) else {
+            continue;
+        };
+        if !is_valid_spawn(&world_map, rule, &origin, (&biomes, is_day)) {
+            continue;
+        }
+
+        let group_size = rng
+            .gen_range(rule.group_size.clone())
+            .min((MOB_CAP - nearby) as u32);
+        for index in 0..group_size {
+            let cell = if index == 0 {
+                Some(origin)
+            } else {
+                let around = origin
+                    + IVec3::new(
+                        rng.gen_range(-GROUP_SPREAD..=GROUP_SPREAD),
+                        0,
+                        rng.gen_range(-GROUP_SPREAD..=GROUP_SPREAD),
+                    );
+                find_spawn_cell(&world_map, &around, height)
+                    .filter(|cell| is_valid_spawn(&world_map, rule, cell, (&biomes, is_day)))
+            };
+            let Some(cell) = cell else {
+                continue;
+            };
+
+            let mob = ServerMob {
+                kind: rule.kind.clone(),
+                target: MobTarget::None,
+                action: MobAction::Idle,
+                position: get_cell_feet_position(&cell),
+                rotation: Quat::from_rotation_y(rng.gen_range(0.0..std::f32::consts::TAU)),
+                velocity: Vec3::ZERO,
+                on_ground: true,
+                health: rule.kind.get_max_health(),
+            };
+            debug!("Spawning {:?} at {:?}", mob.kind, cell);
+            world_map.mobs.insert(create_new_mob_id(), mob);
+        }
+    }
+}
+
+/// Removes the mobs far from every player, and tells the clients about it
+pub fn mob_despawning_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut server: ResMut<RenetServer>,
+) {
+    let world_map = world_map.as_mut();
+    let players = &world_map.players;
+
+    let mut despawned = Vec::new();
+    world_map.mobs.retain(|id, mob| {
+        let is_near = players
+            .values()
+            .any(|player| player.position.distance(mob.position) < DESPAWN_DISTANCE);
+        if !is_near {
+            despawned.push(*id);
+        }
+        is_near
+    });
+
+    for id in despawned {
+        debug!("Despawning mob {}", id);
+        server.broadcast_game_message(ServerToClientMessage::MobDespawn(MobDespawnEvent { id }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::world::{BlockData, BlockDirection, ServerChunk};
+
+    /// A single loaded chunk, whose bottom layer is stone
+    fn flat_world() -> ServerWorldMap {
+        let mut chunk = ServerChunk::default();
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.map.insert(
+                    IVec3::new(x, 0, z),
+                    BlockData::new(BlockId::Stone, false, BlockDirection::Front),
+                );
+            }
+        }
+        let mut world_map = ServerWorldMap::default();
+        world_map.chunks.map.insert(IVec3::ZERO, chunk);
+        world_map
+    }
+
+    #[test]
+    fn finds_the_ground_of_loaded_columns() {
+        let world_map = flat_world();
+        assert_eq!(
+            find_spawn_cell(&world_map, &IVec3::new(4, 8, 4), 2),
+            Some(IVec3::new(4, 1, 4))
+        );
+    }
+
+    #[test]
+    fn skips_columns_of_unloaded_chunks() {
+        let world_map = flat_world();
+        assert_eq!(find_spawn_cell(&world_map, &IVec3::new(40, 8, 4), 2), None);
+    }
+}
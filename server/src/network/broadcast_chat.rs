@@ -1,10 +1,53 @@
 use bevy::prelude::*;
-use shared::messages::ChatConversation;
+use bevy_renet::renet::RenetServer;
+use shared::messages::{ChatConversation, FullChatMessage, ServerToClientMessage};
+
+use super::extensions::SendGameMessageExtension;
+
+/// Number of messages kept in the server's conversation
+const CHAT_HISTORY_MAX_MESSAGES: usize = 100;
+
+/// Number of past messages sent to a player when joining
+pub const CHAT_HISTORY_ON_JOIN: usize = 20;
 
 #[derive(Event)]
-pub struct ChatMessageEvent;
+pub struct ChatMessageEvent(pub FullChatMessage);
 
 pub fn setup_chat_resources(app: &mut App) {
     app.insert_resource(ChatConversation { ..default() });
     app.add_event::<ChatMessageEvent>();
 }
+
+/// Sends the new messages to every client, without the rest of the history
+pub fn broadcast_chat_messages(
+    mut server: ResMut<RenetServer>,
+    mut chat_conversation: ResMut<ChatConversation>,
+    mut events: EventReader<ChatMessageEvent>,
+) {
+    let new_messages: Vec<FullChatMessage> = events.read().map(|ev| ev.0.clone()).collect();
+    if new_messages.is_empty() {
+        return;
+    }
+
+    chat_conversation
+        .messages
+        .extend(new_messages.iter().cloned());
+    let len = chat_conversation.messages.len();
+    if len > CHAT_HISTORY_MAX_MESSAGES {
+        chat_conversation
+            .messages
+            .drain(..len - CHAT_HISTORY_MAX_MESSAGES);
+    }
+
+    server.broadcast_game_message(ServerToClientMessage::ChatConversation(ChatConversation {
+        messages: new_messages,
+    }));
+}
+
+/// Latest messages of the conversation, for players who just joined
+pub fn get_recent_chat_history(chat_conversation: &ChatConversation) -> ChatConversation {
+    let len = chat_conversation.messages.len();
+    ChatConversation {
+        messages: chat_conversation.messages[len.saturating_sub(CHAT_HISTORY_ON_JOIN)..].to_vec(),
+    }
+}
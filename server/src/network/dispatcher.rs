@@ -1,18 +1,45 @@
+use crate::commands::{
+    handle_chat_commands_system, setup_commands, ChatCommandEvent, PermissionLevel,
+    ServerOperators, COMMAND_PREFIX,
+};
 use crate::init::{LobbyPlayer, ServerLobby, ServerTime};
-use crate::mob::behavior::mob_behavior_system;
+use crate::mob::ai::{mob_ai_system, setup_mob_ai};
+use crate::mob::combat::{player_attack_mobs_system, MobAttackEvent, PlayerAttackCooldowns};
+use crate::mob::movement::mob_movement_system;
+use crate::mob::pathfinding::{mob_pathfinding_system, MobPaths};
+use crate::mob::physics::mob_physics_system;
+use crate::mob::spawning::{mob_despawning_system, mob_spawning_system, setup_mob_spawning};
 use crate::network::broadcast_chat::*;
 use crate::network::cleanup::cleanup_player_from_world;
 use crate::world;
 use crate::world::background_generation::background_world_generation_system;
-use crate::world::broadcast_world::broadcast_world_state;
+use crate::world::block_updates::{block_tick_system, neighbour_updates_system};
+use crate::world::broadcast_world::{
+    broadcast_block_updates, broadcast_time, broadcast_world_state,
+};
+use crate::world::chunk_lifecycle::{chunk_unloading_system, ChunkActivity};
+use crate::world::data::WorldSettings;
+use crate::world::falling_blocks::falling_blocks_system;
+use crate::world::health::{
+    apply_damage_system, environment_damage_system, respawn_player, DamageEvent,
+    MobAttackCooldowns, SPAWN_POINT,
+};
+use crate::world::inventory::{
+    handle_inventory_actions, sync_inventories_system, InventoryActionEvent, SentInventories,
+};
+use crate::world::light::light_system;
+use crate::world::random_ticks::{random_tick_system, setup_random_ticks};
 use crate::world::save::SaveRequestEvent;
 use crate::world::simulation::{handle_player_inputs_system, PlayerInputsEvent};
-use crate::world::BlockInteractionEvent;
+use crate::world::storage::WorldStorage;
+use crate::world::ticks::ScheduledTicks;
+use crate::world::weather::{snowfall_system, sync_weather_system, weather_system};
+use crate::world::{BlockInteractionEvent, BreakingBlocks, BLOCK_REACH};
 use bevy::prelude::*;
 use bevy_renet::renet::{RenetServer, ServerEvent};
 use shared::messages::{
     AuthRegisterResponse, ChatConversation, ClientToServerMessage, FullChatMessage,
-    PlayerSpawnEvent, ServerToClientMessage,
+    PlayerGameModeUpdate, PlayerHealthUpdate, PlayerSpawnEvent, ServerToClientMessage,
 };
 use shared::players::Player;
 use shared::world::ServerWorldMap;
@@ -23,55 +50,167 @@ use super::extensions::SendGameMessageExtension;
 pub fn setup_resources_and_events(app: &mut App) {
     app.add_event::<SaveRequestEvent>()
         .add_event::<BlockInteractionEvent>()
-        .add_event::<PlayerInputsEvent>();
+        .add_event::<PlayerInputsEvent>()
+        .add_event::<InventoryActionEvent>()
+        .add_event::<DamageEvent>()
+        .add_event::<MobAttackEvent>();
+
+    app.insert_resource(ChunkActivity::default());
+    app.insert_resource(SentInventories::default());
+    app.insert_resource(BreakingBlocks::default());
+    app.insert_resource(MobAttackCooldowns::default());
+    app.insert_resource(PlayerAttackCooldowns::default());
+    app.insert_resource(MobPaths::default());
+    app.insert_resource(ScheduledTicks::default());
 
     setup_chat_resources(app);
+    setup_commands(app);
+    setup_random_ticks(app);
+    setup_mob_spawning(app);
+    setup_mob_ai(app);
 }
 
 pub fn register_systems(app: &mut App) {
     app.add_systems(Update, server_update_system);
 
-    app.add_systems(Update, broadcast_world_state);
+    app.add_systems(Update, broadcast_chat_messages.after(server_update_system));
+    app.add_systems(
+        Update,
+        handle_chat_commands_system.after(server_update_system),
+    );
+
+    app.add_systems(Update, broadcast_time.after(handle_chat_commands_system));
+    app.add_systems(
+        Update,
+        (weather_system, sync_weather_system)
+            .chain()
+            .after(handle_chat_commands_system),
+    );
+    app.add_systems(Update, snowfall_system.before(neighbour_updates_system));
+
+    app.add_systems(Update, world::stacks::item_stacks_system);
+    app.add_systems(
+        Update,
+        broadcast_world_state
+            .after(world::stacks::item_stacks_system)
+            .after(falling_blocks_system),
+    );
 
     app.add_systems(Update, world::save::save_world_system);
     app.add_systems(Update, world::handle_block_interactions);
-
-    app.add_systems(Update, crate::mob::manage_mob_spawning_system);
+    app.add_systems(
+        Update,
+        (
+            block_tick_system,
+            falling_blocks_system,
+            neighbour_updates_system,
+        )
+            .chain()
+            .after(world::handle_block_interactions)
+            .after(handle_chat_commands_system),
+    );
+    app.add_systems(
+        Update,
+        broadcast_block_updates.after(neighbour_updates_system),
+    );
+    app.add_systems(Update, light_system.after(neighbour_updates_system));
+    app.add_systems(
+        Update,
+        random_tick_system
+            .after(handle_chat_commands_system)
+            .before(neighbour_updates_system),
+    );
+
+    app.add_systems(Update, handle_inventory_actions.after(server_update_system));
+    app.add_systems(
+        Update,
+        sync_inventories_system
+            .after(handle_inventory_actions)
+            .after(world::handle_block_interactions)
+            .after(handle_chat_commands_system)
+            .after(world::stacks::item_stacks_system),
+    );
+
+    app.add_systems(
+        Update,
+        (mob_spawning_system, mob_despawning_system)
+            .chain()
+            .after(light_system),
+    );
+    app.add_systems(
+        Update,
+        player_attack_mobs_system.after(handle_player_inputs_system),
+    );
+    app.add_systems(
+        Update,
+        mob_ai_system
+            .after(mob_despawning_system)
+            .after(player_attack_mobs_system),
+    );
+    app.add_systems(
+        Update,
+        mob_pathfinding_system
+            .after(mob_ai_system)
+            .after(neighbour_updates_system),
+    );
 
     app.add_systems(Update, handle_player_inputs_system);
+    app.add_systems(Update, environment_damage_system);
+    app.add_systems(
+        Update,
+        apply_damage_system
+            .after(handle_player_inputs_system)
+            .after(environment_damage_system)
+            .before(world::stacks::item_stacks_system),
+    );
 
     app.add_systems(Update, background_world_generation_system);
 
+    app.add_systems(Update, chunk_unloading_system);
+
     app.add_systems(PostUpdate, update_server_time);
 
-    app.add_systems(FixedUpdate, mob_behavior_system);
+    app.add_systems(
+        FixedUpdate,
+        (mob_movement_system, mob_physics_system).chain(),
+    );
 }
 
 fn server_update_system(
     mut server_events: EventReader<ServerEvent>,
-    (mut server, mut chat_conversation, mut lobby): (
+    (mut server, chat_conversation, mut lobby): (
         ResMut<RenetServer>,
-        ResMut<ChatConversation>,
+        Res<ChatConversation>,
         ResMut<ServerLobby>,
     ),
     (
         mut ev_chat,
+        mut ev_command,
         mut ev_app_exit,
         // mut ev_world_update_request,
         mut ev_save_request,
         mut ev_block_interaction,
         mut ev_player_inputs,
+        mut ev_inventory_action,
+        mut ev_mob_attack,
     ): (
         EventWriter<ChatMessageEvent>,
+        EventWriter<ChatCommandEvent>,
         EventWriter<AppExit>,
         // EventWriter<WorldUpdateRequestEvent>,
         EventWriter<SaveRequestEvent>,
         EventWriter<BlockInteractionEvent>,
         EventWriter<PlayerInputsEvent>,
+        EventWriter<InventoryActionEvent>,
+        EventWriter<MobAttackEvent>,
     ),
-    config: Res<GameServerConfig>,
-    mut world_map: ResMut<ServerWorldMap>,
-    time: Res<ServerTime>,
+    (config, operators, settings): (
+        Res<GameServerConfig>,
+        Res<ServerOperators>,
+        Res<WorldSettings>,
+    ),
+    (mut world_map, storage): (ResMut<ServerWorldMap>, Res<WorldStorage>),
+    (time, mut breaking): (Res<ServerTime>, ResMut<BreakingBlocks>),
 ) {
     for event in server_events.read() {
         debug!("event received");
@@ -82,6 +221,11 @@ fn server_update_system(
             ServerEvent::ClientDisconnected { client_id, reason } => {
                 info!("Player {} disconnected: {}", client_id, reason);
                 lobby.players.remove(client_id);
+                if let Some(player) = world_map.players.get(client_id) {
+                    if let Err(e) = storage.save_player(player) {
+                        error!("Failed to save player {}: {}", client_id, e);
+                    }
+                }
                 cleanup_player_from_world(&mut world_map, client_id);
             }
         }
@@ -103,25 +247,32 @@ fn server_update_system(
                         .insert(client_id, LobbyPlayer::new(auth_req.username.clone()));
                     debug!("New lobby : {:?}", lobby);
 
-                    let maybe_existing_player = world_map.players.get(&client_id);
-
-                    let new_position = match maybe_existing_player {
-                        Some(existing_player) => existing_player.position,
-                        None => Vec3::new(0.0, 80.0, 0.0),
+                    let saved_player = match storage.load_player(client_id) {
+                        Ok(player) => player,
+                        Err(e) => {
+                            error!("Failed to load player {}: {}", client_id, e);
+                            None
+                        }
                     };
 
-                    let camera_transform = match maybe_existing_player {
-                        Some(existing_player) => existing_player.camera_transform,
-                        None => Transform::default(),
+                    let player_data = match saved_player {
+                        Some(mut player) => {
+                            player.name = auth_req.username.clone();
+                            player.last_input_processed = 0;
+                            player
+                        }
+                        None => {
+                            let mut player = Player::new(
+                                client_id,
+                                auth_req.username.clone(),
+                                SPAWN_POINT,
+                                Transform::default(),
+                            );
+                            player.set_game_mode(settings.default_game_mode);
+                            player
+                        }
                     };
 
-                    let player_data = Player::new(
-                        client_id,
-                        auth_req.username.clone(),
-                        new_position,
-                        camera_transform,
-                    );
-
                     world_map.players.insert(client_id, player_data);
 
                     let timestamp_ms: u64 = std::time::SystemTime::now()
@@ -145,12 +296,37 @@ fn server_update_system(
                         username: auth_req.username,
                         session_token: client_id,
                         tick: time.0,
+                        day_length: settings.day_length,
                         timestamp_ms,
                         players: all_player_spawn_events,
                     };
 
                     server.send_game_message(client_id, auth_res.into());
 
+                    if let Some(player) = world_map.players.get(&client_id) {
+                        server.send_game_message(
+                            client_id,
+                            ServerToClientMessage::PlayerHealth(PlayerHealthUpdate {
+                                health: player.health,
+                                source: None,
+                            }),
+                        );
+                        server.send_game_message(
+                            client_id,
+                            ServerToClientMessage::PlayerGameMode(PlayerGameModeUpdate {
+                                game_mode: player.game_mode,
+                            }),
+                        );
+                    }
+
+                    let history = get_recent_chat_history(&chat_conversation);
+                    if !history.messages.is_empty() {
+                        server.send_game_message(
+                            client_id,
+                            ServerToClientMessage::ChatConversation(history),
+                        );
+                    }
+
                     for (id, player) in lobby.players.iter() {
                         let spawn_message = PlayerSpawnEvent {
                             id: *id,
@@ -168,6 +344,15 @@ fn server_update_system(
                 }
                 ClientToServerMessage::ChatMessage(chat_msg) => {
                     info!("Chat message received: {:?}", &chat_msg);
+
+                    if chat_msg.content.starts_with(COMMAND_PREFIX) {
+                        ev_command.send(ChatCommandEvent {
+                            client_id,
+                            line: chat_msg.content,
+                        });
+                        continue;
+                    }
+
                     let current_timestamp: u64 = std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap()
@@ -175,17 +360,20 @@ fn server_update_system(
 
                     let current_author = lobby.players.get(&client_id).unwrap();
 
-                    chat_conversation.messages.push(FullChatMessage {
+                    ev_chat.send(ChatMessageEvent(FullChatMessage {
                         author: current_author.name.clone(),
                         content: chat_msg.content,
                         timestamp: current_timestamp,
-                    });
-                    ev_chat.send(ChatMessageEvent);
+                    }));
                 }
                 ClientToServerMessage::Exit => {
                     debug!("Received shutdown order...");
-                    // TODO: add permission checks
-                    if config.is_solo {
+                    // Clients send this order whenever they leave, so only the
+                    // operator of a solo server can bring it down with them
+                    let is_operator = lobby.players.get(&client_id).is_some_and(|player| {
+                        operators.permission_level(&player.name) == PermissionLevel::Operator
+                    });
+                    if config.is_solo && is_operator {
                         info!("Server is going down...");
                         ev_app_exit.send(AppExit::Success);
                     } else {
@@ -208,9 +396,20 @@ fn server_update_system(
 
                     ev_save_request.send(SaveRequestEvent);
                 }
+                ClientToServerMessage::StartBreaking { position } => {
+                    let in_reach = world_map.players.get(&client_id).is_some_and(|player| {
+                        player.position.distance(position.as_vec3()) <= BLOCK_REACH
+                    });
+                    if in_reach {
+                        breaking.started.insert(client_id, (position, time.0));
+                    } else {
+                        breaking.started.remove(&client_id);
+                    }
+                }
                 ClientToServerMessage::BlockInteraction {
                     position,
                     block_type,
+                    hotbar_slot,
                 } => {
                     debug!(
                         "Block interaction received at {:?}: {:?}",
@@ -218,8 +417,54 @@ fn server_update_system(
                     );
 
                     ev_block_interaction.send(BlockInteractionEvent {
+                        player_id: client_id,
                         position,
                         block_type,
+                        hotbar_slot,
+                    });
+                }
+                ClientToServerMessage::UseBlock { position } => {
+                    debug!("Block used at {:?}", position);
+                    world::use_block(&mut world_map, client_id, &position);
+                }
+                ClientToServerMessage::Respawn => {
+                    let Some(player) = world_map.players.get_mut(&client_id) else {
+                        continue;
+                    };
+                    if !player.is_dead() {
+                        continue;
+                    }
+
+                    info!("Player {} respawned", client_id);
+                    respawn_player(player);
+                    server.send_game_message(
+                        client_id,
+                        ServerToClientMessage::PlayerHealth(PlayerHealthUpdate {
+                            health: player.health,
+                            source: None,
+                        }),
+                    );
+                }
+                ClientToServerMessage::AttackMob { id } => {
+                    ev_mob_attack.send(MobAttackEvent {
+                        player_id: client_id,
+                        mob_id: id,
+                    });
+                }
+                ClientToServerMessage::InventoryAction(action) => {
+                    debug!("Inventory action received: {:?}", action);
+                    ev_inventory_action.send(InventoryActionEvent {
+                        client_id,
+                        action,
+                        container: None,
+                    });
+                }
+                ClientToServerMessage::ContainerAction { position, action } => {
+                    debug!("Container action received at {:?}: {:?}", position, action);
+                    ev_inventory_action.send(InventoryActionEvent {
+                        client_id,
+                        action,
+                        container: Some(position),
                     });
                 }
             }
@@ -228,7 +473,7 @@ fn server_update_system(
 }
 
 fn update_server_time(mut time: ResMut<ServerTime>) {
-    if (time.0 % (5 * TICKS_PER_SECOND)) == 0 {
+    if time.0.is_multiple_of(5 * TICKS_PER_SECOND) {
         debug!("Server time: {}", time.0);
     }
     time.0 += 1;
@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 use shared::world::{ServerWorldMap, WorldSeed};
 
-use crate::world::generation::generate_chunk;
+use crate::world::chunk_lifecycle::load_or_generate_chunk;
+use crate::world::storage::WorldStorage;
 
 use super::broadcast_world::{get_all_active_chunks, BROADCAST_RENDER_DISTANCE};
 
 pub fn background_world_generation_system(
     mut world_map: ResMut<ServerWorldMap>,
     seed: Res<WorldSeed>,
+    mut storage: ResMut<WorldStorage>,
 ) {
     let all_chunks = get_all_active_chunks(&world_map.players, BROADCAST_RENDER_DISTANCE);
     let mut generated = 0;
@@ -15,8 +17,7 @@ pub fn background_world_generation_system(
         let chunk = world_map.chunks.map.get(&c);
 
         if chunk.is_none() {
-            let chunk = generate_chunk(c, seed.0);
-            info!("Generated chunk: {:?}", c);
+            let chunk = load_or_generate_chunk(&mut storage, c, seed.0);
             world_map.chunks.map.insert(c, chunk);
             generated += 1;
         }
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use shared::world::{
+    global_block_to_chunk_pos, is_supported, BlockData, BlockShape, ItemStack, ServerChunkWorldMap,
+    ServerWorldMap, WorldMap,
+};
+use std::collections::HashSet;
+
+use crate::init::ServerTime;
+use crate::world::falling_blocks::{can_fall_into, start_falling};
+use crate::world::fluids::{needs_fluid_update, update_water, WATER_TICK_DELAY};
+use crate::world::stacks::spawn_item_stack;
+use crate::world::ticks::ScheduledTicks;
+
+/// Ticks before a block starts falling once its support is gone
+const FALL_TICK_DELAY: u64 = 2;
+
+/// Limits the time spent on block updates when a lot of blocks change at once\
+/// Updates over the limit are delayed to the next ticks
+const MAX_BLOCK_UPDATES_PER_TICK: usize = 2048;
+
+/// Gravity-affected block with nothing to hold it\
+/// Blocks in chunks that are not loaded never fall
+fn should_fall(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    if !chunks
+        .map
+        .contains_key(&global_block_to_chunk_pos(position))
+    {
+        return false;
+    }
+
+    chunks
+        .get_block_by_coordinates(position)
+        .is_some_and(|block| block.id.is_affected_by_gravity())
+        && can_fall_into(chunks, &(*position - IVec3::Y))
+}
+
+/// Half of a door whose other half is gone\
+/// Doors across chunks that are not loaded are left as they are
+fn is_broken_door(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    let Some(block) = chunks.get_block_by_coordinates(position) else {
+        return false;
+    };
+    if block.id.get_shape() != BlockShape::Door {
+        return false;
+    }
+
+    let partner = block.door_partner(position);
+    chunks
+        .map
+        .contains_key(&global_block_to_chunk_pos(&partner))
+        && !chunks
+            .get_block_by_coordinates(&partner)
+            .is_some_and(|other| other.id == block.id)
+}
+
+/// Block such as a torch whose support is gone\
+/// Blocks held by a chunk that is not loaded are left as they are
+fn is_unsupported(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    let Some(block) = chunks.get_block_by_coordinates(position) else {
+        return false;
+    };
+    let Some(support) = block.get_support(position) else {
+        return false;
+    };
+
+    chunks
+        .map
+        .contains_key(&global_block_to_chunk_pos(&support))
+        && !is_supported(chunks, position, block)
+}
+
+/// Schedules the blocks notified by a change, if they need to react to it
+pub fn neighbour_updates_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut ticks: ResMut<ScheduledTicks>,
+    time: Res<ServerTime>,
+) {
+    let chunks = &mut world_map.chunks;
+    if chunks.neighbour_updates.is_empty() {
+        return;
+    }
+
+    let mut seen: HashSet<IVec3> = HashSet::new();
+    let mut dropped: Vec<(IVec3, BlockData)> = vec![];
+    for position in std::mem::take(&mut chunks.neighbour_updates) {
+        if !seen.insert(position) {
+            continue;
+        }
+
+        // The drop comes from the half that was broken
+        if is_broken_door(chunks, &position) {
+            chunks.remove_block_by_coordinates(&position);
+            continue;
+        }
+
+        if is_unsupported(chunks, &position) {
+            if let Some(block) = chunks.remove_block_by_coordinates(&position) {
+                dropped.push((position, block));
+            }
+            continue;
+        }
+
+        if should_fall(chunks, &position) {
+            ticks.schedule(position, time.0 + FALL_TICK_DELAY);
+        } else if needs_fluid_update(chunks, &position) {
+            ticks.schedule(position, time.0 + WATER_TICK_DELAY);
+        }
+    }
+
+    for (position, block) in dropped {
+        for (item_id, nb) in block.id.get_drops(1) {
+            spawn_item_stack(
+                &mut world_map,
+                ItemStack {
+                    item_id,
+                    item_type: item_id.get_default_type(),
+                    nb,
+                },
+                position.as_vec3(),
+            );
+        }
+    }
+}
+
+/// Updates the blocks whose scheduled tick is due
+pub fn block_tick_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut ticks: ResMut<ScheduledTicks>,
+    time: Res<ServerTime>,
+) {
+    let due = ticks.take_due(time.0, MAX_BLOCK_UPDATES_PER_TICK);
+    if due.is_empty() {
+        return;
+    }
+
+    debug!(
+        "Updating {} blocks, {} still scheduled",
+        due.len(),
+        ticks.count()
+    );
+    for position in due.iter() {
+        // The block may have changed since it was scheduled
+        if should_fall(&world_map.chunks, position) {
+            start_falling(&mut world_map, position);
+        } else {
+            update_water(&mut world_map.chunks, position);
+        }
+    }
+}
@@ -1,20 +1,49 @@
 use crate::init::ServerTime;
 use crate::network::extensions::SendGameMessageExtension;
+use crate::world::data::WorldSettings;
 use bevy::math::IVec3;
 use bevy::prelude::*;
 use bevy_ecs::system::ResMut;
 use bevy_renet::renet::RenetServer;
 use shared::messages::mob::MobUpdateEvent;
-use shared::messages::{ItemStackUpdateEvent, PlayerId, ServerToClientMessage, WorldUpdate};
+use shared::messages::{
+    BlockUpdate, FallingBlockUpdateEvent, ItemStackUpdateEvent, PlayerId, ServerToClientMessage,
+    TimeUpdate, WorldUpdate,
+};
 use shared::players::Player;
 use shared::world::{
-    world_position_to_chunk_position, ServerChunk, ServerChunkWorldMap, ServerWorldMap,
+    global_block_to_chunk_pos, to_local_pos, world_position_to_chunk_position, ServerChunk,
+    ServerChunkWorldMap, ServerFallingBlock, ServerItemStack, ServerWorldMap,
 };
-use shared::CHUNK_SIZE;
-use std::collections::HashMap;
+use shared::{CHUNK_SIZE, TICKS_PER_SECOND};
+use std::collections::{HashMap, HashSet};
 
 pub const BROADCAST_RENDER_DISTANCE: i32 = 1;
 
+/// Ticks between two time updates, clients interpolate in between
+const TIME_SYNC_INTERVAL: u64 = TICKS_PER_SECOND;
+
+/// Sends the time of day to every client every `TIME_SYNC_INTERVAL` ticks\
+/// It is also sent right away when it jumps, such as after `/time set`, or when the day length changes
+pub fn broadcast_time(
+    mut server: ResMut<RenetServer>,
+    (time, settings): (Res<ServerTime>, Res<WorldSettings>),
+    mut previous: Local<Option<TimeUpdate>>,
+) {
+    let update = TimeUpdate {
+        time: time.0,
+        day_length: settings.day_length,
+    };
+    let continuous = previous.is_some_and(|previous| {
+        update.time == previous.time + 1 && update.day_length == previous.day_length
+    });
+    *previous = Some(update);
+
+    if !continuous || update.time.is_multiple_of(TIME_SYNC_INTERVAL) {
+        server.broadcast_game_message(ServerToClientMessage::TimeUpdate(update));
+    }
+}
+
 pub fn broadcast_world_state(
     mut server: ResMut<RenetServer>,
     time: Res<ServerTime>,
@@ -27,9 +56,11 @@ pub fn broadcast_world_state(
 
     let world_map = world_map.as_mut();
 
-    let mobs = world_map.mobs.clone();
+    let mobs = &world_map.mobs;
     let players = &mut world_map.players;
     let chunks = &mut world_map.chunks;
+    let item_stacks = &mut world_map.item_stacks;
+    let falling_blocks = &world_map.falling_blocks;
 
     for client in server.clients_id().iter_mut() {
         let player = players.get_mut(client);
@@ -56,12 +87,12 @@ pub fn broadcast_world_state(
             tick: time.0,
             time: ts,
             new_map: get_world_map_chunks_to_send(chunks, players, &player),
-            mobs: mobs.clone(),
-            item_stacks: get_items_stacks(),
+            item_stacks: get_item_stacks_to_send(item_stacks, &player),
+            falling_blocks: get_falling_blocks(falling_blocks, &player),
             player_events: vec![],
         };
 
-        if msg.new_map.is_empty() {
+        if msg.new_map.is_empty() && msg.item_stacks.is_empty() && msg.falling_blocks.is_empty() {
             continue;
         }
 
@@ -71,6 +102,45 @@ pub fn broadcast_world_state(
     }
 }
 
+/// Sends the blocks changed since the last call to every client that already received their chunk
+pub fn broadcast_block_updates(
+    mut server: ResMut<RenetServer>,
+    mut world_map: ResMut<ServerWorldMap>,
+) {
+    let chunks = &mut world_map.chunks;
+    if chunks.blocks_to_update.is_empty() {
+        return;
+    }
+
+    let mut updates: HashMap<PlayerId, Vec<BlockUpdate>> = HashMap::new();
+    let mut seen: HashSet<IVec3> = HashSet::new();
+
+    for position in std::mem::take(&mut chunks.blocks_to_update) {
+        if !seen.insert(position) {
+            continue;
+        }
+
+        let Some(chunk) = chunks.map.get(&global_block_to_chunk_pos(&position)) else {
+            continue;
+        };
+
+        let local_pos = to_local_pos(&position);
+        let update = BlockUpdate {
+            position,
+            block: chunk.map.get(&local_pos).copied(),
+            block_entity: chunk.block_entities.get(&local_pos).cloned(),
+        };
+
+        for client in chunk.sent_to_clients.iter() {
+            updates.entry(*client).or_default().push(update.clone());
+        }
+    }
+
+    for (client, client_updates) in updates {
+        server.send_game_message(client, ServerToClientMessage::BlockUpdates(client_updates));
+    }
+}
+
 fn get_world_map_chunks_to_send(
     chunks: &mut ServerChunkWorldMap,
     players: &HashMap<PlayerId, Player>,
@@ -101,21 +171,60 @@ fn get_world_map_chunks_to_send(
     map
 }
 
-fn get_items_stacks() -> Vec<ItemStackUpdateEvent> {
-    // TODO: Update later by requiring less data (does not need to borrow a full ServerWorldMap)
-    vec![]
-    // world_map
-    //     .item_stacks
-    //     .iter()
-    //     .map(|stack| ItemStackUpdateEvent {
-    //         id: stack.id,
-    //         data: if stack.despawned {
-    //             None
-    //         } else {
-    //             Some((stack.stack, stack.pos))
-    //         },
-    //     })
-    //     .collect()
+/// Stacks that appeared or changed near the player since it was last sent them,
+/// and the ones it knows about that despawned or went out of range
+fn get_item_stacks_to_send(
+    item_stacks: &mut [ServerItemStack],
+    player: &Player,
+) -> Vec<ItemStackUpdateEvent> {
+    let mut updates = Vec::new();
+
+    for stack in item_stacks.iter_mut() {
+        let in_range = !stack.despawned
+            && stack.pos.distance(player.position)
+                < (BROADCAST_RENDER_DISTANCE * CHUNK_SIZE) as f32;
+
+        if in_range {
+            let current = (stack.stack.nb, stack.pos);
+            if stack.sent_to_clients.insert(player.id, current) == Some(current) {
+                continue;
+            }
+            updates.push(ItemStackUpdateEvent {
+                id: stack.id,
+                data: Some((stack.stack, stack.pos)),
+            });
+        } else if stack.sent_to_clients.remove(&player.id).is_some() {
+            updates.push(ItemStackUpdateEvent {
+                id: stack.id,
+                data: None,
+            });
+        }
+    }
+
+    updates
+}
+
+/// Falling blocks near the player, and every block that landed this tick
+fn get_falling_blocks(
+    falling_blocks: &[ServerFallingBlock],
+    player: &Player,
+) -> Vec<FallingBlockUpdateEvent> {
+    falling_blocks
+        .iter()
+        .filter(|falling| {
+            falling.despawned
+                || falling.pos.distance(player.position)
+                    < (BROADCAST_RENDER_DISTANCE * CHUNK_SIZE) as f32
+        })
+        .map(|falling| FallingBlockUpdateEvent {
+            id: falling.id,
+            data: if falling.despawned {
+                None
+            } else {
+                Some((falling.block, falling.pos))
+            },
+        })
+        .collect()
 }
 
 pub fn get_all_active_chunks(players: &HashMap<PlayerId, Player>, radius: i32) -> Vec<IVec3> {
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use shared::world::{ServerChunk, ServerWorldMap};
+use shared::TICKS_PER_SECOND;
+use std::collections::HashMap;
+
+use crate::init::ServerTime;
+use crate::world::broadcast_world::{get_all_active_chunks, BROADCAST_RENDER_DISTANCE};
+use crate::world::generation::generate_chunk;
+use crate::world::storage::WorldStorage;
+
+/// Chunks within this radius of a player are never unloaded.\
+/// Slightly larger than the active radius, so walking back and forth on a
+/// chunk border does not unload and reload the same chunks
+const KEEP_LOADED_RADIUS: i32 = BROADCAST_RENDER_DISTANCE + 1;
+
+/// Number of ticks a chunk stays in memory once no player needs it
+const CHUNK_UNLOAD_DELAY: u64 = 30 * TICKS_PER_SECOND;
+
+const UNLOAD_CHECK_INTERVAL: u64 = TICKS_PER_SECOND;
+
+/// Last tick at which each loaded chunk was needed by a player
+#[derive(Resource, Default)]
+pub struct ChunkActivity {
+    pub last_needed: HashMap<IVec3, u64>,
+}
+
+/// Reads the chunk from disk if it was saved before, generates it otherwise
+pub fn load_or_generate_chunk(
+    storage: &mut WorldStorage,
+    chunk_pos: IVec3,
+    seed: u32,
+) -> ServerChunk {
+    match storage.load_chunk(&chunk_pos) {
+        Ok(Some(chunk)) => {
+            debug!("Loaded chunk from disk: {:?}", chunk_pos);
+            return chunk;
+        }
+        Ok(None) => {}
+        Err(e) => error!("Failed to load chunk {:?}: {}", chunk_pos, e),
+    }
+
+    let chunk = generate_chunk(chunk_pos, seed);
+    info!("Generated chunk: {:?}", chunk_pos);
+    chunk
+}
+
+pub fn chunk_unloading_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut storage: ResMut<WorldStorage>,
+    mut activity: ResMut<ChunkActivity>,
+    time: Res<ServerTime>,
+) {
+    let world_map = world_map.as_mut();
+
+    for c in get_all_active_chunks(&world_map.players, KEEP_LOADED_RADIUS) {
+        activity.last_needed.insert(c, time.0);
+    }
+
+    if !time.0.is_multiple_of(UNLOAD_CHECK_INTERVAL) {
+        return;
+    }
+
+    let to_unload: Vec<IVec3> = world_map
+        .chunks
+        .map
+        .keys()
+        .filter(|c| {
+            // Chunks loaded without any player around start their delay now
+            let last_needed = *activity.last_needed.entry(**c).or_insert(time.0);
+            time.0.saturating_sub(last_needed) > CHUNK_UNLOAD_DELAY
+        })
+        .copied()
+        .collect();
+
+    for c in to_unload {
+        let chunk = &world_map.chunks.map[&c];
+
+        if storage.is_dirty(chunk) {
+            if let Err(e) = storage.save_chunk(&c, chunk) {
+                // Keep the chunk in memory rather than losing its changes
+                error!("Failed to save chunk {:?} before unloading: {}", c, e);
+                continue;
+            }
+        }
+
+        world_map.chunks.map.remove(&c);
+        activity.last_needed.remove(&c);
+        debug!("Unloaded chunk: {:?}", c);
+    }
+}
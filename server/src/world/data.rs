@@ -1,11 +1,57 @@
-use bevy::prelude::*;
+use bevy::prelude::Resource;
 use serde::{Deserialize, Serialize};
-use shared::world::ServerChunk;
-use std::collections::HashMap;
+use shared::players::GameMode;
+use shared::world::WorldSeed;
+use shared::DEFAULT_DAY_LENGTH;
+
+use crate::world::weather::WeatherState;
 
 pub const SAVE_PATH: &str = "saves/";
 
-#[derive(Serialize, Deserialize)]
-pub struct Save {
-    pub map: HashMap<IVec3, ServerChunk>,
+pub const DEFAULT_RANDOM_TICK_SPEED: u32 = 3;
+/// Higher speeds would tick most blocks of every loaded chunk each tick, and stall the server
+pub const MAX_RANDOM_TICK_SPEED: u32 = 1024;
+
+fn default_random_tick_speed() -> u32 {
+    DEFAULT_RANDOM_TICK_SPEED
+}
+
+fn default_day_length() -> u64 {
+    DEFAULT_DAY_LENGTH
+}
+
+/// Rules of the world, saved in `level.ron` and editable by hand
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct WorldSettings {
+    /// Game mode given to players joining the world for the first time
+    pub default_game_mode: GameMode,
+    /// Blocks picked in every loaded chunk each tick to run their random tick, 0 disables them\
+    /// Capped to `MAX_RANDOM_TICK_SPEED`, including when edited by hand
+    #[serde(default = "default_random_tick_speed")]
+    pub random_tick_speed: u32,
+    /// Ticks in a full day, sent to clients along with the time
+    #[serde(default = "default_day_length")]
+    pub day_length: u64,
+}
+
+impl Default for WorldSettings {
+    fn default() -> Self {
+        Self {
+            default_game_mode: GameMode::default(),
+            random_tick_speed: DEFAULT_RANDOM_TICK_SPEED,
+            day_length: DEFAULT_DAY_LENGTH,
+        }
+    }
+}
+
+/// World metadata, stored next to the region files
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorldData {
+    pub name: String,
+    pub seed: WorldSeed,
+    pub time: u64,
+    #[serde(default)]
+    pub settings: WorldSettings,
+    #[serde(default)]
+    pub weather: WeatherState,
 }
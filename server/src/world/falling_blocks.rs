@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use shared::players::constants::FALL_LIMIT;
+use shared::world::{
+    global_block_to_chunk_pos, ItemStack, ServerChunkWorldMap, ServerFallingBlock, ServerWorldMap,
+    WorldMap,
+};
+use shared::TICKS_PER_SECOND;
+use ulid::Ulid;
+
+use crate::world::stacks::spawn_item_stack;
+
+/// In blocks per second squared
+const BLOCK_GRAVITY: f32 = -20.0;
+/// A falling block never moves more than one block per tick, so it cannot skip the ground
+const MAX_FALL_SPEED: f32 = -(TICKS_PER_SECOND as f32);
+
+/// Air, water and decorations let blocks fall through\
+/// Chunks that are not loaded hold everything
+pub fn can_fall_into(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    if !chunks
+        .map
+        .contains_key(&global_block_to_chunk_pos(position))
+    {
+        return false;
+    }
+
+    chunks
+        .get_block_by_coordinates(position)
+        .is_none_or(|block| !block.id.has_hitbox())
+}
+
+/// Replaces the block by a falling entity
+pub fn start_falling(world_map: &mut ServerWorldMap, position: &IVec3) {
+    let Some(mut block) = world_map.chunks.remove_block_by_coordinates(position) else {
+        return;
+    };
+    block.breaking_progress = 0;
+
+    debug!("{:?} at {:?} starts falling", block.id, position);
+    world_map.falling_blocks.push(ServerFallingBlock {
+        id: Ulid::new().0,
+        despawned: false,
+        block,
+        pos: position.as_vec3(),
+        velocity: 0.0,
+    });
+}
+
+pub fn falling_blocks_system(mut world_map: ResMut<ServerWorldMap>) {
+    let world_map = world_map.as_mut();
+    let delta = 1.0 / TICKS_PER_SECOND as f32;
+
+    // Blocks that landed during the last tick have been broadcast, they can be forgotten
+    world_map
+        .falling_blocks
+        .retain(|falling| !falling.despawned);
+
+    let mut landed = Vec::new();
+    for falling in world_map.falling_blocks.iter_mut() {
+        let candidate = falling.pos.y + falling.velocity * delta;
+        if candidate < FALL_LIMIT {
+            falling.despawned = true;
+            continue;
+        }
+
+        let below = IVec3::new(
+            falling.pos.x.round() as i32,
+            candidate.floor() as i32,
+            falling.pos.z.round() as i32,
+        );
+
+        // Blocks above unloaded chunks wait for them to be loaded
+        if !world_map
+            .chunks
+            .map
+            .contains_key(&global_block_to_chunk_pos(&below))
+        {
+            continue;
+        }
+
+        if can_fall_into(&world_map.chunks, &below) {
+            falling.pos.y = candidate;
+            falling.velocity = (falling.velocity + BLOCK_GRAVITY * delta).max(MAX_FALL_SPEED);
+        } else {
+            falling.despawned = true;
+            landed.push((falling.block, below + IVec3::Y));
+        }
+    }
+
+    for (block, position) in landed {
+        // Something took the place of the block while it was falling, drop it instead
+        if !can_fall_into(&world_map.chunks, &position) {
+            for (item_id, nb) in block.id.get_drops(1) {
+                spawn_item_stack(
+                    world_map,
+                    ItemStack {
+                        item_id,
+                        item_type: item_id.get_default_type(),
+                        nb,
+                    },
+                    position.as_vec3(),
+                );
+            }
+            continue;
+        }
+
+        world_map.chunks.set_block(&position, block);
+        debug!("{:?} landed at {:?}", block.id, position);
+    }
+}
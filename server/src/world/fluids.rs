@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use shared::world::{
+    global_block_to_chunk_pos, BlockData, BlockId, ServerChunkWorldMap, WorldMap, FALLING_FLAG,
+    HORIZONTAL_NEIGHBOURS, MAX_FLOW_DISTANCE, SIX_OFFSETS,
+};
+
+/// Ticks between two steps of the water flow
+pub const WATER_TICK_DELAY: u64 = 5;
+
+/// What water sees when looking at a block
+#[derive(Debug, Clone, Copy)]
+enum FluidCell {
+    /// Solid block, or block in a chunk that is not loaded
+    Blocked,
+    /// Air, or a block washed away by water
+    Empty,
+    Water(BlockData),
+}
+
+fn get_cell(chunks: &ServerChunkWorldMap, position: &IVec3) -> FluidCell {
+    // Water never flows into unloaded chunks
+    if !chunks
+        .map
+        .contains_key(&global_block_to_chunk_pos(position))
+    {
+        return FluidCell::Blocked;
+    }
+
+    match chunks.get_block_by_coordinates(position) {
+        None => FluidCell::Empty,
+        Some(block) if block.id == BlockId::Water => FluidCell::Water(*block),
+        Some(block) if block.id.is_replaceable_by_fluid() => FluidCell::Empty,
+        Some(_) => FluidCell::Blocked,
+    }
+}
+
+/// Water spreads sideways only when it cannot fall through the block below
+fn spreads_sideways(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    match get_cell(chunks, &(*position - IVec3::Y)) {
+        FluidCell::Blocked => true,
+        FluidCell::Water(below) => below.is_water_source(),
+        FluidCell::Empty => false,
+    }
+}
+
+/// State water should have at `position` given its neighbours, `None` if there should be no water
+fn get_expected_state(chunks: &ServerChunkWorldMap, position: &IVec3) -> Option<u8> {
+    if let FluidCell::Water(_) = get_cell(chunks, &(*position + IVec3::Y)) {
+        return Some(FALLING_FLAG);
+    }
+
+    HORIZONTAL_NEIGHBOURS
+        .iter()
+        .filter_map(|offset| {
+            let neighbour = *position + *offset;
+            match get_cell(chunks, &neighbour) {
+                FluidCell::Water(block) if spreads_sideways(chunks, &neighbour) => {
+                    Some(block.flow_distance() + 1)
+                }
+                _ => None,
+            }
+        })
+        .filter(|distance| *distance <= MAX_FLOW_DISTANCE)
+        .min()
+}
+
+/// Sources never change, other blocks are filled, updated or dried up from their neighbours\
+/// Changed blocks notify their neighbours, which get scheduled in turn
+pub fn update_water(chunks: &mut ServerChunkWorldMap, position: &IVec3) {
+    let current = match get_cell(chunks, position) {
+        FluidCell::Blocked => return,
+        FluidCell::Water(block) if block.is_water_source() => return,
+        FluidCell::Water(block) => Some(block.state),
+        FluidCell::Empty => None,
+    };
+
+    let expected = get_expected_state(chunks, position);
+    if expected == current {
+        return;
+    }
+
+    match expected {
+        Some(state) => chunks.set_block(position, BlockData::water(state)),
+        None => {
+            chunks.remove_block_by_coordinates(position);
+        }
+    }
+}
+
+/// Whether the block should be scheduled after itself or a neighbour changed\
+/// Empty blocks are only worth updating when water may flow into them
+pub fn needs_fluid_update(chunks: &ServerChunkWorldMap, position: &IVec3) -> bool {
+    match get_cell(chunks, position) {
+        FluidCell::Water(_) => true,
+        FluidCell::Empty => SIX_OFFSETS.iter().any(|offset| {
+            matches!(
+                get_cell(chunks, &(*position + *offset)),
+                FluidCell::Water(_)
+            )
+        }),
+        FluidCell::Blocked => false,
+    }
+}
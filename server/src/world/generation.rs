@@ -3,6 +3,8 @@ use noise::{NoiseFn, Perlin};
 use shared::{world::*, CHUNK_SIZE};
 use std::collections::HashMap;
 
+const BIOME_SCALE: f64 = 0.01;
+
 fn generate_tree(chunk: &mut ServerChunk, x: i32, y: i32, z: i32, trunk: BlockId, leaves: BlockId) {
     // create trunk
     let trunk_height = 3 + rand::random::<u8>() % 3; // random height between 3 and 5
@@ -19,14 +21,9 @@ fn generate_tree(chunk: &mut ServerChunk, x: i32, y: i32, z: i32, trunk: BlockId
         let current_y = leaf_start_y + layer;
         for offset_x in -2i32..=2i32 {
             for offset_z in -2i32..=2i32 {
-                if (offset_x.abs() + offset_z.abs()) < 3 - layer {
-                    chunk.map.insert(
-                        IVec3::new(x + offset_x, current_y, z + offset_z),
-                        BlockData::new(leaves, false, BlockDirection::Front),
-                    );
-                } else if (offset_x.abs() + offset_z.abs()) == 3 - layer
-                    && rand::random::<f32>() < 0.2
-                    && layer < 2
+                let distance = offset_x.abs() + offset_z.abs();
+                if distance < 3 - layer
+                    || (distance == 3 - layer && rand::random::<f32>() < 0.2 && layer < 2)
                 {
                     chunk.map.insert(
                         IVec3::new(x + offset_x, current_y, z + offset_z),
@@ -120,14 +117,9 @@ fn generate_big_tree(
         let current_y = leaf_start_y + layer + 2;
         for offset_x in -2i32..=2i32 {
             for offset_z in -2i32..=2i32 {
-                if (offset_x.abs() + offset_z.abs()) < 3 - layer {
-                    chunk.map.insert(
-                        IVec3::new(x + offset_x, current_y, z + offset_z),
-                        BlockData::new(leaves, false, BlockDirection::Front),
-                    );
-                } else if (offset_x.abs() + offset_z.abs()) == 3 - layer
-                    && rand::random::<f32>() < 0.2
-                    && layer < 2
+                let distance = offset_x.abs() + offset_z.abs();
+                if distance < 3 - layer
+                    || (distance == 3 - layer && rand::random::<f32>() < 0.2 && layer < 2)
                 {
                     chunk.map.insert(
                         IVec3::new(x + offset_x, current_y, z + offset_z),
@@ -185,6 +177,54 @@ pub fn determine_biome(temperature: f64, humidity: f64) -> BiomeType {
     }
 }
 
+/// Temperature under which precipitation falls as snow, the one of the coldest biomes
+pub const SNOW_TEMPERATURE: f64 = 0.3;
+
+/// Biome of the column at (x, z), from its temperature and humidity
+fn get_biome(
+    x: i32,
+    z: i32,
+    biome_scale: f64,
+    temp_perlin: &Perlin,
+    humidity_perlin: &Perlin,
+) -> BiomeType {
+    let temperature =
+        (temp_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
+    let humidity =
+        (humidity_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
+    determine_biome(temperature, humidity)
+}
+
+/// Noise the biomes of the world are generated from, built once from the seed
+#[derive(Resource)]
+pub struct BiomeNoise {
+    temp_perlin: Perlin,
+    humidity_perlin: Perlin,
+}
+
+impl BiomeNoise {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            temp_perlin: Perlin::new(seed + 1),
+            humidity_perlin: Perlin::new(seed + 2),
+        }
+    }
+
+    /// Temperature of the column at (x, z) between 0 and 1, used to pick its biome
+    pub fn get_temperature_at(&self, x: i32, z: i32) -> f64 {
+        (self
+            .temp_perlin
+            .get([x as f64 * BIOME_SCALE, z as f64 * BIOME_SCALE])
+            + 1.0)
+            / 2.0
+    }
+
+    /// Biome the column at (x, z) was generated with
+    pub fn get_biome_at(&self, x: i32, z: i32) -> BiomeType {
+        get_biome(x, z, BIOME_SCALE, &self.temp_perlin, &self.humidity_perlin)
+    }
+}
+
 fn interpolated_height(
     x: i32,
     z: i32,
@@ -195,11 +235,7 @@ fn interpolated_height(
     scale: f64,
 ) -> i32 {
     // get the properties of the main biome at (x, z)
-    let temperature =
-        (temp_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
-    let humidity =
-        (humidity_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
-    let biome_type = determine_biome(temperature, humidity);
+    let biome_type = get_biome(x, z, biome_scale, temp_perlin, humidity_perlin);
     let biome = get_biome_data(biome_type);
 
     // initialize weighted values
@@ -261,18 +297,20 @@ pub fn generate_chunk(chunk_pos: IVec3, seed: u32) -> ServerChunk {
     let humidity_perlin = Perlin::new(seed + 2);
 
     let scale = 0.1;
-    let biome_scale = 0.01;
+    let biome_scale = BIOME_SCALE;
     let cx = chunk_pos.x;
     let cy = chunk_pos.y;
     let cz = chunk_pos.z;
 
     let mut chunk = ServerChunk {
         map: HashMap::new(),
+        block_entities: HashMap::new(),
         ts: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_millis() as u64,
         sent_to_clients: vec![],
+        light: ChunkLight::default(),
     };
 
     for dx in 0..CHUNK_SIZE {
@@ -280,14 +318,7 @@ pub fn generate_chunk(chunk_pos: IVec3, seed: u32) -> ServerChunk {
             let x = CHUNK_SIZE * cx + dx;
             let z = CHUNK_SIZE * cz + dz;
 
-            // calculate temperature and humidity
-            let temperature =
-                (temp_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
-            let humidity =
-                (humidity_perlin.get([x as f64 * biome_scale, z as f64 * biome_scale]) + 1.0) / 2.0;
-
-            // get biome regarding the two values
-            let biome_type = determine_biome(temperature, humidity);
+            let biome_type = get_biome(x, z, biome_scale, &temp_perlin, &humidity_perlin);
             let biome = get_biome_data(biome_type);
 
             // get terrain height
@@ -337,35 +368,33 @@ pub fn generate_chunk(chunk_pos: IVec3, seed: u32) -> ServerChunk {
                     // Add flowers
                     let flower_chance = rand::random::<f32>();
                     match biome_type {
-                        BiomeType::FlowerPlains => {
-                            // High probability for flowers in Flower Plains
-                            if flower_chance < 0.1 {
-                                let flower_type = if rand::random::<f32>() < 0.5 {
-                                    BlockId::Dandelion
-                                } else {
-                                    BlockId::Poppy
-                                };
-
-                                chunk.map.insert(
-                                    block_pos.with_y(block_pos.y + 1),
-                                    BlockData::new(flower_type, false, BlockDirection::Front),
-                                );
-                            }
+                        // High probability for flowers in Flower Plains
+                        BiomeType::FlowerPlains if flower_chance < 0.1 => {
+                            let flower_type = if rand::random::<f32>() < 0.5 {
+                                BlockId::Dandelion
+                            } else {
+                                BlockId::Poppy
+                            };
+
+                            chunk.map.insert(
+                                block_pos.with_y(block_pos.y + 1),
+                                BlockData::new(flower_type, false, BlockDirection::Front),
+                            );
                         }
-                        BiomeType::Plains | BiomeType::Forest | BiomeType::MediumMountain => {
-                            // Low probability for flowers in Plains, Forest, Medium Mountain
-                            if flower_chance < 0.02 {
-                                let flower_type = if rand::random::<f32>() < 0.5 {
-                                    BlockId::Dandelion
-                                } else {
-                                    BlockId::Poppy
-                                };
-
-                                chunk.map.insert(
-                                    block_pos.with_y(block_pos.y + 1),
-                                    BlockData::new(flower_type, false, BlockDirection::Front),
-                                );
-                            }
+                        // Low probability for flowers in Plains, Forest, Medium Mountain
+                        BiomeType::Plains | BiomeType::Forest | BiomeType::MediumMountain
+                            if flower_chance < 0.02 =>
+                        {
+                            let flower_type = if rand::random::<f32>() < 0.5 {
+                                BlockId::Dandelion
+                            } else {
+                                BlockId::Poppy
+                            };
+
+                            chunk.map.insert(
+                                block_pos.with_y(block_pos.y + 1),
+                                BlockData::new(flower_type, false, BlockDirection::Front),
+                            );
                         }
                         _ => {}
                     }
@@ -387,33 +416,21 @@ pub fn generate_chunk(chunk_pos: IVec3, seed: u32) -> ServerChunk {
                     // Add trees
                     let tree_chance = rand::random::<f32>();
                     match biome_type {
-                        BiomeType::Forest => {
-                            // High probability for trees in Forest
-                            if tree_chance < 0.06 && !chunk.map.contains_key(&above_surface_pos) {
-                                if tree_chance < 0.01 {
-                                    generate_big_tree(
-                                        &mut chunk,
-                                        dx,
-                                        dy + 1,
-                                        dz,
-                                        BlockId::OakLog,
-                                        BlockId::OakLeaves,
-                                    );
-                                } else {
-                                    generate_tree(
-                                        &mut chunk,
-                                        dx,
-                                        dy + 1,
-                                        dz,
-                                        BlockId::OakLog,
-                                        BlockId::OakLeaves,
-                                    );
-                                }
-                            }
-                        }
-                        BiomeType::FlowerPlains | BiomeType::MediumMountain => {
-                            // Medium probability for trees in Flower Plains and Medium Mountain
-                            if tree_chance < 0.02 && !chunk.map.contains_key(&above_surface_pos) {
+                        // High probability for trees in Forest
+                        BiomeType::Forest
+                            if tree_chance < 0.06
+                                && !chunk.map.contains_key(&above_surface_pos) =>
+                        {
+                            if tree_chance < 0.01 {
+                                generate_big_tree(
+                                    &mut chunk,
+                                    dx,
+                                    dy + 1,
+                                    dz,
+                                    BlockId::OakLog,
+                                    BlockId::OakLeaves,
+                                );
+                            } else {
                                 generate_tree(
                                     &mut chunk,
                                     dx,
@@ -424,6 +441,20 @@ pub fn generate_chunk(chunk_pos: IVec3, seed: u32) -> ServerChunk {
                                 );
                             }
                         }
+                        // Medium probability for trees in Flower Plains and Medium Mountain
+                        BiomeType::FlowerPlains | BiomeType::MediumMountain
+                            if tree_chance < 0.02
+                                && !chunk.map.contains_key(&above_surface_pos) =>
+                        {
+                            generate_tree(
+                                &mut chunk,
+                                dx,
+                                dy + 1,
+                                dz,
+                                BlockId::OakLog,
+                                BlockId::OakLeaves,
+                            );
+                        }
                         _ => {}
                     }
 
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_renet::renet::RenetServer;
+use shared::crafting::CRAFTING_RESULT_SLOT;
+use shared::messages::{
+    DamageSource, FullChatMessage, PlayerHealthUpdate, PlayerId, ServerToClientMessage,
+};
+use shared::players::constants::{FALL_LIMIT, MAX_HEALTH, SAFE_FALL_DISTANCE};
+use shared::players::{Inventory, Player};
+use shared::world::{ItemStack, MobAction, MobId, MobTarget, ServerWorldMap};
+use shared::TICKS_PER_SECOND;
+use std::collections::HashMap;
+
+use crate::init::ServerTime;
+use crate::network::broadcast_chat::ChatMessageEvent;
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::stacks::spawn_item_stack;
+
+/// Where players appear when joining for the first time or respawning
+pub const SPAWN_POINT: Vec3 = Vec3::new(0.0, 80.0, 0.0);
+
+/// Damage dealt every tick to players below `FALL_LIMIT`
+const VOID_DAMAGE: u32 = 4;
+
+const MOB_ATTACK_DAMAGE: u32 = 2;
+const MOB_ATTACK_RANGE: f32 = 1.5;
+const MOB_ATTACK_COOLDOWN: u64 = TICKS_PER_SECOND;
+
+#[derive(Event, Debug)]
+pub struct DamageEvent {
+    pub player_id: PlayerId,
+    pub amount: u32,
+    pub source: DamageSource,
+}
+
+/// Last tick at which each mob attacked
+#[derive(Resource, Default)]
+pub struct MobAttackCooldowns {
+    pub last_attack: HashMap<MobId, u64>,
+}
+
+pub fn get_fall_damage(fall_distance: f32) -> u32 {
+    (fall_distance - SAFE_FALL_DISTANCE).ceil().max(0.0) as u32
+}
+
+pub fn environment_damage_system(
+    world_map: Res<ServerWorldMap>,
+    time: Res<ServerTime>,
+    mut cooldowns: ResMut<MobAttackCooldowns>,
+    mut ev_damage: EventWriter<DamageEvent>,
+) {
+    for player in world_map.players.values() {
+        if !player.is_dead() && player.position.y < FALL_LIMIT {
+            ev_damage.send(DamageEvent {
+                player_id: player.id,
+                amount: VOID_DAMAGE,
+                source: DamageSource::Void,
+            });
+        }
+    }
+
+    cooldowns
+        .last_attack
+        .retain(|id, _| world_map.mobs.contains_key(id));
+
+    for (id, mob) in world_map.mobs.iter() {
+        let (MobAction::Attack, MobTarget::Player(target)) = (mob.action, mob.target) else {
+            continue;
+        };
+        let Some(player) = world_map.players.get(&target) else {
+            continue;
+        };
+        if player.is_dead() || mob.position.distance(player.position) > MOB_ATTACK_RANGE {
+            continue;
+        }
+
+        let last_attack = cooldowns.last_attack.get(id).copied();
+        if last_attack.is_some_and(|tick| time.0.saturating_sub(tick) < MOB_ATTACK_COOLDOWN) {
+            continue;
+        }
+
+        cooldowns.last_attack.insert(*id, time.0);
+        ev_damage.send(DamageEvent {
+            player_id: target,
+            amount: MOB_ATTACK_DAMAGE,
+            source: DamageSource::Mob,
+        });
+    }
+}
+
+pub fn apply_damage_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut server: ResMut<RenetServer>,
+    mut ev_damage: EventReader<DamageEvent>,
+    mut ev_chat: EventWriter<ChatMessageEvent>,
+) {
+    let world_map = world_map.as_mut();
+
+    for event in ev_damage.read() {
+        let Some(player) = world_map.players.get_mut(&event.player_id) else {
+            continue;
+        };
+        if player.is_dead()
+            || event.amount == 0
+            || !player.game_mode.takes_damage_from(event.source)
+        {
+            continue;
+        }
+
+        player.health = player.health.saturating_sub(event.amount);
+        debug!(
+            "Player {} took {} damage from {:?}, health: {}",
+            player.id, event.amount, event.source, player.health
+        );
+
+        server.send_game_message(
+            player.id,
+            ServerToClientMessage::PlayerHealth(PlayerHealthUpdate {
+                health: player.health,
+                source: Some(event.source),
+            }),
+        );
+
+        if !player.is_dead() {
+            continue;
+        }
+
+        info!("Player {} died: {:?}", player.name, event.source);
+        ev_chat.send(ChatMessageEvent(FullChatMessage {
+            author: "Server".to_string(),
+            content: get_death_message(&player.name, event.source),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        }));
+
+        // Items dropped in the void would fall forever, drop them at the spawn point instead
+        let drop_position = if player.position.y < FALL_LIMIT {
+            SPAWN_POINT
+        } else {
+            player.position
+        };
+
+        for stack in take_death_drops(&mut player.inventory) {
+            spawn_item_stack(world_map, stack, drop_position);
+        }
+    }
+}
+
+/// Empties the inventory of a dead player, returning the stacks to drop\
+/// The crafting result is only a preview of the ingredients, which are dropped instead
+fn take_death_drops(inventory: &mut Inventory) -> Vec<ItemStack> {
+    inventory.inner.remove(&CRAFTING_RESULT_SLOT);
+
+    let mut dropped: Vec<_> = inventory.inner.drain().map(|(_, s)| s).collect();
+    dropped.extend(inventory.floating_stack.take());
+    dropped
+}
+
+fn get_death_message(name: &str, source: DamageSource) -> String {
+    match source {
+        DamageSource::Fall => format!("{} hit the ground too hard", name),
+        DamageSource::Void => format!("{} fell out of the world", name),
+        DamageSource::Mob => format!("{} was killed by a mob", name),
+    }
+}
+
+/// Brings a dead player back to life at the spawn point
+pub fn respawn_player(player: &mut Player) {
+    player.health = MAX_HEALTH;
+    player.position = SPAWN_POINT;
+    player.velocity = Vec3::ZERO;
+    player.on_ground = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::crafting::CRAFTING_GRID_START;
+    use shared::world::ItemId;
+
+    fn stack(item_id: ItemId, nb: u32) -> ItemStack {
+        ItemStack {
+            item_id,
+            item_type: item_id.get_default_type(),
+            nb,
+        }
+    }
+
+    #[test]
+    fn death_drops_crafting_ingredients_but_not_the_result() {
+        let mut inventory = Inventory::new();
+        inventory.inner.insert(0, stack(ItemId::Dirt, 12));
+        inventory
+            .inner
+            .insert(CRAFTING_GRID_START, stack(ItemId::OakLog, 1));
+        inventory
+            .inner
+            .insert(CRAFTING_RESULT_SLOT, stack(ItemId::OakPlanks, 4));
+        inventory.floating_stack = Some(stack(ItemId::Stick, 2));
+
+        let mut dropped: Vec<_> = take_death_drops(&mut inventory)
+            .into_iter()
+            .map(|stack| (stack.item_id, stack.nb))
+            .collect();
+        dropped.sort_by_key(|(item_id, _)| format!("{:?}", item_id));
+
+        assert_eq!(
+            dropped,
+            vec![(ItemId::Dirt, 12), (ItemId::OakLog, 1), (ItemId::Stick, 2)]
+        );
+        assert!(inventory.inner.is_empty());
+        assert!(inventory.floating_stack.is_none());
+    }
+}
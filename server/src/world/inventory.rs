@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use bevy_renet::renet::RenetServer;
+use shared::crafting::{apply_inventory_action, RecipeRegistry};
+use shared::messages::{PlayerId, ServerToClientMessage};
+use shared::players::{Inventory, InventoryAction, InventoryUpdate};
+use shared::world::ServerWorldMap;
+use std::collections::HashMap;
+
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::BLOCK_REACH;
+
+#[derive(Event, Debug)]
+pub struct InventoryActionEvent {
+    pub client_id: PlayerId,
+    pub action: InventoryAction,
+    /// Block entity holding the clicked slot, `None` for the player's own inventory
+    pub container: Option<IVec3>,
+}
+
+/// Last inventory sent to each player, used to only send what changed
+#[derive(Resource, Default)]
+pub struct SentInventories {
+    pub inventories: HashMap<PlayerId, Inventory>,
+}
+
+pub fn handle_inventory_actions(
+    mut world_map: ResMut<ServerWorldMap>,
+    mut events: EventReader<InventoryActionEvent>,
+    recipes: Res<RecipeRegistry>,
+    mut sent: ResMut<SentInventories>,
+) {
+    let world_map = world_map.as_mut();
+
+    for event in events.read() {
+        let Some(player) = world_map.players.get_mut(&event.client_id) else {
+            continue;
+        };
+
+        if let Some(position) = event.container {
+            let in_reach = player.position.distance(position.as_vec3()) <= BLOCK_REACH;
+            let applied = in_reach
+                && !player.is_dead()
+                && player.game_mode.can_interact()
+                && world_map
+                    .chunks
+                    .get_block_entity_mut(&position)
+                    .is_some_and(|entity| entity.apply_action(&mut player.inventory, event.action));
+
+            if !applied {
+                debug!(
+                    "Player {} cannot use {:?} on the container at {:?}",
+                    event.client_id, event.action, position
+                );
+                // The client predicted the move, send back the real container and inventory
+                world_map.chunks.blocks_to_update.push(position);
+                sent.inventories.remove(&event.client_id);
+            }
+            continue;
+        }
+
+        let allowed = match event.action {
+            InventoryAction::PickItem { .. } => player.game_mode.has_unlimited_blocks(),
+            _ => player.game_mode.can_interact(),
+        };
+        if !allowed {
+            debug!(
+                "Player {} cannot use {:?} in {} mode",
+                event.client_id, event.action, player.game_mode
+            );
+            continue;
+        }
+
+        apply_inventory_action(&mut player.inventory, event.action, &recipes);
+    }
+}
+
+pub fn sync_inventories_system(
+    mut server: ResMut<RenetServer>,
+    world_map: Res<ServerWorldMap>,
+    mut sent: ResMut<SentInventories>,
+) {
+    sent.inventories
+        .retain(|id, _| world_map.players.contains_key(id));
+
+    for (id, player) in world_map.players.iter() {
+        let update = match sent.inventories.get(id) {
+            Some(previous) => player.inventory.diff(previous),
+            // First time this player is seen : send everything
+            None => Some(InventoryUpdate::Snapshot(player.inventory.clone())),
+        };
+
+        if let Some(update) = update {
+            debug!("Sending inventory update to {}", id);
+            server.send_game_message(*id, ServerToClientMessage::InventoryUpdate(update));
+            sent.inventories.insert(*id, player.inventory.clone());
+        }
+    }
+}
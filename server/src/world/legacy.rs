@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use shared::messages::PlayerId;
+use shared::players::Player;
+use shared::world::{
+    BlockData, BlockDirection, BlockId, ItemStack, MobAction, MobId, MobKind, MobTarget,
+    ServerChunk, ServerItemStack, ServerMob, WorldSeed,
+};
+use std::collections::HashMap;
+
+/// Format used before region files, a single `saves/<world>.ron`\
+/// These types mirror the ones of that time and must not change with the current ones,
+/// the fields added since are filled in when converting
+#[derive(Deserialize)]
+pub struct LegacyWorldData {
+    pub seed: WorldSeed,
+    pub map: LegacyWorldMap,
+    pub time: u64,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyWorldMap {
+    pub chunks: LegacyChunkMap,
+    pub players: HashMap<PlayerId, LegacyPlayer>,
+    pub mobs: HashMap<MobId, LegacyMob>,
+    pub item_stacks: Vec<LegacyItemStack>,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyChunkMap {
+    pub map: HashMap<IVec3, LegacyChunk>,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyChunk {
+    pub map: HashMap<IVec3, LegacyBlockData>,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyBlockData {
+    pub id: BlockId,
+    pub flipped: bool,
+    pub direction: BlockDirection,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyPlayer {
+    pub id: PlayerId,
+    pub name: String,
+    pub position: Vec3,
+    pub camera_transform: Transform,
+    pub is_flying: bool,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyMob {
+    pub kind: MobKind,
+    pub target: MobTarget,
+    pub action: MobAction,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+#[derive(Deserialize)]
+pub struct LegacyItemStack {
+    pub id: u128,
+    pub despawned: bool,
+    pub stack: ItemStack,
+    pub pos: Vec3,
+    pub timestamp: u64,
+}
+
+impl From<LegacyChunk> for ServerChunk {
+    fn from(chunk: LegacyChunk) -> Self {
+        ServerChunk {
+            map: chunk
+                .map
+                .into_iter()
+                .map(|(pos, block)| {
+                    (
+                        pos,
+                        BlockData::new(block.id, block.flipped, block.direction),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<LegacyPlayer> for Player {
+    fn from(player: LegacyPlayer) -> Self {
+        let mut new_player = Player::new(
+            player.id,
+            player.name,
+            player.position,
+            player.camera_transform,
+        );
+        new_player.is_flying = player.is_flying;
+        new_player
+    }
+}
+
+impl From<LegacyMob> for ServerMob {
+    fn from(mob: LegacyMob) -> Self {
+        ServerMob {
+            health: mob.kind.get_max_health(),
+            kind: mob.kind,
+            target: mob.target,
+            action: mob.action,
+            position: mob.position,
+            rotation: mob.rotation,
+            velocity: Vec3::ZERO,
+            on_ground: false,
+        }
+    }
+}
+
+impl From<LegacyItemStack> for ServerItemStack {
+    fn from(stack: LegacyItemStack) -> Self {
+        ServerItemStack {
+            id: stack.id,
+            despawned: stack.despawned,
+            stack: stack.stack,
+            pos: stack.pos,
+            timestamp: stack.timestamp,
+            velocity: Vec3::ZERO,
+            sent_to_clients: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY_SAVE: &str = r#"(
+        seed: (42),
+        map: (
+            name: "old",
+            chunks: (
+                map: {
+                    (0, 4, 0): (
+                        map: {
+                            (1, 2, 3): (id: Dirt, flipped: false, direction: Front, breaking_progress: 0),
+                        },
+                        ts: 5,
+                        sent_to_clients: [],
+                    ),
+                },
+                chunks_to_update: [],
+            ),
+            players: {},
+            mobs: {
+                7: (
+                    kind: Fox,
+                    target: None,
+                    action: Idle,
+                    position: (1.0, 70.0, 3.0),
+                    rotation: (0.0, 0.0, 0.0, 1.0),
+                ),
+            },
+            item_stacks: [],
+            time: 100,
+        ),
+        time: 100,
+    )"#;
+
+    #[test]
+    fn legacy_saves_are_converted() {
+        let legacy: LegacyWorldData = ron::de::from_str(LEGACY_SAVE).unwrap();
+        assert_eq!(legacy.seed.0, 42);
+        assert_eq!(legacy.time, 100);
+
+        let mut chunks = legacy.map.chunks.map;
+        let chunk: ServerChunk = chunks.remove(&IVec3::new(0, 4, 0)).unwrap().into();
+        let block = chunk.map[&IVec3::new(1, 2, 3)];
+        assert_eq!(block.id, BlockId::Dirt);
+        assert_eq!(block.state, 0);
+
+        let mut mobs = legacy.map.mobs;
+        let mob: ServerMob = mobs.remove(&7).unwrap().into();
+        assert_eq!(mob.kind, MobKind::Fox);
+        assert_eq!(mob.health, MobKind::Fox.get_max_health());
+    }
+}
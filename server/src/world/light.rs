@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use shared::world::{light_chunk, update_light, ServerWorldMap};
+use std::collections::HashSet;
+
+/// Limits the time spent lighting chunks when a lot of them are loaded at once\
+/// Chunks over the limit are lit on the next ticks
+const MAX_CHUNKS_LIT_PER_TICK: usize = 16;
+
+/// Lights the chunks loaded since the last tick, then updates the light around the blocks that changed
+pub fn light_system(mut world_map: ResMut<ServerWorldMap>) {
+    let chunks = &mut world_map.chunks;
+
+    let unlit: Vec<IVec3> = chunks
+        .map
+        .iter()
+        .filter(|(_, chunk)| !chunk.light.is_lit)
+        .map(|(chunk_pos, _)| *chunk_pos)
+        .take(MAX_CHUNKS_LIT_PER_TICK)
+        .collect();
+    for chunk_pos in unlit.iter() {
+        light_chunk(chunks, chunk_pos);
+    }
+
+    let updates: HashSet<IVec3> = chunks.light_updates.drain(..).collect();
+    for position in updates.iter() {
+        update_light(chunks, position);
+    }
+
+    if !unlit.is_empty() {
+        debug!("Lit {} chunks", unlit.len());
+    }
+}
@@ -4,70 +4,123 @@ use shared::world::data::{ServerWorldMap, WorldSeed};
 use shared::world::get_game_folder;
 use shared::GameFolderPaths;
 use std::fs;
-use std::path::Path;
-
-use crate::world::data::SAVE_PATH;
 use std::path::PathBuf;
 
-#[derive(serde::Serialize, serde::Deserialize)]
-pub struct WorldData {
-    pub seed: WorldSeed,
-    pub map: ServerWorldMap,
-    pub time: u64,
-}
+use crate::world::data::{WorldData, WorldSettings, SAVE_PATH};
+use crate::world::legacy::LegacyWorldData;
+use crate::world::storage::{WorldEntities, WorldStorage};
+use crate::world::weather::WeatherState;
 
 pub fn load_world_data(
-    file_name: &str,
-    app: &App,
-) -> Result<WorldData, Box<dyn std::error::Error>> {
-    let game_folder_path = app.world().get_resource::<GameFolderPaths>().unwrap();
-
-    let file_path: PathBuf = get_game_folder(Some(game_folder_path))
-        .join(SAVE_PATH)
-        .join(format!("{file_name}.ron"));
-    let path: &Path = file_path.as_path();
+    world_name: &str,
+    storage: &mut WorldStorage,
+    game_folder_paths: &GameFolderPaths,
+    new_world_settings: WorldSettings,
+) -> Result<(WorldData, ServerWorldMap), Box<dyn std::error::Error>> {
+    if !storage.exists() {
+        let legacy_path: PathBuf = get_game_folder(Some(game_folder_paths))
+            .join(SAVE_PATH)
+            .join(format!("{world_name}.ron"));
+        if legacy_path.exists() {
+            return import_legacy_world(world_name, &legacy_path, storage, new_world_settings);
+        }
 
-    if !path.exists() {
         info!(
-            "World data file not found: {}. Generating default world and seed.",
-            file_path.display()
+            "World data not found: {}. Generating default world and seed.",
+            storage.world_path().display()
         );
-        let seed = WorldSeed(rand::random::<u32>());
-        return Ok(WorldData {
-            map: ServerWorldMap {
-                name: file_name.to_string(),
-                ..Default::default()
-            },
-            seed,
+        let world_data = WorldData {
+            name: world_name.to_string(),
+            seed: WorldSeed(rand::random::<u32>()),
             time: 0,
-        });
+            settings: new_world_settings,
+            weather: WeatherState::default(),
+        };
+        // Chunks get written to disk when unloaded, so the seed they were
+        // generated with must be stored right away
+        storage.save_level(&world_data)?;
+        let world_map = ServerWorldMap {
+            name: world_name.to_string(),
+            ..Default::default()
+        };
+        return Ok((world_data, world_map));
     }
 
-    let contents: String = fs::read_to_string(path)?;
-    let world_data: WorldData = from_str(&contents)?;
+    let world_data = storage.load_level()?;
+    let entities = storage.load_entities()?;
 
-    info!("Found world data file from disk: {}", file_path.display());
+    // Chunks are loaded from the region files when players need them,
+    // and players from their own file when they join
+    let world_map = ServerWorldMap {
+        name: world_data.name.clone(),
+        mobs: entities.mobs,
+        item_stacks: entities.item_stacks,
+        falling_blocks: entities.falling_blocks,
+        time: world_data.time,
+        ..Default::default()
+    };
 
-    Ok(world_data)
-}
+    info!(
+        "Found world data from disk: {}",
+        storage.world_path().display()
+    );
 
-pub fn load_world_map(
-    file_name: &str,
-    app: &App,
-) -> Result<ServerWorldMap, Box<dyn std::error::Error>> {
-    let world_data = load_world_data(file_name, app)?;
-    Ok(world_data.map)
+    Ok((world_data, world_map))
 }
 
-pub fn load_world_time(file_name: &str, app: &App) -> Result<u64, Box<dyn std::error::Error>> {
-    let world_data = load_world_data(file_name, app)?;
-    Ok(world_data.time)
-}
+fn import_legacy_world(
+    world_name: &str,
+    legacy_path: &PathBuf,
+    storage: &mut WorldStorage,
+    settings: WorldSettings,
+) -> Result<(WorldData, ServerWorldMap), Box<dyn std::error::Error>> {
+    let contents: String = fs::read_to_string(legacy_path)?;
+    let legacy: LegacyWorldData = from_str(&contents)?;
+
+    info!(
+        "Importing world from legacy save file: {}",
+        legacy_path.display()
+    );
+
+    let mut world_map = ServerWorldMap {
+        name: world_name.to_string(),
+        mobs: legacy
+            .map
+            .mobs
+            .into_iter()
+            .map(|(id, mob)| (id, mob.into()))
+            .collect(),
+        item_stacks: legacy.map.item_stacks.into_iter().map(Into::into).collect(),
+        time: legacy.time,
+        ..Default::default()
+    };
+
+    // Players are now stored in their own file, loaded when they join
+    for (_, player) in legacy.map.players {
+        storage.save_player(&player.into())?;
+    }
+
+    for (chunk_pos, chunk) in legacy.map.chunks.map {
+        let chunk = chunk.into();
+        storage.save_chunk(&chunk_pos, &chunk)?;
+        world_map.chunks.map.insert(chunk_pos, chunk);
+    }
+    storage.save_entities(&WorldEntities {
+        mobs: world_map.mobs.clone(),
+        item_stacks: world_map.item_stacks.clone(),
+        falling_blocks: world_map.falling_blocks.clone(),
+    })?;
+
+    // Written last: the world only counts as imported once everything else is on disk,
+    // an interrupted import starts over from the legacy file
+    let world_data = WorldData {
+        name: world_name.to_string(),
+        seed: legacy.seed,
+        time: legacy.time,
+        settings,
+        weather: WeatherState::default(),
+    };
+    storage.save_level(&world_data)?;
 
-pub fn load_world_seed(
-    file_name: &str,
-    app: &App,
-) -> Result<WorldSeed, Box<dyn std::error::Error>> {
-    let world_data = load_world_data(file_name, app)?;
-    Ok(world_data.seed)
+    Ok((world_data, world_map))
 }
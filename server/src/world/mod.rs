@@ -1,59 +1,256 @@
 pub mod background_generation;
+pub mod block_updates;
 pub mod broadcast_world;
-mod data;
+pub mod chunk_lifecycle;
+pub mod data;
+pub mod falling_blocks;
+pub mod fluids;
 pub mod generation;
+pub mod health;
+pub mod inventory;
+pub mod legacy;
+pub mod light;
 pub mod load_from_file;
+pub mod random_ticks;
+mod region;
 pub mod save;
 pub mod simulation;
 pub mod stacks;
+pub mod storage;
+pub mod ticks;
+pub mod weather;
 
 use bevy::prelude::Event;
 use bevy::prelude::EventReader;
 use bevy::prelude::IVec3;
 use bevy::prelude::ResMut;
 use bevy::prelude::*;
-use shared::world::{BlockData, ItemStack, ServerItemStack, ServerWorldMap, WorldMap};
-use ulid::Ulid;
+use inventory::SentInventories;
+use random_ticks::PERSISTENT_LEAVES;
+use shared::messages::PlayerId;
+use shared::world::{
+    is_supported, toggle_door, BlockData, BlockId, BlockShape, ItemStack, ItemType,
+    ServerChunkWorldMap, ServerWorldMap, WorldMap, DOOR_UPPER, TORCH_ON_WALL,
+};
+use stacks::spawn_item_stack;
+use std::collections::HashMap;
+
+use crate::init::ServerTime;
+
+/// Distance from which a player can place, break or use a block, slightly more than the client allows
+pub const BLOCK_REACH: f32 = 8.0;
 
 #[derive(Event, Debug)]
 pub struct BlockInteractionEvent {
+    pub player_id: PlayerId,
     pub position: IVec3,
     pub block_type: Option<BlockData>, // None = delete, Some = add
+    /// Hotbar slot holding the placed block, or the tool used to break one
+    pub hotbar_slot: u32,
+}
+
+/// Ticks a break may come earlier than expected, messages of a player can arrive a few ticks apart
+const BREAK_TICKS_TOLERANCE: u64 = 5;
+
+/// Block each player started breaking, and the tick they started at
+#[derive(Resource, Default)]
+pub struct BreakingBlocks {
+    pub started: HashMap<PlayerId, (IVec3, u64)>,
+}
+
+/// Items stored in a block are dropped when it is broken, even in creative mode
+fn drop_block_entity_contents(world_map: &mut ServerWorldMap, position: &IVec3) {
+    let contents: Vec<ItemStack> = world_map
+        .chunks
+        .get_block_entity(position)
+        .map(|entity| entity.items().values().copied().collect())
+        .unwrap_or_default();
+
+    for stack in contents {
+        spawn_item_stack(world_map, stack, position.as_vec3());
+    }
+}
+
+/// The client may have already changed the block and used the item,
+/// sends the real block and the whole inventory back
+fn reject_interaction(
+    chunks: &mut ServerChunkWorldMap,
+    sent: &mut SentInventories,
+    event: &BlockInteractionEvent,
+) {
+    chunks.blocks_to_update.push(event.position);
+    sent.inventories.remove(&event.player_id);
+}
+
+/// Block placed by a player, only its orientation comes from the client\
+/// Its state is rebuilt, so a client cannot place open doors or stacked snow layers
+fn get_placed_block(block: &BlockData) -> BlockData {
+    let shape = block.id.get_shape();
+    let state = match block.id {
+        // Leaves placed by players never decay
+        BlockId::OakLeaves | BlockId::SpruceLeaves => PERSISTENT_LEAVES,
+        // Whether a torch hangs on a wall, `is_supported` checks there is one
+        _ if shape == BlockShape::Torch => block.state & TORCH_ON_WALL,
+        _ => 0,
+    };
+    BlockData {
+        flipped: block.flipped && shape.can_be_flipped(),
+        state,
+        ..BlockData::new(block.id, false, block.direction)
+    }
 }
 
 pub fn handle_block_interactions(
     mut world_map: ResMut<ServerWorldMap>,
     mut events: EventReader<BlockInteractionEvent>,
+    mut sent: ResMut<SentInventories>,
+    (mut breaking, time): (ResMut<BreakingBlocks>, Res<ServerTime>),
 ) {
+    let world_map = world_map.as_mut();
+
     for event in events.read() {
+        let Some(player) = world_map.players.get_mut(&event.player_id) else {
+            continue;
+        };
+        let in_reach = player.position.distance(event.position.as_vec3()) <= BLOCK_REACH;
+        if !in_reach || player.is_dead() || !player.game_mode.can_interact() {
+            debug!(
+                "Player {} cannot interact with the block at {:?}",
+                event.player_id, event.position
+            );
+            reject_interaction(&mut world_map.chunks, &mut sent, event);
+            continue;
+        }
+
+        let unlimited_blocks = player.game_mode.has_unlimited_blocks();
+
         match &event.block_type {
             Some(block) => {
-                world_map.chunks.set_block(&event.position, *block);
+                let owns_block = player
+                    .inventory
+                    .inner
+                    .get(&event.hotbar_slot)
+                    .is_some_and(|stack| stack.item_type == ItemType::Block(block.id));
+
+                if !owns_block && !unlimited_blocks {
+                    debug!(
+                        "Player {} tried to place {:?} without owning it",
+                        event.player_id, block.id
+                    );
+                    reject_interaction(&mut world_map.chunks, &mut sent, event);
+                    continue;
+                }
+
+                // Placing over a block would get rid of it without breaking it
+                let is_free = world_map
+                    .chunks
+                    .get_block_by_coordinates(&event.position)
+                    .is_none_or(|target| target.id.is_replaceable());
+                if !is_free {
+                    debug!("No room for {:?} at {:?}", block.id, event.position);
+                    reject_interaction(&mut world_map.chunks, &mut sent, event);
+                    continue;
+                }
+
+                let block = get_placed_block(block);
+                // Doors are placed from their lower half, and need room for the upper one
+                let door_top =
+                    (block.id.get_shape() == BlockShape::Door).then_some(event.position + IVec3::Y);
+                if let Some(top) = door_top {
+                    if world_map.chunks.get_block_by_coordinates(&top).is_some() {
+                        debug!("No room for a door at {:?}", event.position);
+                        reject_interaction(&mut world_map.chunks, &mut sent, event);
+                        continue;
+                    }
+                }
+                if !is_supported(&world_map.chunks, &event.position, &block) {
+                    debug!("Nothing to hold {:?} at {:?}", block.id, event.position);
+                    reject_interaction(&mut world_map.chunks, &mut sent, event);
+                    continue;
+                }
+
+                if !unlimited_blocks {
+                    player
+                        .inventory
+                        .remove_item_from_stack(event.hotbar_slot, 1);
+                }
+                world_map.chunks.set_block(&event.position, block);
+                if let Some(top) = door_top {
+                    world_map.chunks.set_block(
+                        &top,
+                        BlockData {
+                            state: DOOR_UPPER,
+                            ..block
+                        },
+                    );
+                }
                 debug!("Block added at {:?}: {:?}", event.position, block);
             }
             None => {
-                for (id, nb) in world_map
-                    .chunks
-                    .get_block_by_coordinates(&event.position)
-                    .unwrap()
-                    .id
-                    .get_drops(1)
-                {
-                    world_map.item_stacks.push(ServerItemStack {
-                        id: Ulid::new().0,
-                        despawned: false,
-                        stack: ItemStack {
-                            item_id: id,
-                            item_type: id.get_default_type(),
+                let Some(&block) = world_map.chunks.get_block_by_coordinates(&event.position)
+                else {
+                    continue;
+                };
+
+                if block.id.get_break_steps(None).is_none() {
+                    debug!("Player {} tried to break {:?}", event.player_id, block.id);
+                    reject_interaction(&mut world_map.chunks, &mut sent, event);
+                    continue;
+                }
+
+                // Creative players break blocks instantly, without using their tools or getting drops
+                if player.game_mode.breaks_instantly() {
+                    drop_block_entity_contents(world_map, &event.position);
+                    world_map
+                        .chunks
+                        .remove_block_by_coordinates(&event.position);
+                    info!("Block removed at {:?}", event.position);
+                    continue;
+                }
+
+                let tool = player
+                    .inventory
+                    .inner
+                    .get(&event.hotbar_slot)
+                    .map(|stack| stack.item_id);
+
+                // The client breaks the block once it has been breaking it for enough ticks
+                let started = breaking.started.remove(&event.player_id);
+                let steps = block.id.get_break_steps(tool).unwrap_or_default() as u64;
+                let broken_in_time = started.is_some_and(|(position, tick)| {
+                    position == event.position
+                        && time.0.saturating_sub(tick) + BREAK_TICKS_TOLERANCE >= steps
+                });
+                if !broken_in_time {
+                    debug!(
+                        "Player {} broke {:?} too fast",
+                        event.player_id, event.position
+                    );
+                    reject_interaction(&mut world_map.chunks, &mut sent, event);
+                    continue;
+                }
+
+                if block.id.is_effective_tool(tool) {
+                    player.inventory.wear_tool(event.hotbar_slot);
+                }
+
+                let drops = if block.id.can_harvest(tool) {
+                    block.id.get_drops(1)
+                } else {
+                    Default::default()
+                };
+
+                drop_block_entity_contents(world_map, &event.position);
+                for (item_id, nb) in drops {
+                    spawn_item_stack(
+                        world_map,
+                        ItemStack {
+                            item_id,
+                            item_type: item_id.get_default_type(),
                             nb,
                         },
-                        pos: Vec3::new(
-                            event.position.x as f32,
-                            event.position.y as f32,
-                            event.position.z as f32,
-                        ),
-                        timestamp: 0,
-                    });
+                        event.position.as_vec3(),
+                    );
                 }
 
                 world_map
@@ -64,3 +261,52 @@ pub fn handle_block_interactions(
         }
     }
 }
+
+/// Right-click on a block that does something when used, such as a door
+pub fn use_block(world_map: &mut ServerWorldMap, player_id: PlayerId, position: &IVec3) {
+    let Some(player) = world_map.players.get(&player_id) else {
+        return;
+    };
+    let in_reach = player.position.distance(position.as_vec3()) <= BLOCK_REACH;
+    if !in_reach || player.is_dead() || !player.game_mode.can_interact() {
+        debug!(
+            "Player {} cannot use the block at {:?}",
+            player_id, position
+        );
+        // The client may have already changed the block, send the real one back
+        world_map.chunks.blocks_to_update.push(*position);
+        return;
+    }
+
+    if toggle_door(&mut world_map.chunks, position).is_empty() {
+        world_map.chunks.blocks_to_update.push(*position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::world::{BlockDirection, DOOR_OPEN};
+
+    fn placed(id: BlockId, state: u8) -> BlockData {
+        get_placed_block(&BlockData {
+            state,
+            ..BlockData::new(id, true, BlockDirection::Left)
+        })
+    }
+
+    #[test]
+    fn placed_blocks_only_keep_their_orientation() {
+        assert_eq!(placed(BlockId::OakDoor, DOOR_OPEN).state, 0);
+        assert_eq!(placed(BlockId::SnowLayer, 7).state, 0);
+        assert!(!placed(BlockId::Cactus, 0).flipped);
+        assert!(placed(BlockId::OakSlab, 0).flipped);
+        assert_eq!(placed(BlockId::OakSlab, 0).direction, BlockDirection::Left);
+    }
+
+    #[test]
+    fn placed_torches_and_leaves_keep_their_flags() {
+        assert_eq!(placed(BlockId::Torch, 0xFF).state, TORCH_ON_WALL);
+        assert_eq!(placed(BlockId::OakLeaves, 0).state, PERSISTENT_LEAVES);
+    }
+}
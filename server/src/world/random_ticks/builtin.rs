@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use rand::Rng;
+use shared::world::{
+    BiomeType, BlockData, BlockDirection, BlockId, BlockTransparency, ItemStack, WorldMap,
+};
+
+use crate::world::stacks::spawn_item_stack;
+
+use super::{RandomTickContext, RandomTickRegistry};
+
+/// Leaves state flag, set on leaves placed by players so they never decay
+pub const PERSISTENT_LEAVES: u8 = 0x1;
+
+/// Leaves further than this from any log decay
+const LEAF_DECAY_DISTANCE: i32 = 4;
+
+const CACTUS_MAX_HEIGHT: i32 = 3;
+/// Chance for a cactus to grow one block on each random tick
+const CACTUS_GROWTH_CHANCE: f64 = 1.0 / 16.0;
+
+pub fn register_builtin_random_ticks(registry: &mut RandomTickRegistry) {
+    registry.register(BlockId::Grass, grass);
+    registry.register(BlockId::Cactus, cactus);
+    registry.register(BlockId::OakLeaves, leaves);
+    registry.register(BlockId::SpruceLeaves, leaves);
+    registry.register(BlockId::Water, water);
+}
+
+/// Opaque blocks and water stop grass from growing, thin blocks such as snow layers do not
+fn is_covered(context: &RandomTickContext, position: &IVec3) -> bool {
+    context
+        .get_block(&(*position + IVec3::Y))
+        .is_some_and(|block| match block.id.get_visibility() {
+            BlockTransparency::Solid => block.id.is_full_cube(),
+            BlockTransparency::Liquid => true,
+            _ => false,
+        })
+}
+
+/// Dies when covered, otherwise spreads to a random dirt block around
+fn grass(context: &mut RandomTickContext, position: &IVec3, _block: BlockData) {
+    if is_covered(context, position) {
+        context.world_map.chunks.set_block(
+            position,
+            BlockData::new(BlockId::Dirt, false, BlockDirection::Front),
+        );
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let target = *position
+        + IVec3::new(
+            rng.gen_range(-1..=1),
+            rng.gen_range(-3..=1),
+            rng.gen_range(-1..=1),
+        );
+
+    if context
+        .get_block(&target)
+        .is_some_and(|block| block.id == BlockId::Dirt)
+        && !is_covered(context, &target)
+    {
+        context.world_map.chunks.set_block(
+            &target,
+            BlockData::new(BlockId::Grass, false, BlockDirection::Front),
+        );
+    }
+}
+
+/// Sometimes grows one block, up to `CACTUS_MAX_HEIGHT`
+fn cactus(context: &mut RandomTickContext, position: &IVec3, _block: BlockData) {
+    if !rand::thread_rng().gen_bool(CACTUS_GROWTH_CHANCE) {
+        return;
+    }
+
+    let above = *position + IVec3::Y;
+    if !context.is_loaded(&above) || context.get_block(&above).is_some() {
+        return;
+    }
+
+    let height = (1..CACTUS_MAX_HEIGHT)
+        .take_while(|dy| {
+            context
+                .get_block(&(*position - IVec3::Y * *dy))
+                .is_some_and(|below| below.id == BlockId::Cactus)
+        })
+        .count() as i32
+        + 1;
+    if height >= CACTUS_MAX_HEIGHT {
+        return;
+    }
+
+    context.world_map.chunks.set_block(
+        &above,
+        BlockData::new(BlockId::Cactus, false, BlockDirection::Front),
+    );
+}
+
+/// Decays when no log is close enough to hold it
+fn leaves(context: &mut RandomTickContext, position: &IVec3, block: BlockData) {
+    if block.state & PERSISTENT_LEAVES != 0 {
+        return;
+    }
+
+    let range = -LEAF_DECAY_DISTANCE..=LEAF_DECAY_DISTANCE;
+    for x in range.clone() {
+        for y in range.clone() {
+            for z in range.clone() {
+                let candidate = *position + IVec3::new(x, y, z);
+                // A log may be hidden in a chunk that is not loaded
+                if !context.is_loaded(&candidate) {
+                    return;
+                }
+                if context
+                    .get_block(&candidate)
+                    .is_some_and(|block| matches!(block.id, BlockId::OakLog | BlockId::SpruceLog))
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    debug!("{:?} decayed at {:?}", block.id, position);
+    context
+        .world_map
+        .chunks
+        .remove_block_by_coordinates(position);
+    for (item_id, nb) in block.id.get_drops(1) {
+        spawn_item_stack(
+            context.world_map,
+            ItemStack {
+                item_id,
+                item_type: item_id.get_default_type(),
+                nb,
+            },
+            position.as_vec3(),
+        );
+    }
+}
+
+/// Sources open to the air freeze in cold biomes
+fn water(context: &mut RandomTickContext, position: &IVec3, block: BlockData) {
+    if !block.is_water_source() {
+        return;
+    }
+
+    let above = *position + IVec3::Y;
+    if !context.is_loaded(&above) || context.get_block(&above).is_some() {
+        return;
+    }
+
+    if context.biomes.get_biome_at(position.x, position.z) == BiomeType::IcePlain {
+        context.world_map.chunks.set_block(
+            position,
+            BlockData::new(BlockId::Ice, false, BlockDirection::Front),
+        );
+    }
+}
@@ -0,0 +1,110 @@
+mod builtin;
+
+pub use builtin::PERSISTENT_LEAVES;
+
+use bevy::prelude::*;
+use rand::Rng;
+use shared::world::{
+    global_block_to_chunk_pos, to_global_pos, BlockData, BlockId, ServerWorldMap, WorldMap,
+};
+use shared::CHUNK_SIZE;
+use std::collections::HashMap;
+
+use crate::world::data::{WorldSettings, MAX_RANDOM_TICK_SPEED};
+use crate::world::generation::BiomeNoise;
+
+/// Everything a random tick handler can read or update
+pub struct RandomTickContext<'a> {
+    pub world_map: &'a mut ServerWorldMap,
+    pub biomes: &'a BiomeNoise,
+}
+
+impl RandomTickContext<'_> {
+    pub fn is_loaded(&self, position: &IVec3) -> bool {
+        self.world_map
+            .chunks
+            .map
+            .contains_key(&global_block_to_chunk_pos(position))
+    }
+
+    /// `None` for air, and for blocks in chunks that are not loaded
+    pub fn get_block(&self, position: &IVec3) -> Option<BlockData> {
+        if !self.is_loaded(position) {
+            return None;
+        }
+        self.world_map
+            .chunks
+            .get_block_by_coordinates(position)
+            .copied()
+    }
+}
+
+/// Called with the position of the ticked block and its data
+pub type RandomTickHandler = fn(&mut RandomTickContext, &IVec3, BlockData);
+
+/// Random tick behaviour of each block, blocks without one are ignored
+#[derive(Resource, Default)]
+pub struct RandomTickRegistry {
+    handlers: HashMap<BlockId, RandomTickHandler>,
+}
+
+impl RandomTickRegistry {
+    pub fn register(&mut self, block: BlockId, handler: RandomTickHandler) {
+        self.handlers.insert(block, handler);
+    }
+
+    pub fn get(&self, block: &BlockId) -> Option<&RandomTickHandler> {
+        self.handlers.get(block)
+    }
+}
+
+pub fn setup_random_ticks(app: &mut App) {
+    let mut registry = RandomTickRegistry::default();
+    builtin::register_builtin_random_ticks(&mut registry);
+
+    app.insert_resource(registry);
+}
+
+/// Picks `random_tick_speed` random blocks in every loaded chunk, and runs their random tick
+pub fn random_tick_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    (registry, settings, biomes): (Res<RandomTickRegistry>, Res<WorldSettings>, Res<BiomeNoise>),
+) {
+    let speed = settings.random_tick_speed.min(MAX_RANDOM_TICK_SPEED);
+    if speed == 0 {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut picked: Vec<IVec3> = Vec::new();
+    for (chunk_pos, chunk) in world_map.chunks.map.iter() {
+        for _ in 0..speed {
+            let local_pos = IVec3::new(
+                rng.gen_range(0..CHUNK_SIZE),
+                rng.gen_range(0..CHUNK_SIZE),
+                rng.gen_range(0..CHUNK_SIZE),
+            );
+            if chunk
+                .map
+                .get(&local_pos)
+                .is_some_and(|block| registry.get(&block.id).is_some())
+            {
+                picked.push(to_global_pos(chunk_pos, &local_pos));
+            }
+        }
+    }
+
+    let mut context = RandomTickContext {
+        world_map: &mut world_map,
+        biomes: &biomes,
+    };
+    for position in picked.iter() {
+        // An earlier tick may have changed the block
+        let Some(&block) = context.world_map.chunks.get_block_by_coordinates(position) else {
+            continue;
+        };
+        if let Some(handler) = registry.get(&block.id) {
+            handler(&mut context, position, block);
+        }
+    }
+}
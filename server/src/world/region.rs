@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use bincode::Options;
+use shared::world::ServerChunk;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Number of chunks stored along each axis of a region file
+pub const REGION_SIZE: i32 = 8;
+
+const REGION_CHUNK_COUNT: usize = (REGION_SIZE * REGION_SIZE * REGION_SIZE) as usize;
+const SECTOR_SIZE: u64 = 4096;
+const HEADER_ENTRY_SIZE: u64 = 8;
+// 512 entries of 8 bytes : the header fills exactly one sector
+const HEADER_SECTORS: u32 = 1;
+
+pub fn chunk_to_region_pos(chunk_pos: &IVec3) -> IVec3 {
+    chunk_pos.div_euclid(IVec3::splat(REGION_SIZE))
+}
+
+fn chunk_index(chunk_pos: &IVec3) -> usize {
+    let local = chunk_pos.rem_euclid(IVec3::splat(REGION_SIZE));
+    (local.x + local.y * REGION_SIZE + local.z * REGION_SIZE * REGION_SIZE) as usize
+}
+
+fn sectors_for(length: u64) -> u32 {
+    length.div_ceil(SECTOR_SIZE) as u32
+}
+
+/// Location of a chunk inside a region file.\
+/// A `sector` of 0 means the chunk is not stored in this region.
+#[derive(Debug, Clone, Copy, Default)]
+struct HeaderEntry {
+    sector: u32,
+    length: u32,
+}
+
+/// A region file groups `REGION_SIZE`^3 chunks.\
+/// It starts with a header sector indexing every chunk, followed by
+/// the chunks themselves, each one using a whole number of sectors.
+/// Chunks are encoded with bincode then compressed with lz4, so they can be
+/// read and rewritten one at a time.
+pub struct RegionFile {
+    file: File,
+    header: Vec<HeaderEntry>,
+    /// Whether each sector of the file holds the header or a chunk
+    used_sectors: Vec<bool>,
+}
+
+impl RegionFile {
+    /// Opens a region file, creating it with an empty header if needed
+    pub fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        let mut header = vec![HeaderEntry::default(); REGION_CHUNK_COUNT];
+
+        if file.metadata()?.len() < HEADER_SECTORS as u64 * SECTOR_SIZE {
+            file.set_len(HEADER_SECTORS as u64 * SECTOR_SIZE)?;
+        } else {
+            let mut raw = vec![0u8; REGION_CHUNK_COUNT * HEADER_ENTRY_SIZE as usize];
+            file.seek(SeekFrom::Start(0))?;
+            file.read_exact(&mut raw)?;
+            for (entry, bytes) in header.iter_mut().zip(raw.chunks_exact(8)) {
+                entry.sector = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+                entry.length = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+            }
+        }
+
+        let mut region = Self {
+            file,
+            header,
+            used_sectors: vec![true; HEADER_SECTORS as usize],
+        };
+        for entry in region.header.clone() {
+            region.set_sectors_used(&entry, true);
+        }
+        Ok(region)
+    }
+
+    fn set_sectors_used(&mut self, entry: &HeaderEntry, used: bool) {
+        if entry.sector == 0 {
+            return;
+        }
+        let start = entry.sector as usize;
+        let end = start + sectors_for(entry.length as u64) as usize;
+        if self.used_sectors.len() < end {
+            self.used_sectors.resize(end, false);
+        }
+        self.used_sectors[start..end].fill(used);
+    }
+
+    /// First run of `count` unused sectors, past the end of the file if there is none
+    fn find_free_sectors(&self, count: u32) -> u32 {
+        let mut run_start = HEADER_SECTORS as usize;
+        for (sector, used) in self.used_sectors.iter().enumerate() {
+            if *used {
+                run_start = sector + 1;
+            } else if sector + 1 - run_start >= count as usize {
+                break;
+            }
+        }
+        run_start as u32
+    }
+
+    pub fn read_chunk(
+        &mut self,
+        chunk_pos: &IVec3,
+    ) -> Result<Option<ServerChunk>, Box<dyn std::error::Error>> {
+        let entry = self.header[chunk_index(chunk_pos)];
+        if entry.sector == 0 {
+            return Ok(None);
+        }
+
+        let mut data = vec![0u8; entry.length as usize];
+        self.file
+            .seek(SeekFrom::Start(entry.sector as u64 * SECTOR_SIZE))?;
+        self.file.read_exact(&mut data)?;
+
+        Ok(Some(decode_chunk(&data)?))
+    }
+
+    pub fn write_chunk(
+        &mut self,
+        chunk_pos: &IVec3,
+        chunk: &ServerChunk,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let data = encode_chunk(chunk)?;
+        let index = chunk_index(chunk_pos);
+        let previous = self.header[index];
+        let needed_sectors = sectors_for(data.len() as u64);
+
+        // The previous copy of the chunk is never overwritten, the new one goes
+        // to unused sectors or at the end of the file
+        let sector = self.find_free_sectors(needed_sectors);
+
+        let mut padded = data;
+        let length = padded.len() as u32;
+        padded.resize((needed_sectors as u64 * SECTOR_SIZE) as usize, 0);
+
+        self.file
+            .seek(SeekFrom::Start(sector as u64 * SECTOR_SIZE))?;
+        self.file.write_all(&padded)?;
+
+        // The header keeps pointing to the previous copy until the new one is written
+        let entry = HeaderEntry { sector, length };
+        self.header[index] = entry;
+        let mut entry_bytes = [0u8; HEADER_ENTRY_SIZE as usize];
+        entry_bytes[0..4].copy_from_slice(&sector.to_le_bytes());
+        entry_bytes[4..8].copy_from_slice(&length.to_le_bytes());
+        self.file
+            .seek(SeekFrom::Start(index as u64 * HEADER_ENTRY_SIZE))?;
+        self.file.write_all(&entry_bytes)?;
+
+        self.set_sectors_used(&previous, false);
+        self.set_sectors_used(&entry, true);
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
+
+fn encode_chunk(chunk: &ServerChunk) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let raw = bincode::options().serialize(chunk)?;
+    Ok(lz4::block::compress(&raw, None, true)?)
+}
+
+fn decode_chunk(data: &[u8]) -> Result<ServerChunk, Box<dyn std::error::Error>> {
+    let raw = lz4::block::decompress(data, None)?;
+    Ok(bincode::options().deserialize(&raw)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::world::{BlockData, BlockDirection, BlockId};
+    use std::path::PathBuf;
+
+    fn temp_region_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("region-test-{}-{}.bin", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn chunk_with(blocks: &[(IVec3, BlockId)]) -> ServerChunk {
+        let mut chunk = ServerChunk::default();
+        for (position, id) in blocks {
+            chunk
+                .map
+                .insert(*position, BlockData::new(*id, false, BlockDirection::Front));
+        }
+        chunk
+    }
+
+    fn block_ids(chunk: &ServerChunk) -> Vec<(IVec3, BlockId)> {
+        let mut blocks: Vec<_> = chunk.map.iter().map(|(pos, b)| (*pos, b.id)).collect();
+        blocks.sort_by_key(|(pos, _)| pos.to_array());
+        blocks
+    }
+
+    #[test]
+    fn chunks_are_read_back_after_reopening() {
+        let path = temp_region_path("round-trip");
+        let first = chunk_with(&[(IVec3::ZERO, BlockId::Dirt), (IVec3::ONE, BlockId::Stone)]);
+        let second = chunk_with(&[(IVec3::new(3, 4, 5), BlockId::OakLog)]);
+
+        let mut region = RegionFile::open(&path).unwrap();
+        region.write_chunk(&IVec3::new(0, 0, 0), &first).unwrap();
+        region.write_chunk(&IVec3::new(-1, 2, 7), &second).unwrap();
+        region.flush().unwrap();
+        drop(region);
+
+        let mut region = RegionFile::open(&path).unwrap();
+        let read_first = region.read_chunk(&IVec3::new(0, 0, 0)).unwrap().unwrap();
+        let read_second = region.read_chunk(&IVec3::new(-1, 2, 7)).unwrap().unwrap();
+        assert_eq!(block_ids(&read_first), block_ids(&first));
+        assert_eq!(block_ids(&read_second), block_ids(&second));
+        assert!(region.read_chunk(&IVec3::new(1, 0, 0)).unwrap().is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rewritten_chunks_do_not_overwrite_their_previous_copy() {
+        let path = temp_region_path("rewrite");
+        let chunk_pos = IVec3::new(1, 1, 1);
+
+        let mut region = RegionFile::open(&path).unwrap();
+        region
+            .write_chunk(&chunk_pos, &chunk_with(&[(IVec3::ZERO, BlockId::Dirt)]))
+            .unwrap();
+        let first_sector = region.header[chunk_index(&chunk_pos)].sector;
+
+        region
+            .write_chunk(&chunk_pos, &chunk_with(&[(IVec3::ZERO, BlockId::Stone)]))
+            .unwrap();
+        let second_sector = region.header[chunk_index(&chunk_pos)].sector;
+        assert_ne!(first_sector, second_sector);
+
+        // The sectors freed by the first copy are used again
+        region
+            .write_chunk(&chunk_pos, &chunk_with(&[(IVec3::ZERO, BlockId::Sand)]))
+            .unwrap();
+        assert_eq!(region.header[chunk_index(&chunk_pos)].sector, first_sector);
+        drop(region);
+
+        let mut region = RegionFile::open(&path).unwrap();
+        let chunk = region.read_chunk(&chunk_pos).unwrap().unwrap();
+        assert_eq!(block_ids(&chunk), vec![(IVec3::ZERO, BlockId::Sand)]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,29 +1,21 @@
 use crate::init::ServerTime;
+use crate::world::data::{WorldData, WorldSettings};
+use crate::world::storage::{WorldEntities, WorldStorage};
+use crate::world::weather::WeatherState;
 use bevy::prelude::*;
-use ron::ser::PrettyConfig;
-use shared::world::get_game_folder;
 use shared::world::ServerWorldMap;
 use shared::world::WorldSeed;
-use shared::GameFolderPaths;
-use std::{fs::File, io::Write, path::Path};
 
 #[derive(Event)]
 pub struct SaveRequestEvent;
 
-use crate::world::data::SAVE_PATH;
-
-#[derive(serde::Serialize, serde::Deserialize)]
-pub struct WorldData {
-    pub seed: WorldSeed,
-    pub map: ServerWorldMap,
-    pub time: u64,
-}
-
 pub fn save_world_system(
     world_map: Res<ServerWorldMap>,
     world_seed: Res<WorldSeed>,
-    game_folder_path: Res<GameFolderPaths>,
+    mut storage: ResMut<WorldStorage>,
     time: Res<ServerTime>,
+    settings: Res<WorldSettings>,
+    weather: Res<WeatherState>,
     mut event: EventReader<SaveRequestEvent>,
 ) {
     // Reads all events to prevent them from being queued forever and repeatedly request a save
@@ -35,22 +27,14 @@ pub fn save_world_system(
     // If a save was requested by the user
     if save_requested {
         let world_data = WorldData {
-            map: world_map.clone(),
+            name: world_map.name.clone(),
             seed: *world_seed,
             time: time.0,
+            settings: settings.clone(),
+            weather: weather.clone(),
         };
 
-        // define save file path
-        let save_file_path = format!(
-            "{}{}.ron",
-            get_game_folder(Some(&game_folder_path))
-                .join(SAVE_PATH)
-                .display(),
-            world_map.name
-        );
-
-        // save seed and world data
-        if let Err(e) = save_world_data(&world_data, &save_file_path) {
+        if let Err(e) = save_world_data(&world_data, &world_map, &mut storage) {
             error!("Failed to save world data: {}", e);
         } else {
             info!("World data saved successfully! Name: {}", world_map.name);
@@ -58,21 +42,30 @@ pub fn save_world_system(
     }
 }
 
+/// Saves the world metadata, the entities, the online players and every chunk changed since the last save
 pub fn save_world_data(
     world_data: &WorldData,
-    file_path: &str,
+    world_map: &ServerWorldMap,
+    storage: &mut WorldStorage,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // configure RON serialization
-    let pretty_config = PrettyConfig::new()
-        .with_depth_limit(3)
-        .with_separate_tuple_members(true)
-        .with_enumerate_arrays(true);
+    storage.save_level(world_data)?;
+
+    storage.save_entities(&WorldEntities {
+        mobs: world_map.mobs.clone(),
+        item_stacks: world_map.item_stacks.clone(),
+        falling_blocks: world_map.falling_blocks.clone(),
+    })?;
+
+    for player in world_map.players.values() {
+        storage.save_player(player)?;
+    }
+
+    let written = storage.save_chunks(world_map.chunks.map.iter())?;
 
-    // serialize combined data (map + seed)
-    let serialized = ron::ser::to_string_pretty(world_data, pretty_config)?;
-    let path = Path::new(file_path);
-    let mut file = File::create(path)?;
-    file.write_all(serialized.as_bytes())?;
-    info!("World data saved to {}", file_path);
+    info!(
+        "World data saved to {} ({} chunks written)",
+        storage.world_path().display(),
+        written
+    );
     Ok(())
 }
@@ -4,12 +4,19 @@ use bevy::{
 };
 use bevy_renet::renet::{ClientId, RenetServer};
 use shared::{
-    messages::{NetworkAction, PlayerFrameInput, PlayerUpdateEvent},
+    messages::{DamageSource, NetworkAction, PlayerFrameInput, PlayerUpdateEvent},
     players::movement::simulate_player_movement,
     world::{ServerWorldMap, WorldSeed},
 };
 
-use crate::{network::extensions::SendGameMessageExtension, world::generation::generate_chunk};
+use crate::{
+    network::extensions::SendGameMessageExtension,
+    world::{
+        chunk_lifecycle::load_or_generate_chunk,
+        health::{get_fall_damage, DamageEvent},
+        storage::WorldStorage,
+    },
+};
 
 use super::broadcast_world::get_all_active_chunks;
 
@@ -24,6 +31,8 @@ pub fn handle_player_inputs_system(
     mut world_map: ResMut<ServerWorldMap>,
     mut server: ResMut<RenetServer>,
     seed: Res<WorldSeed>,
+    mut storage: ResMut<WorldStorage>,
+    mut ev_damage: EventWriter<DamageEvent>,
 ) {
     let world_map = world_map.as_mut();
     let players = &mut world_map.players;
@@ -34,8 +43,7 @@ pub fn handle_player_inputs_system(
         let chunk = chunks.map.get(&c);
 
         if chunk.is_none() {
-            let chunk = generate_chunk(c, seed.0);
-            info!("Generated chunk: {:?}", c);
+            let chunk = load_or_generate_chunk(&mut storage, c, seed.0);
             chunks.map.insert(c, chunk);
         }
     }
@@ -58,7 +66,15 @@ pub fn handle_player_inputs_system(
 
         // let initial = player.position;
 
-        simulate_player_movement(player, chunks, &ev.input.clone());
+        let result = simulate_player_movement(player, chunks, &ev.input.clone());
+
+        if let Some(fall_distance) = result.landed_after_falling {
+            ev_damage.send(DamageEvent {
+                player_id: player.id,
+                amount: get_fall_damage(fall_distance),
+                source: DamageSource::Fall,
+            });
+        }
 
         // let end = player.position;
         // if initial != end {
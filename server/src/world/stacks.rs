@@ -1,9 +1,133 @@
-// use bevy::prelude::*;
-// use shared::world::ServerWorldMap;
-
-// pub fn item_stacks_system(mut world_map: ResMut<ServerWorldMap>) {
-//     let current_ts = std::time::SystemTime::now()
-//         .duration_since(std::time::UNIX_EPOCH)
-//         .unwrap()
-//         .as_millis() as u64;
-// }
+use bevy::prelude::*;
+use shared::physics::{simulate_physics, PhysicsBody, PhysicsProperties};
+use shared::world::{
+    world_position_to_chunk_position, ItemStack, ServerItemStack, ServerWorldMap, WorldMap,
+};
+use shared::TICKS_PER_SECOND;
+use std::collections::HashMap;
+use ulid::Ulid;
+
+/// Time in milliseconds before a dropped stack disappears
+const ITEM_STACK_LIFETIME: u64 = 5 * 60 * 1000;
+
+/// Distance under which a player picks up a stack
+const PICKUP_RADIUS: f32 = 1.5;
+
+/// Distance under which two identical stacks merge into one
+const MERGE_RADIUS: f32 = 1.0;
+
+const ITEM_PHYSICS: PhysicsProperties = PhysicsProperties {
+    size: Vec3::splat(0.25),
+    gravity: -20.0,
+    max_fall_speed: 40.0,
+    ground_friction: 0.6,
+};
+
+fn current_ts() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Drops a stack in the world, with a small random push so drops spread out
+pub fn spawn_item_stack(world_map: &mut ServerWorldMap, stack: ItemStack, pos: Vec3) {
+    world_map.item_stacks.push(ServerItemStack {
+        id: Ulid::new().0,
+        despawned: false,
+        stack,
+        pos,
+        timestamp: current_ts(),
+        velocity: Vec3::new(
+            rand::random::<f32>() - 0.5,
+            2.0,
+            rand::random::<f32>() - 0.5,
+        ),
+        sent_to_clients: HashMap::new(),
+    });
+}
+
+pub fn item_stacks_system(mut world_map: ResMut<ServerWorldMap>) {
+    let world_map = world_map.as_mut();
+    let now = current_ts();
+
+    // Stacks despawned during the last tick have been broadcast, they can be forgotten
+    world_map.item_stacks.retain(|stack| !stack.despawned);
+
+    for stack in world_map.item_stacks.iter_mut() {
+        if now.saturating_sub(stack.timestamp) > ITEM_STACK_LIFETIME {
+            stack.despawned = true;
+            continue;
+        }
+
+        // Stacks in unloaded chunks would fall through the world
+        if !world_map
+            .chunks
+            .map
+            .contains_key(&world_position_to_chunk_position(stack.pos))
+        {
+            continue;
+        }
+
+        apply_item_physics(stack, &world_map.chunks);
+    }
+
+    merge_item_stacks(&mut world_map.item_stacks);
+
+    for player in world_map.players.values_mut() {
+        if player.is_dead() || !player.game_mode.can_interact() {
+            continue;
+        }
+
+        for stack in world_map.item_stacks.iter_mut() {
+            if stack.despawned || stack.pos.distance(player.position) > PICKUP_RADIUS {
+                continue;
+            }
+
+            let remaining = player.inventory.add_item_to_inventory(stack.stack);
+            if remaining == 0 {
+                stack.despawned = true;
+            } else {
+                stack.stack.nb = remaining;
+            }
+            debug!("Player {} picked up {:?}", player.id, stack.stack.item_id);
+        }
+    }
+}
+
+fn apply_item_physics(stack: &mut ServerItemStack, world_map: &impl WorldMap) {
+    let delta = 1.0 / TICKS_PER_SECOND as f32;
+
+    // Stacks resting on the ground fall again as soon as gravity moves them
+    let mut body = PhysicsBody {
+        position: stack.pos,
+        velocity: stack.velocity,
+        on_ground: false,
+    };
+    simulate_physics(&mut body, &ITEM_PHYSICS, world_map, delta);
+
+    stack.pos = body.position;
+    stack.velocity = body.velocity;
+}
+
+fn merge_item_stacks(stacks: &mut [ServerItemStack]) {
+    for i in 0..stacks.len() {
+        for j in (i + 1)..stacks.len() {
+            let (a, b) = (&stacks[i], &stacks[j]);
+            if a.despawned
+                || b.despawned
+                || a.stack.item_id != b.stack.item_id
+                || a.stack.nb + b.stack.nb > a.stack.item_id.get_max_stack()
+                || a.pos.distance(b.pos) > MERGE_RADIUS
+            {
+                continue;
+            }
+
+            // The merged stack keeps the most recent timestamp
+            let (nb, timestamp) = (b.stack.nb, b.timestamp.max(a.timestamp));
+            stacks[i].stack.nb += nb;
+            stacks[i].timestamp = timestamp;
+            stacks[j].despawned = true;
+        }
+    }
+}
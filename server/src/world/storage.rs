@@ -0,0 +1,341 @@
+use bevy::prelude::*;
+use bincode::Options;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use shared::messages::PlayerId;
+use shared::players::Player;
+use shared::world::{
+    get_game_folder, MobId, ServerChunk, ServerFallingBlock, ServerItemStack, ServerMob,
+};
+use shared::GameFolderPaths;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::world::data::{WorldData, SAVE_PATH};
+use crate::world::region::{chunk_to_region_pos, RegionFile};
+
+const LEVEL_FILE_NAME: &str = "level.ron";
+const ENTITIES_FILE_NAME: &str = "entities.bin";
+const REGION_FOLDER_NAME: &str = "region";
+const PLAYERS_FOLDER_NAME: &str = "players";
+
+/// Region files kept open at once, the least recently used one is closed past it
+const MAX_OPEN_REGIONS: usize = 32;
+
+/// Everything that is not bound to a chunk or to a player
+#[derive(Serialize, Deserialize, Default)]
+pub struct WorldEntities {
+    pub mobs: HashMap<MobId, ServerMob>,
+    pub item_stacks: Vec<ServerItemStack>,
+    pub falling_blocks: Vec<ServerFallingBlock>,
+}
+
+/// On-disk layout of a world :\
+/// `saves/<world>/level.ron` : name, seed and time\
+/// `saves/<world>/entities.bin` : mobs, item stacks and falling blocks\
+/// `saves/<world>/players/<id>.bin` : one file per player, with its inventory\
+/// `saves/<world>/region/r.<x>.<y>.<z>.bin` : chunks, see `RegionFile`
+#[derive(Resource)]
+pub struct WorldStorage {
+    world_path: PathBuf,
+    /// Chunks updated since this timestamp still need to be written
+    last_save_ts: u64,
+    /// Open region files, with the last time they were used
+    regions: HashMap<IVec3, (RegionFile, u64)>,
+    region_uses: u64,
+}
+
+fn current_ts() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Writes to a temporary file first, so an interrupted write leaves the previous file intact
+fn write_file_safely(path: &Path, contents: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+impl WorldStorage {
+    pub fn new(game_folder_paths: &GameFolderPaths, world_name: &str) -> Self {
+        Self {
+            world_path: get_game_folder(Some(game_folder_paths))
+                .join(SAVE_PATH)
+                .join(world_name),
+            last_save_ts: current_ts(),
+            regions: HashMap::new(),
+            region_uses: 0,
+        }
+    }
+
+    pub fn world_path(&self) -> &Path {
+        &self.world_path
+    }
+
+    pub fn exists(&self) -> bool {
+        self.world_path.join(LEVEL_FILE_NAME).exists()
+    }
+
+    fn region_path(&self, region_pos: &IVec3) -> PathBuf {
+        self.world_path.join(REGION_FOLDER_NAME).join(format!(
+            "r.{}.{}.{}.bin",
+            region_pos.x, region_pos.y, region_pos.z
+        ))
+    }
+
+    pub fn load_level(&self) -> Result<WorldData, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(self.world_path.join(LEVEL_FILE_NAME))?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    pub fn save_level(&self, world_data: &WorldData) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.world_path)?;
+        let serialized = ron::ser::to_string_pretty(world_data, PrettyConfig::new())?;
+        write_file_safely(
+            &self.world_path.join(LEVEL_FILE_NAME),
+            serialized.as_bytes(),
+        )
+    }
+
+    pub fn load_entities(&self) -> Result<WorldEntities, Box<dyn std::error::Error>> {
+        let path = self.world_path.join(ENTITIES_FILE_NAME);
+        if !path.exists() {
+            return Ok(WorldEntities::default());
+        }
+        let raw = lz4::block::decompress(&fs::read(path)?, None)?;
+        Ok(bincode::options().deserialize(&raw)?)
+    }
+
+    pub fn save_entities(
+        &self,
+        entities: &WorldEntities,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.world_path)?;
+        let raw = bincode::options().serialize(entities)?;
+        let compressed = lz4::block::compress(&raw, None, true)?;
+        write_file_safely(&self.world_path.join(ENTITIES_FILE_NAME), &compressed)
+    }
+
+    fn player_path(&self, player_id: PlayerId) -> PathBuf {
+        self.world_path
+            .join(PLAYERS_FOLDER_NAME)
+            .join(format!("{player_id}.bin"))
+    }
+
+    /// Reads a player saved in this world, `None` if they never joined it
+    pub fn load_player(
+        &self,
+        player_id: PlayerId,
+    ) -> Result<Option<Player>, Box<dyn std::error::Error>> {
+        let path = self.player_path(player_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = lz4::block::decompress(&fs::read(path)?, None)?;
+        Ok(Some(bincode::options().deserialize(&raw)?))
+    }
+
+    pub fn save_player(&self, player: &Player) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(self.world_path.join(PLAYERS_FOLDER_NAME))?;
+        let raw = bincode::options().serialize(player)?;
+        let compressed = lz4::block::compress(&raw, None, true)?;
+        write_file_safely(&self.player_path(player.id), &compressed)
+    }
+
+    /// Reads a single chunk from its region file, if it was ever saved\
+    /// The chunk is unchanged since it was written, so it is not dirty
+    pub fn load_chunk(
+        &mut self,
+        chunk_pos: &IVec3,
+    ) -> Result<Option<ServerChunk>, Box<dyn std::error::Error>> {
+        let region_pos = chunk_to_region_pos(chunk_pos);
+        if !self.regions.contains_key(&region_pos) && !self.region_path(&region_pos).exists() {
+            return Ok(None);
+        }
+        let mut chunk = self.open_region(&region_pos)?.read_chunk(chunk_pos)?;
+        if let Some(chunk) = chunk.as_mut() {
+            chunk.ts = 0;
+        }
+        Ok(chunk)
+    }
+
+    /// Whether the chunk changed since the last save
+    pub fn is_dirty(&self, chunk: &ServerChunk) -> bool {
+        chunk.ts >= self.last_save_ts
+    }
+
+    /// Writes the chunks that changed since the last save, returns how many were written
+    pub fn save_chunks<'a>(
+        &mut self,
+        chunks: impl IntoIterator<Item = (&'a IVec3, &'a ServerChunk)>,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let save_ts = current_ts();
+
+        let mut by_region: HashMap<IVec3, Vec<(&IVec3, &ServerChunk)>> = HashMap::new();
+        for (chunk_pos, chunk) in chunks {
+            if self.is_dirty(chunk) {
+                by_region
+                    .entry(chunk_to_region_pos(chunk_pos))
+                    .or_default()
+                    .push((chunk_pos, chunk));
+            }
+        }
+
+        let mut written = 0;
+        for (region_pos, chunks) in by_region {
+            let region = self.open_region(&region_pos)?;
+            for (chunk_pos, chunk) in chunks {
+                region.write_chunk(chunk_pos, chunk)?;
+                written += 1;
+            }
+            region.flush()?;
+        }
+
+        self.last_save_ts = save_ts;
+        Ok(written)
+    }
+
+    /// Writes a single chunk, regardless of whether it changed
+    pub fn save_chunk(
+        &mut self,
+        chunk_pos: &IVec3,
+        chunk: &ServerChunk,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let region = self.open_region(&chunk_to_region_pos(chunk_pos))?;
+        region.write_chunk(chunk_pos, chunk)?;
+        region.flush()
+    }
+
+    /// Region file holding the chunks of `region_pos`, opened on first use
+    fn open_region(
+        &mut self,
+        region_pos: &IVec3,
+    ) -> Result<&mut RegionFile, Box<dyn std::error::Error>> {
+        self.region_uses += 1;
+
+        if !self.regions.contains_key(region_pos) {
+            if self.regions.len() >= MAX_OPEN_REGIONS {
+                let least_used = self
+                    .regions
+                    .iter()
+                    .min_by_key(|(_, (_, last_use))| *last_use)
+                    .map(|(pos, _)| *pos);
+                if let Some(pos) = least_used {
+                    self.regions.remove(&pos);
+                }
+            }
+
+            fs::create_dir_all(self.world_path.join(REGION_FOLDER_NAME))?;
+            let region = RegionFile::open(&self.region_path(region_pos))?;
+            self.regions.insert(*region_pos, (region, 0));
+        }
+
+        let (region, last_use) = self.regions.get_mut(region_pos).unwrap();
+        *last_use = self.region_uses;
+        Ok(region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::region::REGION_SIZE;
+    use shared::world::{BlockData, BlockDirection, BlockId, MobAction, MobKind, MobTarget};
+
+    fn temp_storage(name: &str) -> WorldStorage {
+        let game_folder_paths = GameFolderPaths {
+            game_folder_path: std::env::temp_dir()
+                .join(format!("storage-test-{}-{}", name, std::process::id()))
+                .to_string_lossy()
+                .to_string(),
+            assets_folder_path: String::new(),
+        };
+        let storage = WorldStorage::new(&game_folder_paths, "world");
+        let _ = fs::remove_dir_all(storage.world_path());
+        storage
+    }
+
+    #[test]
+    fn loaded_chunks_are_not_dirty() {
+        let mut storage = temp_storage("chunks");
+        let chunk_pos = IVec3::new(2, -1, 9);
+
+        let mut chunk = ServerChunk::default();
+        chunk.map.insert(
+            IVec3::ZERO,
+            BlockData::new(BlockId::Stone, false, BlockDirection::Front),
+        );
+        chunk.touch();
+        assert!(storage.is_dirty(&chunk));
+        storage.save_chunk(&chunk_pos, &chunk).unwrap();
+
+        let loaded = storage.load_chunk(&chunk_pos).unwrap().unwrap();
+        assert_eq!(loaded.map[&IVec3::ZERO].id, BlockId::Stone);
+        assert!(!storage.is_dirty(&loaded));
+        assert!(storage
+            .load_chunk(&IVec3::new(2, -1, 10))
+            .unwrap()
+            .is_none());
+
+        fs::remove_dir_all(storage.world_path()).unwrap();
+    }
+
+    #[test]
+    fn entities_are_read_back() {
+        let storage = temp_storage("entities");
+        let mut entities = WorldEntities::default();
+        entities.mobs.insert(
+            3,
+            ServerMob {
+                kind: MobKind::Zombie,
+                target: MobTarget::None,
+                action: MobAction::Idle,
+                position: Vec3::new(1.0, 64.0, -2.0),
+                rotation: Quat::IDENTITY,
+                velocity: Vec3::ZERO,
+                on_ground: true,
+                health: 7,
+            },
+        );
+        entities.falling_blocks.push(ServerFallingBlock {
+            id: 5,
+            despawned: false,
+            block: BlockData::new(BlockId::Sand, false, BlockDirection::Front),
+            pos: Vec3::new(4.0, 70.5, 1.0),
+            velocity: -3.0,
+        });
+        storage.save_entities(&entities).unwrap();
+
+        let loaded = storage.load_entities().unwrap();
+        let mob = &loaded.mobs[&3];
+        assert_eq!(mob.kind, MobKind::Zombie);
+        assert_eq!(mob.position, Vec3::new(1.0, 64.0, -2.0));
+        assert_eq!(mob.health, 7);
+        let falling = &loaded.falling_blocks[0];
+        assert_eq!(falling.block.id, BlockId::Sand);
+        assert_eq!(falling.pos, Vec3::new(4.0, 70.5, 1.0));
+
+        fs::remove_dir_all(storage.world_path()).unwrap();
+    }
+
+    #[test]
+    fn region_files_stay_open_up_to_the_limit() {
+        let mut storage = temp_storage("regions");
+        let chunk = ServerChunk::default();
+        for x in 0..MAX_OPEN_REGIONS as i32 + 4 {
+            let chunk_pos = IVec3::new(x * REGION_SIZE, 0, 0);
+            storage.save_chunk(&chunk_pos, &chunk).unwrap();
+        }
+        assert_eq!(storage.regions.len(), MAX_OPEN_REGIONS);
+
+        // Closed regions are opened again when needed
+        assert!(storage.load_chunk(&IVec3::ZERO).unwrap().is_some());
+
+        fs::remove_dir_all(storage.world_path()).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+
+/// Blocks waiting for an update at a given server tick\
+/// Only scheduled blocks are simulated, so the cost does not grow with the size of the world
+#[derive(Resource, Default)]
+pub struct ScheduledTicks {
+    queue: BTreeMap<u64, Vec<IVec3>>,
+    scheduled: HashSet<IVec3>,
+}
+
+impl ScheduledTicks {
+    /// Does nothing if the block is already waiting for an update
+    pub fn schedule(&mut self, position: IVec3, tick: u64) {
+        if self.scheduled.insert(position) {
+            self.queue.entry(tick).or_default().push(position);
+        }
+    }
+
+    /// Removes and returns at most `max` blocks due at `now`, oldest first\
+    /// The others stay in the queue for the next ticks
+    pub fn take_due(&mut self, now: u64, max: usize) -> Vec<IVec3> {
+        let mut due = Vec::new();
+
+        while due.len() < max {
+            let Some(mut entry) = self.queue.first_entry() else {
+                break;
+            };
+            if *entry.key() > now {
+                break;
+            }
+
+            let positions = entry.get_mut();
+            let count = positions.len().min(max - due.len());
+            due.extend(positions.drain(..count));
+            if positions.is_empty() {
+                entry.remove();
+            }
+        }
+
+        for position in due.iter() {
+            self.scheduled.remove(position);
+        }
+        due
+    }
+
+    /// Number of blocks waiting for an update
+    pub fn count(&self) -> usize {
+        self.scheduled.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_due_blocks_oldest_first() {
+        let mut ticks = ScheduledTicks::default();
+        ticks.schedule(IVec3::new(0, 0, 0), 5);
+        ticks.schedule(IVec3::new(1, 0, 0), 3);
+        ticks.schedule(IVec3::new(2, 0, 0), 10);
+
+        assert!(ticks.take_due(2, 10).is_empty());
+        assert_eq!(
+            ticks.take_due(5, 10),
+            vec![IVec3::new(1, 0, 0), IVec3::new(0, 0, 0)]
+        );
+        assert_eq!(ticks.count(), 1);
+    }
+
+    #[test]
+    fn schedules_a_block_once() {
+        let mut ticks = ScheduledTicks::default();
+        ticks.schedule(IVec3::ZERO, 1);
+        ticks.schedule(IVec3::ZERO, 2);
+        assert_eq!(ticks.count(), 1);
+
+        assert_eq!(ticks.take_due(2, 10), vec![IVec3::ZERO]);
+        ticks.schedule(IVec3::ZERO, 3);
+        assert_eq!(ticks.count(), 1);
+    }
+
+    #[test]
+    fn keeps_blocks_past_the_limit_for_later() {
+        let mut ticks = ScheduledTicks::default();
+        for x in 0..5 {
+            ticks.schedule(IVec3::new(x, 0, 0), 1);
+        }
+
+        assert_eq!(ticks.take_due(1, 3).len(), 3);
+        assert_eq!(ticks.take_due(1, 3).len(), 2);
+        assert_eq!(ticks.count(), 0);
+    }
+}
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_renet::renet::RenetServer;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use shared::messages::{PlayerId, ServerToClientMessage, WeatherUpdate};
+use shared::world::{
+    get_light_level, is_supported, to_global_pos, BlockData, BlockDirection, BlockId,
+    ServerChunkWorldMap, ServerWorldMap, Weather, WorldMap, MAX_LIGHT_LEVEL,
+};
+use shared::CHUNK_SIZE;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::network::extensions::SendGameMessageExtension;
+use crate::world::generation::{BiomeNoise, SNOW_TEMPERATURE};
+
+/// Chance for each loaded chunk to get snow on one of its columns every tick while it snows
+const SNOWFALL_CHANCE: f64 = 1. / 16.;
+/// Snow piles up to this many layers, thicker snow is never made by the weather
+const MAX_SNOWFALL_LAYERS: u8 = 4;
+
+/// Ticks each weather lasts before changing, picked at random
+fn duration_range(weather: Weather) -> Range<u64> {
+    match weather {
+        Weather::Clear => 2400..9600,
+        Weather::Rain => 1200..4800,
+        Weather::Thunder => 600..2400,
+    }
+}
+
+/// Current weather and the ticks left until it changes, saved with the world
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct WeatherState {
+    pub weather: Weather,
+    pub remaining_ticks: u64,
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self {
+            weather: Weather::Clear,
+            remaining_ticks: rand::thread_rng().gen_range(duration_range(Weather::Clear)),
+        }
+    }
+}
+
+impl WeatherState {
+    /// Changes the weather right away, for `duration` ticks or a random time
+    pub fn set(&mut self, weather: Weather, duration: Option<u64>) {
+        self.weather = weather;
+        self.remaining_ticks =
+            duration.unwrap_or_else(|| rand::thread_rng().gen_range(duration_range(weather)));
+    }
+}
+
+/// Rain turns into a thunderstorm once in a while, and storms calm down into rain
+fn next_weather(weather: Weather) -> Weather {
+    let mut rng = rand::thread_rng();
+    match weather {
+        Weather::Clear if rng.gen_bool(0.2) => Weather::Thunder,
+        Weather::Clear => Weather::Rain,
+        Weather::Rain if rng.gen_bool(0.25) => Weather::Thunder,
+        Weather::Thunder if rng.gen_bool(0.5) => Weather::Rain,
+        Weather::Rain | Weather::Thunder => Weather::Clear,
+    }
+}
+
+pub fn weather_system(mut state: ResMut<WeatherState>) {
+    if state.remaining_ticks > 0 {
+        state.remaining_ticks -= 1;
+        return;
+    }
+
+    let weather = next_weather(state.weather);
+    state.set(weather, None);
+    info!(
+        "Weather changed to {}, for {} ticks",
+        state.weather, state.remaining_ticks
+    );
+}
+
+fn is_cold(x: i32, z: i32, biomes: &BiomeNoise) -> bool {
+    biomes.get_temperature_at(x, z) < SNOW_TEMPERATURE
+}
+
+/// Sends each player the weather where they stand, whenever it changes\
+/// The seed is not shared with clients, so they are told whether it snows there
+pub fn sync_weather_system(
+    mut server: ResMut<RenetServer>,
+    world_map: Res<ServerWorldMap>,
+    (state, biomes): (Res<WeatherState>, Res<BiomeNoise>),
+    mut sent: Local<HashMap<PlayerId, WeatherUpdate>>,
+) {
+    sent.retain(|id, _| world_map.players.contains_key(id));
+
+    for (id, player) in world_map.players.iter() {
+        let position = player.position.round().as_ivec3();
+        let update = WeatherUpdate {
+            weather: state.weather,
+            snowing: is_cold(position.x, position.z, &biomes),
+        };
+
+        if sent.get(id) != Some(&update) {
+            debug!("Sending weather update to {}: {:?}", id, update);
+            server.send_game_message(*id, ServerToClientMessage::WeatherUpdate(update));
+            sent.insert(*id, update);
+        }
+    }
+}
+
+/// Adds a layer of snow on the highest block of the chunk column under `top`, if nothing hides it from the sky
+fn add_snow_layer(chunks: &mut ServerChunkWorldMap, top: &IVec3) {
+    for dy in 0..CHUNK_SIZE {
+        let position = *top - IVec3::Y * dy;
+        let Some(&block) = chunks.get_block_by_coordinates(&position) else {
+            continue;
+        };
+
+        let above = position + IVec3::Y;
+        let is_open =
+            get_light_level(chunks, &above).is_some_and(|light| light.sky == MAX_LIGHT_LEVEL);
+        if !is_open {
+            return;
+        }
+
+        if block.id == BlockId::SnowLayer {
+            if block.snow_layers() < MAX_SNOWFALL_LAYERS {
+                chunks.set_block(
+                    &position,
+                    BlockData {
+                        state: block.state + 1,
+                        ..block
+                    },
+                );
+            }
+        } else {
+            let snow = BlockData::new(BlockId::SnowLayer, false, BlockDirection::Front);
+            if is_supported(chunks, &above, &snow) {
+                chunks.set_block(&above, snow);
+            }
+        }
+        return;
+    }
+}
+
+/// Piles snow up on the blocks open to the sky in cold biomes, while it snows
+pub fn snowfall_system(
+    mut world_map: ResMut<ServerWorldMap>,
+    (state, biomes): (Res<WeatherState>, Res<BiomeNoise>),
+) {
+    if !state.weather.has_precipitation() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let tops: Vec<IVec3> = world_map
+        .chunks
+        .map
+        .keys()
+        .filter_map(|chunk_pos| {
+            if !rng.gen_bool(SNOWFALL_CHANCE) {
+                return None;
+            }
+            let local_pos = IVec3::new(
+                rng.gen_range(0..CHUNK_SIZE),
+                CHUNK_SIZE - 1,
+                rng.gen_range(0..CHUNK_SIZE),
+            );
+            Some(to_global_pos(chunk_pos, &local_pos))
+        })
+        .collect();
+
+    for top in tops.iter() {
+        if is_cold(top.x, top.z, &biomes) {
+            add_snow_layer(&mut world_map.chunks, top);
+        }
+    }
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 pub const PROTOCOL_ID: u64 = 0;
 pub const TICKS_PER_SECOND: u64 = 20;
+/// Ticks in a full day, unless the server is set otherwise
+pub const DEFAULT_DAY_LENGTH: u64 = 20 * 60; // 20 ticks per second * 60 seconds = 1 minute
 pub const CHUNK_SIZE: i32 = 16;
 pub const MAX_INVENTORY_SLOTS: u32 = 4 * 9;
 pub const HALF_BLOCK: Vec3 = Vec3 {
@@ -0,0 +1,141 @@
+mod recipes;
+
+pub use recipes::*;
+
+use crate::players::{Inventory, InventoryAction};
+use crate::world::ItemStack;
+use crate::MAX_INVENTORY_SLOTS;
+
+/// Width and height of the crafting grid of the player's inventory
+pub const CRAFTING_GRID_SIZE: u32 = 2;
+
+/// The crafting grid is stored in the inventory, right after the regular slots
+pub const CRAFTING_GRID_START: u32 = MAX_INVENTORY_SLOTS;
+
+/// Holds the result of the current recipe, can only be taken from
+pub const CRAFTING_RESULT_SLOT: u32 = CRAFTING_GRID_START + CRAFTING_GRID_SIZE * CRAFTING_GRID_SIZE;
+
+pub fn get_crafting_grid(inventory: &Inventory) -> CraftingGrid {
+    CraftingGrid {
+        width: CRAFTING_GRID_SIZE as usize,
+        cells: (CRAFTING_GRID_START..CRAFTING_RESULT_SLOT)
+            .map(|slot| inventory.inner.get(&slot).map(|stack| stack.item_id))
+            .collect(),
+    }
+}
+
+/// Applies a click to the inventory, taking the crafting grid into account\
+/// Used by the server, and by the client to predict the result
+pub fn apply_inventory_action(
+    inventory: &mut Inventory,
+    action: InventoryAction,
+    recipes: &RecipeRegistry,
+) {
+    if action.slot() == CRAFTING_RESULT_SLOT {
+        take_crafting_result(inventory, recipes);
+    } else {
+        inventory.apply_action(action);
+    }
+
+    update_crafting_result(inventory, recipes);
+}
+
+/// Fills the result slot with the output of the recipe matching the grid
+pub fn update_crafting_result(inventory: &mut Inventory, recipes: &RecipeRegistry) {
+    match recipes.find(&get_crafting_grid(inventory)) {
+        Some(recipe) => {
+            let (item_id, nb) = recipe.result();
+            inventory.inner.insert(
+                CRAFTING_RESULT_SLOT,
+                ItemStack {
+                    item_id,
+                    item_type: item_id.get_default_type(),
+                    nb,
+                },
+            );
+        }
+        None => {
+            inventory.inner.remove(&CRAFTING_RESULT_SLOT);
+        }
+    }
+}
+
+/// Moves the result to the floating stack and consumes one item of each grid cell\
+/// The recipe is checked again, so a stale result slot never gives anything
+fn take_crafting_result(inventory: &mut Inventory, recipes: &RecipeRegistry) {
+    let Some(recipe) = recipes.find(&get_crafting_grid(inventory)) else {
+        return;
+    };
+    let (item_id, nb) = recipe.result();
+
+    if let Some(floating) = inventory.floating_stack {
+        if floating.item_id != item_id || floating.nb + nb > item_id.get_max_stack() {
+            return;
+        }
+    }
+
+    inventory.add_item_floating_stack(nb, item_id, item_id.get_default_type());
+    for slot in CRAFTING_GRID_START..CRAFTING_RESULT_SLOT {
+        inventory.remove_item_from_stack(slot, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::ItemId;
+
+    fn planks_registry() -> RecipeRegistry {
+        RecipeRegistry {
+            recipes: vec![Recipe::Shapeless {
+                ingredients: vec![ItemId::OakLog],
+                result: ItemId::OakPlanks,
+                count: 4,
+            }],
+        }
+    }
+
+    fn logs(nb: u32) -> ItemStack {
+        ItemStack {
+            item_id: ItemId::OakLog,
+            item_type: ItemId::OakLog.get_default_type(),
+            nb,
+        }
+    }
+
+    #[test]
+    fn taking_the_result_consumes_the_ingredients() {
+        let recipes = planks_registry();
+        let mut inventory = Inventory::new();
+        inventory.inner.insert(CRAFTING_GRID_START, logs(2));
+        update_crafting_result(&mut inventory, &recipes);
+        assert_eq!(inventory.inner[&CRAFTING_RESULT_SLOT].nb, 4);
+
+        apply_inventory_action(
+            &mut inventory,
+            InventoryAction::LeftClick(CRAFTING_RESULT_SLOT),
+            &recipes,
+        );
+
+        assert_eq!(inventory.floating_stack.unwrap().item_id, ItemId::OakPlanks);
+        assert_eq!(inventory.floating_stack.unwrap().nb, 4);
+        assert_eq!(inventory.inner[&CRAFTING_GRID_START].nb, 1);
+        assert!(inventory.inner.contains_key(&CRAFTING_RESULT_SLOT));
+    }
+
+    #[test]
+    fn the_result_is_removed_with_the_last_ingredient() {
+        let recipes = planks_registry();
+        let mut inventory = Inventory::new();
+        inventory.inner.insert(CRAFTING_GRID_START, logs(1));
+
+        apply_inventory_action(
+            &mut inventory,
+            InventoryAction::LeftClick(CRAFTING_RESULT_SLOT),
+            &recipes,
+        );
+
+        assert!(!inventory.inner.contains_key(&CRAFTING_GRID_START));
+        assert!(!inventory.inner.contains_key(&CRAFTING_RESULT_SLOT));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::world::{get_game_folder, ItemId};
+use crate::GameFolderPaths;
+
+/// Folder holding one `.ron` file per recipe, relative to the game folder
+pub const RECIPES_PATH: &str = "data/recipes";
+
+/// A recipe, as written in the RON files\
+/// Shaped example :
+/// ```ron
+/// Shaped(
+///     pattern: ["##", "##"],
+///     key: {'#': Snowball},
+///     result: Snow,
+///     count: 1,
+/// )
+/// ```
+/// Shapeless example :
+/// ```ron
+/// Shapeless(
+///     ingredients: [OakLog],
+///     result: OakPlanks,
+///     count: 4,
+/// )
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Recipe {
+    /// Items must be placed following `pattern`, anywhere in the grid\
+    /// Each character of the pattern refers to an item of `key`, spaces are empty cells
+    Shaped {
+        pattern: Vec<String>,
+        key: HashMap<char, ItemId>,
+        result: ItemId,
+        count: u32,
+    },
+    /// Items can be placed in any cell
+    Shapeless {
+        ingredients: Vec<ItemId>,
+        result: ItemId,
+        count: u32,
+    },
+}
+
+/// Content of a crafting grid, row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct CraftingGrid {
+    pub width: usize,
+    pub cells: Vec<Option<ItemId>>,
+}
+
+impl CraftingGrid {
+    fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<ItemId> {
+        self.cells[y * self.width + x]
+    }
+
+    /// Smallest rectangle holding every non-empty cell, as (min_x, min_y, width, height)
+    fn bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        let filled: Vec<(usize, usize)> = (0..self.height())
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|(x, y)| self.get(*x, *y).is_some())
+            .collect();
+
+        let min_x = filled.iter().map(|(x, _)| *x).min()?;
+        let max_x = filled.iter().map(|(x, _)| *x).max()?;
+        let min_y = filled.iter().map(|(_, y)| *y).min()?;
+        let max_y = filled.iter().map(|(_, y)| *y).max()?;
+
+        Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+    }
+}
+
+impl Recipe {
+    pub fn result(&self) -> (ItemId, u32) {
+        match self {
+            Self::Shaped { result, count, .. } | Self::Shapeless { result, count, .. } => {
+                (*result, *count)
+            }
+        }
+    }
+
+    pub fn matches(&self, grid: &CraftingGrid) -> bool {
+        match self {
+            Self::Shaped { pattern, key, .. } => {
+                let Some((min_x, min_y, width, height)) = grid.bounds() else {
+                    return false;
+                };
+
+                let rows: Vec<Vec<char>> =
+                    pattern.iter().map(|row| row.chars().collect()).collect();
+                let pattern_width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+                if rows.len() != height || pattern_width != width {
+                    return false;
+                }
+
+                for (y, row) in rows.iter().enumerate() {
+                    for x in 0..width {
+                        let expected = row.get(x).and_then(|c| key.get(c)).copied();
+                        if grid.get(min_x + x, min_y + y) != expected {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }
+            Self::Shapeless { ingredients, .. } => {
+                let mut items: Vec<ItemId> = grid.cells.iter().flatten().copied().collect();
+                let mut ingredients = ingredients.clone();
+                items.sort();
+                ingredients.sort();
+                items == ingredients
+            }
+        }
+    }
+}
+
+/// Every recipe known by the game
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RecipeRegistry {
+    pub recipes: Vec<Recipe>,
+}
+
+impl RecipeRegistry {
+    /// Loads every recipe from `RECIPES_PATH`\
+    /// Invalid files are skipped, so a typo in one recipe does not disable crafting
+    pub fn load(paths: &GameFolderPaths) -> Self {
+        let path = get_game_folder(Some(paths)).join(RECIPES_PATH);
+        match Self::load_from_dir(&path) {
+            Ok(registry) => {
+                info!(
+                    "Loaded {} recipes from {}",
+                    registry.recipes.len(),
+                    path.display()
+                );
+                registry
+            }
+            Err(e) => {
+                error!("Failed to read recipes from {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from_dir(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut recipes = Vec::new();
+
+        for entry in fs::read_dir(path)? {
+            let file_path = entry?.path();
+            if file_path.extension().is_none_or(|ext| ext != "ron") {
+                continue;
+            }
+
+            let contents = fs::read_to_string(&file_path)?;
+            match ron::de::from_str::<Recipe>(&contents) {
+                Ok(recipe) => recipes.push(recipe),
+                Err(e) => error!("Invalid recipe {}: {}", file_path.display(), e),
+            }
+        }
+
+        Ok(Self { recipes })
+    }
+
+    pub fn find(&self, grid: &CraftingGrid) -> Option<&Recipe> {
+        self.recipes.iter().find(|recipe| recipe.matches(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stick_recipe() -> Recipe {
+        Recipe::Shaped {
+            pattern: vec!["#".to_string(), "#".to_string()],
+            key: HashMap::from([('#', ItemId::OakPlanks)]),
+            result: ItemId::Stick,
+            count: 4,
+        }
+    }
+
+    fn grid(cells: [Option<ItemId>; 4]) -> CraftingGrid {
+        CraftingGrid {
+            width: 2,
+            cells: cells.to_vec(),
+        }
+    }
+
+    #[test]
+    fn shaped_recipes_match_anywhere_in_the_grid() {
+        let planks = Some(ItemId::OakPlanks);
+        assert!(stick_recipe().matches(&grid([planks, None, planks, None])));
+        assert!(stick_recipe().matches(&grid([None, planks, None, planks])));
+    }
+
+    #[test]
+    fn shaped_recipes_need_their_exact_pattern() {
+        let planks = Some(ItemId::OakPlanks);
+        assert!(!stick_recipe().matches(&grid([planks, planks, None, None])));
+        assert!(!stick_recipe().matches(&grid([planks, planks, planks, None])));
+        assert!(!stick_recipe().matches(&grid([None; 4])));
+    }
+
+    #[test]
+    fn shapeless_recipes_ignore_the_order() {
+        let recipe = Recipe::Shapeless {
+            ingredients: vec![ItemId::Snow, ItemId::Dirt],
+            result: ItemId::Grass,
+            count: 1,
+        };
+        assert!(recipe.matches(&grid([None, Some(ItemId::Dirt), Some(ItemId::Snow), None])));
+        assert!(!recipe.matches(&grid([None, Some(ItemId::Dirt), None, None])));
+    }
+
+    #[test]
+    fn every_recipe_file_is_valid() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(RECIPES_PATH);
+        let files = fs::read_dir(&path).unwrap().count();
+        let registry = RecipeRegistry::load_from_dir(&path).unwrap();
+        assert_eq!(registry.recipes.len(), files);
+    }
+}
@@ -5,13 +5,16 @@ use bevy_renet::renet::{ChannelConfig, ConnectionConfig, SendType};
 use bincode::Options;
 
 pub mod constants;
+pub mod crafting;
 pub mod messages;
+pub mod physics;
 pub mod players;
 pub mod utils;
 pub mod world;
 
 pub use constants::*;
 use messages::{ClientToServerMessage, ServerToClientMessage};
+use players::GameMode;
 use utils::format_bytes;
 
 #[derive(Resource, Debug, Clone)]
@@ -29,6 +32,8 @@ pub struct SpecialFlag {
 pub struct GameServerConfig {
     pub world_name: String,
     pub is_solo: bool,
+    /// Game mode of new players, only used when the world does not exist yet
+    pub new_world_game_mode: GameMode,
 }
 
 const MAX_MEMORY: usize = 128 * 1024 * 1024;
@@ -135,6 +140,8 @@ impl ChannelResolvableExt for ServerToClientMessage {
     fn get_channel_id(&self) -> u8 {
         match self {
             ServerToClientMessage::WorldUpdate(_) => STC_CHUNK_DATA_CHANNEL,
+            // Same channel as chunks, so an update never arrives before its chunk
+            ServerToClientMessage::BlockUpdates(_) => STC_CHUNK_DATA_CHANNEL,
             ServerToClientMessage::AuthRegisterResponse(_) => STC_AUTH_CHANNEL,
             _ => STC_STANDARD_CHANNEL,
         }
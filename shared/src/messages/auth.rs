@@ -18,6 +18,8 @@ pub struct AuthRegisterResponse {
     pub username: String,
     pub session_token: u64,
     pub tick: u64,
+    /// Ticks in a full day, see `TimeUpdate`
+    pub day_length: u64,
     pub timestamp_ms: u64,
     pub players: Vec<PlayerSpawnEvent>, // all players (including the new one)
 }
@@ -8,3 +8,17 @@ pub struct MobUpdateEvent {
     pub id: MobId,
     pub mob: ServerMob,
 }
+
+/// Sent when a mob is removed from the world
+#[derive(Event, Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct MobDespawnEvent {
+    pub id: MobId,
+}
+
+/// Sent when a player hits a mob\
+/// A mob left with no health died and is removed from the world
+#[derive(Event, Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct MobHitEvent {
+    pub id: MobId,
+    pub health: u32,
+}
@@ -4,11 +4,12 @@ pub mod mob;
 pub mod player;
 mod world;
 
-use crate::world::BlockData;
+use crate::players::{InventoryAction, InventoryUpdate};
+use crate::world::{BlockData, MobId};
 pub use auth::*;
 use bevy::math::IVec3;
 pub use chat::*;
-use mob::MobUpdateEvent;
+use mob::{MobDespawnEvent, MobHitEvent, MobUpdateEvent};
 pub use player::*;
 use serde::{Deserialize, Serialize};
 pub use world::*;
@@ -22,9 +23,30 @@ pub enum ClientToServerMessage {
     Exit,
     PlayerInputs(Vec<PlayerFrameInput>),
     SaveWorldRequest,
+    /// The player started breaking the block, the server checks it took long enough when it is broken
+    StartBreaking {
+        position: IVec3,
+    },
     BlockInteraction {
         position: IVec3,
         block_type: Option<BlockData>,
+        /// Hotbar slot holding the placed block, or the tool used to break one
+        hotbar_slot: u32,
+    },
+    InventoryAction(InventoryAction),
+    /// Click on a slot of the container at `position`, such as a chest
+    ContainerAction {
+        position: IVec3,
+        action: InventoryAction,
+    },
+    /// Right-click on a block that does something when used, such as a door
+    UseBlock {
+        position: IVec3,
+    },
+    Respawn,
+    /// Left-click on a mob, checked against the reach and attack cooldown of the player
+    AttackMob {
+        id: MobId,
     },
 }
 
@@ -35,5 +57,13 @@ pub enum ServerToClientMessage {
     WorldUpdate(WorldUpdate),
     PlayerSpawn(PlayerSpawnEvent),
     MobUpdate(MobUpdateEvent),
+    MobDespawn(MobDespawnEvent),
+    MobHit(MobHitEvent),
     PlayerUpdate(PlayerUpdateEvent),
+    BlockUpdates(Vec<BlockUpdate>),
+    InventoryUpdate(InventoryUpdate),
+    PlayerHealth(PlayerHealthUpdate),
+    PlayerGameMode(PlayerGameModeUpdate),
+    TimeUpdate(TimeUpdate),
+    WeatherUpdate(WeatherUpdate),
 }
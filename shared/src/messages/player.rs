@@ -2,6 +2,7 @@ use bevy::{prelude::*, utils::HashSet};
 use serde::{Deserialize, Serialize};
 
 use super::PlayerId;
+use crate::players::GameMode;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Eq, Hash)]
 pub enum NetworkAction {
@@ -39,3 +40,24 @@ pub struct PlayerFrameInput {
     #[serde(skip)]
     pub position: Vec3,
 }
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DamageSource {
+    Fall,
+    Void,
+    Mob,
+}
+
+/// Sent to a player when their health changes
+#[derive(Event, Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct PlayerHealthUpdate {
+    pub health: u32,
+    /// What caused the damage, `None` when healed or respawned
+    pub source: Option<DamageSource>,
+}
+
+/// Sent to a player when they join, and when their game mode changes
+#[derive(Event, Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct PlayerGameModeUpdate {
+    pub game_mode: GameMode,
+}
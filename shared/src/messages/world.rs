@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::world::{ItemStack, MobId, ServerChunk, ServerMob};
+use crate::world::{BlockData, BlockEntity, ItemStack, ServerChunk, Weather};
 use bevy::{
     math::{IVec3, Vec3},
     prelude::Event,
@@ -16,8 +16,8 @@ pub struct WorldUpdate {
     pub tick: u64,
     pub time: u64,
     pub new_map: HashMap<IVec3, ServerChunk>,
-    pub mobs: HashMap<MobId, ServerMob>,
     pub item_stacks: Vec<ItemStackUpdateEvent>,
+    pub falling_blocks: Vec<FallingBlockUpdateEvent>,
     pub player_events: Vec<PlayerUpdateEvent>,
 }
 
@@ -28,6 +28,39 @@ pub struct ItemStackUpdateEvent {
     pub data: Option<(ItemStack, Vec3)>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Event)]
+pub struct FallingBlockUpdateEvent {
+    pub id: u128,
+    /// `None` once the block has landed
+    pub data: Option<(BlockData, Vec3)>,
+}
+
+/// Time of day owned by the server, sent periodically and whenever it jumps
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct TimeUpdate {
+    /// Ticks since the world was created
+    pub time: u64,
+    /// Ticks in a full day
+    pub day_length: u64,
+}
+
+/// Weather where a player stands, sent whenever it changes\
+/// Only the server knows the temperature of biomes, so it tells whether the precipitation is snow
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherUpdate {
+    pub weather: Weather,
+    pub snowing: bool,
+}
+
+/// A single block change, sent to every client holding the chunk
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BlockUpdate {
+    pub position: IVec3,
+    /// `None` if the block has been removed
+    pub block: Option<BlockData>,
+    pub block_entity: Option<BlockEntity>,
+}
+
 pub struct ChunkUpdate {
     pub position: IVec3,
     pub chunk: ServerChunk,
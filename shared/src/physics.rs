@@ -0,0 +1,206 @@
+use bevy::math::bounding::Aabb3d;
+use bevy::prelude::*;
+
+use crate::world::WorldMap;
+use crate::TICKS_PER_SECOND;
+
+/// Longest distance moved at once along an axis, so fast entities cannot go through a block
+const MAX_SUBSTEP: f32 = 0.4;
+/// Halvings of a blocked move, to bring the entity right against what blocked it
+const CONTACT_PRECISION: u32 = 6;
+
+/// How a kind of entity moves and collides with the world
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicsProperties {
+    /// Size of the hitbox, centred on the simulated position
+    pub size: Vec3,
+    /// In blocks per second squared
+    pub gravity: f32,
+    /// In blocks per second
+    pub max_fall_speed: f32,
+    /// Horizontal velocity kept every tick while on the ground
+    pub ground_friction: f32,
+}
+
+/// State of a simulated entity, copied in and out of the entity by its owner
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhysicsBody {
+    /// Centre of the hitbox
+    pub position: Vec3,
+    /// In blocks per second
+    pub velocity: Vec3,
+    pub on_ground: bool,
+}
+
+/// What happened to the entity during a simulated step
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PhysicsResult {
+    /// Height the entity fell from, if it just landed
+    pub landed_after_falling: Option<f32>,
+    /// Whether a block stopped the entity horizontally
+    pub hit_wall: bool,
+}
+
+fn collides(world_map: &impl WorldMap, position: Vec3, properties: &PhysicsProperties) -> bool {
+    world_map.check_collision_box(&Aabb3d::new(position, properties.size / 2.0))
+}
+
+/// Applies gravity and moves the entity by its velocity for `delta` seconds\
+/// Each axis is resolved separately, so an entity can slide along a wall
+pub fn simulate_physics(
+    body: &mut PhysicsBody,
+    properties: &PhysicsProperties,
+    world_map: &impl WorldMap,
+    delta: f32,
+) -> PhysicsResult {
+    let mut result = PhysicsResult::default();
+
+    body.velocity.y =
+        (body.velocity.y + properties.gravity * delta).max(-properties.max_fall_speed);
+
+    let was_on_ground = body.on_ground;
+    let fall_speed = -body.velocity.y;
+    body.on_ground = false;
+
+    let motion = body.velocity * delta;
+    let substeps = (motion.abs().max_element() / MAX_SUBSTEP).ceil().max(1.0) as u32;
+    let step = motion / substeps as f32;
+
+    for _ in 0..substeps {
+        for axis in 0..3 {
+            if body.velocity[axis] == 0.0 {
+                continue;
+            }
+
+            let mut offset = Vec3::ZERO;
+            offset[axis] = step[axis];
+            if !collides(world_map, body.position + offset, properties) {
+                body.position += offset;
+                continue;
+            }
+
+            // Moves the entity as far as possible before the block
+            let (mut free, mut blocked) = (0.0, 1.0);
+            for _ in 0..CONTACT_PRECISION {
+                let middle = (free + blocked) / 2.0;
+                if collides(world_map, body.position + offset * middle, properties) {
+                    blocked = middle;
+                } else {
+                    free = middle;
+                }
+            }
+            body.position += offset * free;
+
+            if axis == 1 {
+                if step.y < 0.0 {
+                    body.on_ground = true;
+                    if !was_on_ground && properties.gravity < 0.0 {
+                        // Speed grows by |gravity| every second while falling,
+                        // so the height fallen is v² / (2 * |gravity|)
+                        result.landed_after_falling =
+                            Some(fall_speed.powi(2) / (2.0 * -properties.gravity));
+                    }
+                }
+            } else {
+                result.hit_wall = true;
+            }
+            body.velocity[axis] = 0.0;
+        }
+    }
+
+    if body.on_ground {
+        let friction = properties
+            .ground_friction
+            .powf(delta * TICKS_PER_SECOND as f32);
+        body.velocity.x *= friction;
+        body.velocity.z *= friction;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{BlockData, BlockDirection, BlockId, ServerChunk, ServerChunkWorldMap};
+
+    const PROPERTIES: PhysicsProperties = PhysicsProperties {
+        size: Vec3::ONE,
+        gravity: -20.0,
+        max_fall_speed: 50.0,
+        ground_friction: 0.5,
+    };
+    const DELTA: f32 = 1.0 / TICKS_PER_SECOND as f32;
+
+    /// A single chunk whose bottom layer is stone, blocks being centred on their position
+    fn flat_world() -> ServerChunkWorldMap {
+        let mut chunk = ServerChunk::default();
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.map.insert(
+                    IVec3::new(x, 0, z),
+                    BlockData::new(BlockId::Stone, false, BlockDirection::Front),
+                );
+            }
+        }
+        let mut world = ServerChunkWorldMap::default();
+        world.map.insert(IVec3::ZERO, chunk);
+        world
+    }
+
+    #[test]
+    fn falling_bodies_land_on_the_ground() {
+        let world = flat_world();
+        let mut body = PhysicsBody {
+            position: Vec3::new(8.0, 6.0, 8.0),
+            ..default()
+        };
+
+        let mut landed = None;
+        for _ in 0..TICKS_PER_SECOND {
+            let result = simulate_physics(&mut body, &PROPERTIES, &world, DELTA);
+            landed = landed.or(result.landed_after_falling);
+        }
+
+        assert!(body.on_ground);
+        assert!((body.position.y - 1.0).abs() < 0.05);
+        assert!((landed.unwrap() - 5.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn walls_stop_bodies() {
+        let mut world = flat_world();
+        world.map.get_mut(&IVec3::ZERO).unwrap().map.insert(
+            IVec3::new(4, 1, 8),
+            BlockData::new(BlockId::Stone, false, BlockDirection::Front),
+        );
+        let mut body = PhysicsBody {
+            position: Vec3::new(1.0, 1.0, 8.0),
+            velocity: Vec3::new(60.0, 0.0, 0.0),
+            on_ground: true,
+        };
+
+        let hit_wall = (0..5)
+            .map(|_| simulate_physics(&mut body, &PROPERTIES, &world, DELTA))
+            .any(|result| result.hit_wall);
+
+        assert!(hit_wall);
+        assert!(body.position.x <= 3.0 + f32::EPSILON);
+        assert_eq!(body.velocity.x, 0.0);
+    }
+
+    #[test]
+    fn ground_friction_slows_bodies_down() {
+        let world = flat_world();
+        let mut body = PhysicsBody {
+            position: Vec3::new(8.0, 1.0, 8.0),
+            velocity: Vec3::new(0.0, 0.0, 2.0),
+            on_ground: true,
+        };
+
+        simulate_physics(&mut body, &PROPERTIES, &world, DELTA);
+
+        assert!(body.on_ground);
+        assert!((body.velocity.z - 1.0).abs() < 0.001);
+    }
+}
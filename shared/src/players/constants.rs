@@ -1,4 +1,18 @@
-pub const GRAVITY: f32 = -0.5;
+/// In blocks per second squared
+pub const GRAVITY: f32 = -30.0;
+/// In blocks per second
 pub const JUMP_VELOCITY: f32 = 10.0;
+pub const MAX_FALL_SPEED: f32 = 60.0;
 pub const FLY_SPEED_MULTIPLIER: f32 = 4.0;
 pub const SPEED: f32 = 5.0;
+pub const MAX_HEALTH: u32 = 20;
+/// Players can fall from this height without taking damage
+pub const SAFE_FALL_DISTANCE: f32 = 3.0;
+/// Players below this height take void damage
+pub const FALL_LIMIT: f32 = -50.0;
+/// Multiplies the walking speed and the gravity of players in water
+pub const SWIM_SPEED_MULTIPLIER: f32 = 0.5;
+pub const WATER_GRAVITY_MULTIPLIER: f32 = 0.2;
+/// In blocks per second
+pub const SWIM_UP_SPEED: f32 = 3.0;
+pub const MAX_SINK_SPEED: f32 = 2.0;
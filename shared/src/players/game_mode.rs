@@ -0,0 +1,73 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::messages::DamageSource;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Health, limited items and no flight
+    #[default]
+    Survival,
+    /// Flight, instant breaking and unlimited blocks
+    Creative,
+    /// Flies through blocks, cannot interact with the world
+    Spectator,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [Self::Survival, Self::Creative, Self::Spectator];
+
+    /// Accepts the full name or a short alias (s, c, sp), case insensitive
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "survival" | "s" => Some(Self::Survival),
+            "creative" | "c" => Some(Self::Creative),
+            "spectator" | "sp" => Some(Self::Spectator),
+            _ => None,
+        }
+    }
+
+    /// Next mode in `ALL`, used to cycle through modes in menus
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|mode| mode == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn can_toggle_flight(&self) -> bool {
+        *self == Self::Creative
+    }
+
+    pub fn has_collisions(&self) -> bool {
+        *self != Self::Spectator
+    }
+
+    /// Breaking and placing blocks, using items and picking them up
+    pub fn can_interact(&self) -> bool {
+        *self != Self::Spectator
+    }
+
+    pub fn breaks_instantly(&self) -> bool {
+        *self == Self::Creative
+    }
+
+    /// Placed blocks are not taken from the inventory, and any block can be picked
+    pub fn has_unlimited_blocks(&self) -> bool {
+        *self == Self::Creative
+    }
+
+    /// Creative players can still fall out of the world
+    pub fn takes_damage_from(&self, source: DamageSource) -> bool {
+        match *self {
+            Self::Survival => true,
+            Self::Creative => source == DamageSource::Void,
+            Self::Spectator => false,
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::{prelude::Resource, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    crafting::CRAFTING_RESULT_SLOT,
+    world::{ItemId, ItemStack, ItemType},
+    MAX_INVENTORY_SLOTS,
+};
+
+/// Click on an inventory slot, applied the same way by the server and by the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InventoryAction {
+    /// Merges the floating stack into the slot, or swaps them
+    LeftClick(u32),
+    /// Drops a single item of the floating stack into the slot, or picks up half of the slot
+    RightClick(u32),
+    /// Fills the slot with a full stack of the item, only allowed in creative mode
+    PickItem { slot: u32, item_id: ItemId },
+}
+
+impl InventoryAction {
+    pub fn slot(&self) -> u32 {
+        match *self {
+            Self::LeftClick(slot) | Self::RightClick(slot) | Self::PickItem { slot, .. } => slot,
+        }
+    }
+}
+
+/// Changes sent by the server to keep the client's copy of its inventory up to date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum InventoryUpdate {
+    Snapshot(Inventory),
+    Delta {
+        /// Slots that changed, `None` if emptied
+        slots: Vec<(u32, Option<ItemStack>)>,
+        floating_stack: Option<ItemStack>,
+    },
+}
+
+#[derive(Debug, Resource, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    pub inner: HashMap<u32, ItemStack>,
+    /// The stack held by the cursor, not considered in the inventory slots
+    pub floating_stack: Option<ItemStack>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self {
+            inner: HashMap::new(),
+            floating_stack: None,
+        }
+    }
+
+    /// Adds items to the first slots that can hold them\
+    /// Returns the number of items that did not fit in the inventory
+    pub fn add_item_to_inventory(&mut self, mut stack: ItemStack) -> u32 {
+        for i in 0..MAX_INVENTORY_SLOTS {
+            let item_option = self.inner.get(&i);
+
+            if let Some(existing_item) = item_option {
+                // If not item of right type or stack already full : pass
+                if existing_item.item_id != stack.item_id
+                    || existing_item.nb >= stack.item_id.get_max_stack()
+                {
+                    continue;
+                }
+
+                stack.nb += existing_item.nb;
+            }
+
+            let inserted_stack = ItemStack {
+                item_id: stack.item_id,
+                item_type: stack.item_type,
+                nb: if stack.nb >= stack.item_id.get_max_stack() {
+                    stack.item_id.get_max_stack()
+                } else {
+                    stack.nb
+                },
+            };
+            stack.nb -= inserted_stack.nb;
+
+            // Push inserted items in right inventory slot
+            self.inner.insert(i, inserted_stack);
+
+            // If no more items to add, end loop
+            if stack.nb == 0 {
+                break;
+            }
+        }
+
+        stack.nb
+    }
+
+    /// Add items to stack at specified position\
+    /// Stacks cannot exceed MAX_ITEM_STACK number of items\
+    /// Returns number of items really added to the stack
+    pub fn add_item_to_stack(
+        &mut self,
+        stack: u32,
+        mut nb: u32,
+        id: ItemId,
+        item_type: ItemType,
+    ) -> u32 {
+        let item_option = self.inner.get(&stack);
+        let mut new_item = ItemStack {
+            item_id: id,
+            nb,
+            item_type,
+        };
+
+        if let Some(item) = item_option {
+            if nb + item.nb > item.item_id.get_max_stack() {
+                nb = item.item_id.get_max_stack() - item.nb;
+            }
+            new_item.nb = nb + item.nb;
+        }
+        self.inner.insert(stack, new_item);
+        nb
+    }
+
+    /// Removes items from stack at specified position\
+    /// Stacks cannot have < 0 number of items\
+    /// Returns number of items really removed from the stack
+    pub fn remove_item_from_stack(&mut self, stack: u32, mut nb: u32) -> u32 {
+        let item_option = self.inner.get(&stack);
+
+        if let Some(&item) = item_option {
+            if nb >= item.nb {
+                nb = item.nb;
+                self.inner.remove(&stack);
+            } else {
+                self.inner.insert(
+                    stack,
+                    ItemStack {
+                        item_id: item.item_id,
+                        nb: item.nb - nb,
+                        item_type: item.item_type,
+                    },
+                );
+            }
+            return nb;
+        }
+        0
+    }
+}
+
+impl Inventory {
+    pub fn apply_action(&mut self, action: InventoryAction) {
+        // The crafting result slot can only be taken from, see `crafting::apply_inventory_action`
+        if action.slot() >= CRAFTING_RESULT_SLOT {
+            return;
+        }
+
+        match action {
+            InventoryAction::LeftClick(slot) => {
+                let stack = self.inner.get(&slot).copied();
+                match (stack, self.floating_stack) {
+                    (Some(stack), Some(floating))
+                        if stack.item_id == floating.item_id
+                            && stack.nb < stack.item_id.get_max_stack() =>
+                    {
+                        let added = self.add_item_to_stack(
+                            slot,
+                            floating.nb,
+                            floating.item_id,
+                            stack.item_type,
+                        );
+                        self.remove_item_floating_stack(added);
+                    }
+                    (stack, floating) => {
+                        match floating {
+                            Some(floating) => self.inner.insert(slot, floating),
+                            None => self.inner.remove(&slot),
+                        };
+                        self.floating_stack = stack;
+                    }
+                }
+            }
+            InventoryAction::RightClick(slot) => {
+                let stack = self.inner.get(&slot).copied();
+                if let Some(floating) = self.floating_stack {
+                    let same_item = stack.is_none_or(|stack| stack.item_id == floating.item_id);
+                    if same_item {
+                        let added =
+                            self.add_item_to_stack(slot, 1, floating.item_id, floating.item_type);
+                        self.remove_item_floating_stack(added);
+                    }
+                } else if let Some(stack) = stack {
+                    let removed = self.remove_item_from_stack(slot, stack.nb.div_ceil(2));
+                    self.add_item_floating_stack(removed, stack.item_id, stack.item_type);
+                }
+            }
+            InventoryAction::PickItem { slot, item_id } => {
+                self.inner.insert(
+                    slot,
+                    ItemStack {
+                        item_id,
+                        item_type: item_id.get_default_type(),
+                        nb: item_id.get_max_stack(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Removes one durability point from the tool in the slot, the tool breaks when reaching 0
+    pub fn wear_tool(&mut self, slot: u32) {
+        let Some(stack) = self.inner.get_mut(&slot) else {
+            return;
+        };
+        let ItemType::Tool { durability } = &mut stack.item_type else {
+            return;
+        };
+
+        *durability = durability.saturating_sub(1);
+        if *durability == 0 {
+            self.inner.remove(&slot);
+        }
+    }
+
+    /// Removes `nb` items from the floating stack\
+    /// Returns number of items _actually_ removed
+    pub fn remove_item_floating_stack(&mut self, nb: u32) -> u32 {
+        let Some(mut item) = self.floating_stack else {
+            return 0;
+        };
+        if nb >= item.nb {
+            self.floating_stack = None;
+            return item.nb;
+        }
+        item.nb -= nb;
+        self.floating_stack = Some(item);
+        nb
+    }
+
+    /// Adds `nb` items to the floating stack\
+    /// Parameter `item_type` will **ONLY BE USED** if no items are present in the floating stack\
+    /// Returns number of items _actually_ added
+    pub fn add_item_floating_stack(
+        &mut self,
+        nb: u32,
+        item_id: ItemId,
+        item_type: ItemType,
+    ) -> u32 {
+        if nb == 0 {
+            return 0;
+        }
+        let mut item = self.floating_stack.unwrap_or(ItemStack {
+            item_id,
+            item_type,
+            nb: 0,
+        });
+        let added = nb.min(item.item_id.get_max_stack() - item.nb);
+        item.nb += added;
+        self.floating_stack = Some(item);
+        added
+    }
+
+    /// Lists the changes from `previous` to this inventory, `None` if there are none
+    pub fn diff(&self, previous: &Inventory) -> Option<InventoryUpdate> {
+        let mut slots: Vec<(u32, Option<ItemStack>)> = Vec::new();
+        for (slot, stack) in self.inner.iter() {
+            if previous.inner.get(slot) != Some(stack) {
+                slots.push((*slot, Some(*stack)));
+            }
+        }
+        for slot in previous.inner.keys() {
+            if !self.inner.contains_key(slot) {
+                slots.push((*slot, None));
+            }
+        }
+
+        if slots.is_empty() && self.floating_stack == previous.floating_stack {
+            return None;
+        }
+
+        Some(InventoryUpdate::Delta {
+            slots,
+            floating_stack: self.floating_stack,
+        })
+    }
+
+    pub fn apply_update(&mut self, update: InventoryUpdate) {
+        match update {
+            InventoryUpdate::Snapshot(inventory) => *self = inventory,
+            InventoryUpdate::Delta {
+                slots,
+                floating_stack,
+            } => {
+                for (slot, stack) in slots {
+                    match stack {
+                        Some(stack) => self.inner.insert(slot, stack),
+                        None => self.inner.remove(&slot),
+                    };
+                }
+                self.floating_stack = floating_stack;
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
-pub mod collision;
 pub mod constants;
 mod data;
+mod game_mode;
+mod inventory;
 pub mod movement;
 
 pub use data::*;
+pub use game_mode::*;
+pub use inventory::*;
@@ -1,25 +1,39 @@
 use crate::{
     messages::{NetworkAction, PlayerFrameInput},
-    players::{
-        collision::check_player_collision,
-        constants::{FLY_SPEED_MULTIPLIER, GRAVITY, JUMP_VELOCITY, SPEED},
+    physics::{simulate_physics, PhysicsBody, PhysicsProperties},
+    players::constants::{
+        FLY_SPEED_MULTIPLIER, GRAVITY, JUMP_VELOCITY, MAX_FALL_SPEED, MAX_SINK_SPEED, SPEED,
+        SWIM_SPEED_MULTIPLIER, SWIM_UP_SPEED, WATER_GRAVITY_MULTIPLIER,
     },
-    world::WorldMap,
+    world::{BlockId, WorldMap},
 };
 use bevy::prelude::*;
 
-use super::Player;
+use super::{GameMode, Player};
+
+/// What happened to the player during a simulated frame
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MovementResult {
+    /// Height the player fell from, if they just landed
+    pub landed_after_falling: Option<f32>,
+}
 
 pub fn simulate_player_movement(
     player: &mut Player,
     world_map: &impl WorldMap,
     action: &PlayerFrameInput,
-) {
+) -> MovementResult {
+    let mut result = MovementResult::default();
+
+    if player.is_dead() {
+        return result;
+    }
+
     // let's check if the 9 chunks around the player are loaded
     let chunks = world_map.get_surrounding_chunks(player.position, 1);
     if chunks.len() < 9 {
         debug!("Not enough chunks loaded, skipping movement simulation");
-        return;
+        return result;
     }
 
     let delta = action.delta_ms as f32 / 1000.0;
@@ -28,8 +42,13 @@ pub fn simulate_player_movement(
 
     let mut direction = Vec3::ZERO;
 
-    if action.is_pressed(NetworkAction::ToggleFlyMode) {
-        player.is_flying = !player.is_flying;
+    if player.game_mode.can_toggle_flight() {
+        if action.is_pressed(NetworkAction::ToggleFlyMode) {
+            player.is_flying = !player.is_flying;
+        }
+    } else {
+        // Survival players never fly, spectators always do
+        player.is_flying = player.game_mode == GameMode::Spectator;
     }
 
     player.camera_transform.rotation = action.camera;
@@ -65,66 +84,81 @@ pub fn simulate_player_movement(
         direction -= Vec3::Y;
     }
 
-    // Handle jumping (if on the ground) and gravity, only if not flying
-    if !player.is_flying {
-        if player.on_ground && is_jumping {
-            // Player can jump only when grounded
-            player.velocity.y = JUMP_VELOCITY * delta;
-            player.on_ground = false;
-        } else if !player.on_ground {
-            // Apply gravity when the player is in the air
-            player.velocity.y += GRAVITY * delta;
-        }
-    }
+    let in_water = !player.is_flying && is_in_water(player, world_map);
 
-    let new_y = player.position.y + player.velocity.y;
-    let new_vec = &Vec3::new(player.position.x, new_y, player.position.z);
+    let speed = if player.is_flying {
+        SPEED * FLY_SPEED_MULTIPLIER
+    } else if in_water {
+        SPEED * SWIM_SPEED_MULTIPLIER
+    } else {
+        SPEED
+    };
 
-    let max_velocity = 0.9;
+    player.velocity.x = direction.x * speed;
+    player.velocity.z = direction.z * speed;
 
-    if player.velocity.y > max_velocity {
-        player.velocity.y = max_velocity;
+    // Handle jumping (if on the ground) and swimming, gravity is applied by the physics
+    if player.is_flying {
+        player.velocity.y = direction.y * speed;
+    } else if in_water {
+        // Swimming : players rise while jumping, and sink slowly otherwise
+        if is_jumping {
+            player.velocity.y = SWIM_UP_SPEED;
+        }
+    } else if player.on_ground && is_jumping {
+        // Player can jump only when grounded
+        player.velocity.y = JUMP_VELOCITY;
     }
 
-    if !player.is_flying {
-        if check_player_collision(new_vec, player, world_map) {
-            player.on_ground = true;
-            player.velocity.y = 0.0;
-        } else {
-            player.position.y = new_y;
-            player.on_ground = false;
-        }
+    if !player.game_mode.has_collisions() {
+        player.position += player.velocity * delta;
+        return result;
     }
 
-    let speed = if player.is_flying {
-        SPEED * FLY_SPEED_MULTIPLIER
-    } else {
-        SPEED
+    let mut body = PhysicsBody {
+        position: player.position,
+        velocity: player.velocity,
+        on_ground: player.on_ground,
     };
-    let speed = speed * delta;
-
-    // Attempt to move the player by the calculated direction
-    let new_x = player.position.x + direction.x * speed;
-    let new_y = player.position.y + direction.y * speed;
-    let new_z = player.position.z + direction.z * speed;
+    let physics = simulate_physics(
+        &mut body,
+        &get_player_physics(player, in_water),
+        world_map,
+        delta,
+    );
+    player.position = body.position;
+    player.velocity = body.velocity;
+    player.on_ground = body.on_ground;
+
+    result.landed_after_falling = physics.landed_after_falling;
+    result
+}
 
-    let new_vec = &Vec3::new(new_x, new_y, new_z);
-    if check_player_collision(new_vec, player, world_map) && !player.is_flying {
-        // If a block is detected in the new position, don't move the player
+fn get_player_physics(player: &Player, in_water: bool) -> PhysicsProperties {
+    let (gravity, max_fall_speed) = if player.is_flying {
+        (0.0, f32::INFINITY)
+    } else if in_water {
+        (GRAVITY * WATER_GRAVITY_MULTIPLIER, MAX_SINK_SPEED)
     } else {
-        player.position.x = new_x;
-        player.position.y = new_y;
-        player.position.z = new_z;
-    }
+        (GRAVITY, MAX_FALL_SPEED)
+    };
 
-    // If the player is below the world, reset their position
-    const FALL_LIMIT: f32 = -50.0;
-    if player.position.y < FALL_LIMIT {
-        player.position = Vec3::new(0.0, 100.0, 0.0);
-        player.velocity.y = 0.0;
+    PhysicsProperties {
+        size: Vec3::new(player.width, player.height, player.width),
+        gravity,
+        max_fall_speed,
+        // Horizontal velocity comes from the inputs of every frame
+        ground_friction: 1.0,
     }
 }
 
+/// Players swim when the middle of their body is in water
+fn is_in_water(player: &Player, world_map: &impl WorldMap) -> bool {
+    world_map
+        .get_block_by_coordinates(&player.position.round().as_ivec3())
+        .is_some_and(|block| block.id == BlockId::Water)
+}
+
 trait IsPressed {
     fn is_pressed(&self, action: NetworkAction) -> bool;
 }
@@ -0,0 +1,64 @@
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::players::{Inventory, InventoryAction};
+
+use super::{BlockId, ItemStack};
+
+/// Number of slots of a chest
+pub const CHEST_SLOTS: u32 = 27;
+
+/// Data attached to a block that does not fit in `BlockData`\
+/// Stored in its chunk, so it is saved and sent to clients along with the blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BlockEntity {
+    Chest { items: HashMap<u32, ItemStack> },
+}
+
+impl BlockEntity {
+    /// Entity created when the block is placed, `None` for blocks without one
+    pub fn new(block_id: BlockId) -> Option<Self> {
+        match block_id {
+            BlockId::Chest => Some(Self::Chest {
+                items: HashMap::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn items(&self) -> &HashMap<u32, ItemStack> {
+        match self {
+            Self::Chest { items } => items,
+        }
+    }
+
+    pub fn slot_count(&self) -> u32 {
+        match self {
+            Self::Chest { .. } => CHEST_SLOTS,
+        }
+    }
+
+    /// Click on a slot of the container, moving items to or from the floating stack of `inventory`\
+    /// Returns `false` if the action is not allowed, in which case nothing changes
+    pub fn apply_action(&mut self, inventory: &mut Inventory, action: InventoryAction) -> bool {
+        if action.slot() >= self.slot_count() {
+            return false;
+        }
+        if let InventoryAction::PickItem { .. } = action {
+            return false;
+        }
+
+        let Self::Chest { items } = self;
+
+        // The container slots are handled as an inventory holding the player's floating stack
+        let mut container = Inventory {
+            inner: std::mem::take(items),
+            floating_stack: inventory.floating_stack,
+        };
+        container.apply_action(action);
+
+        *items = container.inner;
+        inventory.floating_stack = container.floating_stack;
+        true
+    }
+}
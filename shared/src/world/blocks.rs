@@ -42,6 +42,14 @@ pub enum BlockId {
     SpruceLeaves,
     SpruceLog,
     Water,
+    Chest,
+    OakSlab,
+    OakStairs,
+    OakDoor,
+    Torch,
+    Glowstone,
+    /// Thin layer of snow, see `BlockData::snow_layers`
+    SnowLayer,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -59,6 +67,8 @@ pub struct BlockData {
     pub flipped: bool,
     pub direction: BlockDirection,
     pub breaking_progress: u8,
+    /// Meaning depends on the block, see `fluids` for water
+    pub state: u8,
 }
 
 impl BlockData {
@@ -68,13 +78,22 @@ impl BlockData {
             flipped,
             direction,
             breaking_progress: 0,
+            state: 0,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockTags {
     Solid,
+    /// Mined with a pickaxe, drops nothing otherwise
     Stone,
+    /// Mined with an axe
+    Wood,
+    /// Mined with a shovel
+    Soil,
+    /// Falls when the block below is removed
+    Gravity,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -89,16 +108,26 @@ impl BlockId {
     pub fn has_hitbox(&self) -> bool {
         !matches!(
             *self,
-            BlockId::Dandelion | BlockId::Poppy | BlockId::TallGrass | BlockId::Water
+            BlockId::Dandelion
+                | BlockId::Poppy
+                | BlockId::TallGrass
+                | BlockId::Water
+                | BlockId::Torch
         )
     }
 
+    /// Blocks that a placed block takes the place of, other blocks must be broken first
+    pub fn is_replaceable(&self) -> bool {
+        matches!(*self, BlockId::TallGrass | BlockId::Water)
+    }
+
     pub fn is_biome_colored() -> bool {
         false
     }
 
-    pub fn get_break_time(&self) -> u8 {
-        match *self {
+    /// `None` for blocks that cannot be broken, such as bedrock
+    pub fn get_break_time(&self) -> Option<u8> {
+        let time = match *self {
             Self::Dirt => 5,
             Self::Debug => 7,
             Self::Grass => 6,
@@ -117,8 +146,15 @@ impl BlockId {
             Self::Snow => 9,
             Self::SpruceLeaves => 2,
             Self::SpruceLog => 10,
-            _ => 100,
-        }
+            Self::Chest => 10,
+            Self::OakSlab | Self::OakStairs => 10,
+            Self::OakDoor => 8,
+            Self::Torch => 0,
+            Self::Glowstone => 3,
+            Self::SnowLayer => 1,
+            _ => return None,
+        };
+        Some(time)
     }
 
     pub fn get_color(&self) -> [f32; 4] {
@@ -172,7 +208,14 @@ impl BlockId {
             BlockId::Poppy => vec![(1, ItemId::Dandelion, 1)],
             BlockId::TallGrass => vec![(1, ItemId::TallGrass, 1)],
             BlockId::SpruceLog => vec![(1, ItemId::SpruceLog, 1)],
+            BlockId::Chest => vec![(1, ItemId::Chest, 1)],
+            BlockId::OakSlab => vec![(1, ItemId::OakSlab, 1)],
+            BlockId::OakStairs => vec![(1, ItemId::OakStairs, 1)],
+            BlockId::OakDoor => vec![(1, ItemId::OakDoor, 1)],
+            BlockId::Torch => vec![(1, ItemId::Torch, 1)],
+            BlockId::Glowstone => vec![(1, ItemId::Glowstone, 1)],
             BlockId::Snow => vec![(1, ItemId::Snowball, 4)],
+            BlockId::SnowLayer => vec![(1, ItemId::Snowball, 1)],
             BlockId::Water => vec![],
             _ => vec![],
         }
@@ -180,14 +223,36 @@ impl BlockId {
 
     pub fn get_tags(&self) -> Vec<BlockTags> {
         match *self {
-            BlockId::Stone => vec![BlockTags::Stone, BlockTags::Solid],
+            BlockId::Stone | BlockId::Cobblestone | BlockId::Ice => {
+                vec![BlockTags::Stone, BlockTags::Solid]
+            }
+            BlockId::OakLog
+            | BlockId::OakPlanks
+            | BlockId::SpruceLog
+            | BlockId::Cactus
+            | BlockId::Chest
+            | BlockId::OakSlab
+            | BlockId::OakStairs
+            | BlockId::OakDoor => {
+                vec![BlockTags::Wood, BlockTags::Solid]
+            }
+            BlockId::Sand => vec![BlockTags::Soil, BlockTags::Solid, BlockTags::Gravity],
+            BlockId::Dirt | BlockId::Grass | BlockId::Snow | BlockId::SnowLayer => {
+                vec![BlockTags::Soil, BlockTags::Solid]
+            }
             _ => vec![BlockTags::Solid],
         }
     }
 
+    pub fn is_affected_by_gravity(&self) -> bool {
+        self.get_tags().contains(&BlockTags::Gravity)
+    }
+
     pub fn get_visibility(&self) -> BlockTransparency {
         match *self {
-            Self::Dandelion | Self::Poppy | Self::TallGrass => BlockTransparency::Decoration,
+            Self::Dandelion | Self::Poppy | Self::TallGrass | Self::Torch => {
+                BlockTransparency::Decoration
+            }
             Self::Glass | Self::OakLeaves | Self::SpruceLeaves => BlockTransparency::Transparent,
             Self::Water => BlockTransparency::Liquid,
             _ => BlockTransparency::Solid,
@@ -4,6 +4,7 @@ use crate::world::block_to_chunk_coord;
 use crate::world::global_block_to_chunk_pos;
 use crate::world::to_local_pos;
 use crate::world::BlockId;
+use crate::world::SIX_OFFSETS;
 use crate::CHUNK_SIZE;
 use bevy::math::bounding::Aabb3d;
 use bevy::prelude::*;
@@ -12,26 +13,63 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 
 use super::BlockData;
+use super::BlockEntity;
+use super::ChunkLight;
 use super::ItemId;
 use super::ItemType;
+use super::LightWorld;
 use super::MobId;
 use super::ServerMob;
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct ServerItemStack {
     pub id: u128,
+    /// Set once the stack is picked up, merged or expired, removed after being broadcast
     pub despawned: bool,
     pub stack: ItemStack,
     pub pos: Vec3,
+    /// Time the stack was dropped at, in milliseconds
     pub timestamp: u64,
+    /// In blocks per second
+    pub velocity: Vec3,
+    /// Number of items and position last sent to each client, so only changes are sent
+    #[serde(skip)]
+    pub sent_to_clients: HashMap<PlayerId, (u32, Vec3)>,
+}
+
+/// Gravity-affected block falling after losing its support
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerFallingBlock {
+    pub id: u128,
+    /// Set once the block has landed, removed after being broadcast
+    pub despawned: bool,
+    pub block: BlockData,
+    pub pos: Vec3,
+    /// Vertical speed, in blocks per second
+    pub velocity: f32,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 pub struct ServerChunk {
     pub map: HashMap<IVec3, BlockData>,
+    /// Keyed by local position, like `map`
+    pub block_entities: HashMap<IVec3, BlockEntity>,
     /// Timestamp marking the last update this chunk has received
     pub ts: u64,
+    #[serde(skip)]
     pub sent_to_clients: Vec<PlayerId>,
+    #[serde(skip)]
+    pub light: ChunkLight,
+}
+
+impl ServerChunk {
+    /// Marks the chunk as updated now
+    pub fn touch(&mut self) {
+        self.ts = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+    }
 }
 
 // #[derive(Resource)]
@@ -44,19 +82,66 @@ pub struct ServerWorldMap {
     pub players: HashMap<PlayerId, Player>,
     pub mobs: HashMap<MobId, ServerMob>,
     pub item_stacks: Vec<ServerItemStack>,
+    pub falling_blocks: Vec<ServerFallingBlock>,
     pub time: u64,
 }
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
 pub struct ServerChunkWorldMap {
     pub map: HashMap<IVec3, ServerChunk>,
-    pub chunks_to_update: Vec<IVec3>,
+    /// Global positions of the blocks changed since the last broadcast
+    #[serde(skip)]
+    pub blocks_to_update: Vec<IVec3>,
+    /// Changed blocks and their neighbours, waiting to react to the change
+    #[serde(skip)]
+    pub neighbour_updates: Vec<IVec3>,
+    /// Changed blocks whose light has to be updated
+    #[serde(skip)]
+    pub light_updates: Vec<IVec3>,
+}
+
+impl ServerChunkWorldMap {
+    /// Called on every block change, the block and its neighbours are updated on the next tick
+    fn notify_neighbours(&mut self, position: &IVec3) {
+        self.neighbour_updates.push(*position);
+        self.neighbour_updates
+            .extend(SIX_OFFSETS.iter().map(|offset| *position + *offset));
+    }
+
+    pub fn get_block_entity(&self, position: &IVec3) -> Option<&BlockEntity> {
+        self.map
+            .get(&global_block_to_chunk_pos(position))?
+            .block_entities
+            .get(&to_local_pos(position))
+    }
+
+    /// The block is sent again to clients, as the entity is expected to change
+    pub fn get_block_entity_mut(&mut self, position: &IVec3) -> Option<&mut BlockEntity> {
+        let chunk = self.map.get_mut(&global_block_to_chunk_pos(position))?;
+        let local_pos = to_local_pos(position);
+        if !chunk.block_entities.contains_key(&local_pos) {
+            return None;
+        }
+        chunk.touch();
+        self.blocks_to_update.push(*position);
+        chunk.block_entities.get_mut(&local_pos)
+    }
+}
+
+impl LightWorld for ServerChunkWorldMap {
+    fn get_chunk_light(&self, chunk_pos: &IVec3) -> Option<&ChunkLight> {
+        self.map.get(chunk_pos).map(|chunk| &chunk.light)
+    }
+
+    fn get_chunk_light_mut(&mut self, chunk_pos: &IVec3) -> Option<&mut ChunkLight> {
+        self.map.get_mut(chunk_pos).map(|chunk| &mut chunk.light)
+    }
 }
 
 #[derive(Resource, Clone, Copy, Serialize, Deserialize)]
 pub struct WorldSeed(pub u32);
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, Default, PartialEq)]
 pub struct ItemStack {
     pub item_id: ItemId,
     pub item_type: ItemType,
@@ -198,9 +283,6 @@ impl WorldMap for ServerChunkWorldMap {
         let kind: BlockData = *block;
 
         let chunk_pos: IVec3 = global_block_to_chunk_pos(global_block_pos);
-        let cx = chunk_pos.x;
-        let cy = chunk_pos.y;
-        let cz = chunk_pos.z;
 
         let chunk_map: &mut ServerChunk =
             self.map
@@ -209,7 +291,11 @@ impl WorldMap for ServerChunkWorldMap {
         let local_block_pos: IVec3 = to_local_pos(global_block_pos);
 
         chunk_map.map.remove(&local_block_pos);
-        self.chunks_to_update.push(IVec3::new(cx, cy, cz));
+        chunk_map.block_entities.remove(&local_block_pos);
+        chunk_map.touch();
+        self.blocks_to_update.push(*global_block_pos);
+        self.notify_neighbours(global_block_pos);
+        self.light_updates.push(*global_block_pos);
 
         Some(kind)
     }
@@ -226,8 +312,19 @@ impl WorldMap for ServerChunkWorldMap {
         let sub_y: i32 = ((y % CHUNK_SIZE) + CHUNK_SIZE) % CHUNK_SIZE;
         let sub_z: i32 = ((z % CHUNK_SIZE) + CHUNK_SIZE) % CHUNK_SIZE;
 
-        chunk.map.insert(IVec3::new(sub_x, sub_y, sub_z), block);
-        self.chunks_to_update.push(IVec3::new(cx, cy, cz));
+        let local_pos = IVec3::new(sub_x, sub_y, sub_z);
+        let previous = chunk.map.insert(local_pos, block);
+        // The entity is kept as long as the block type does not change
+        if previous.map(|previous| previous.id) != Some(block.id) {
+            chunk.block_entities.remove(&local_pos);
+            if let Some(entity) = BlockEntity::new(block.id) {
+                chunk.block_entities.insert(local_pos, entity);
+            }
+        }
+        chunk.touch();
+        self.blocks_to_update.push(*position);
+        self.notify_neighbours(position);
+        self.light_updates.push(*position);
     }
 
     fn check_collision_box(&self, hitbox: &Aabb3d) -> bool {
@@ -235,8 +332,9 @@ impl WorldMap for ServerChunkWorldMap {
         for x in (hitbox.min.x.round() as i32)..=(hitbox.max.x.round() as i32) {
             for y in (hitbox.min.y.round() as i32)..=(hitbox.max.y.round() as i32) {
                 for z in (hitbox.min.z.round() as i32)..=(hitbox.max.z.round() as i32) {
-                    if let Some(block) = self.get_block_by_coordinates(&IVec3::new(x, y, z)) {
-                        if block.id.has_hitbox() {
+                    let position = IVec3::new(x, y, z);
+                    if let Some(block) = self.get_block_by_coordinates(&position) {
+                        if block.collides_with(&position, hitbox) {
                             return true;
                         }
                     }
@@ -247,12 +345,13 @@ impl WorldMap for ServerChunkWorldMap {
     }
 
     fn check_collision_point(&self, point: &Vec3) -> bool {
-        if let Some(block) = self.get_block_by_coordinates(&IVec3::new(
+        let position = IVec3::new(
             point.x.round() as i32,
             point.y.round() as i32,
             point.z.round() as i32,
-        )) {
-            block.id.has_hitbox()
+        );
+        if let Some(block) = self.get_block_by_coordinates(&position) {
+            block.collides_with(&position, &Aabb3d::new(*point, Vec3::ZERO))
         } else {
             false
         }
@@ -0,0 +1,54 @@
+use bevy::math::IVec3;
+
+use super::{BlockData, BlockDirection, BlockId, BlockTransparency};
+
+/// Water state : 0 is a source block, 1 to `MAX_FLOW_DISTANCE` is flowing water,
+/// the value being its distance to the closest source\
+/// `FALLING_FLAG` marks water fed from above, which spreads like a source when it lands
+pub const FALLING_FLAG: u8 = 0x8;
+pub const MAX_FLOW_DISTANCE: u8 = 7;
+
+/// Directions water spreads to when it cannot go down
+pub const HORIZONTAL_NEIGHBOURS: [IVec3; 4] = [IVec3::X, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z];
+
+impl BlockData {
+    pub fn water(state: u8) -> Self {
+        Self {
+            state,
+            ..Self::new(BlockId::Water, false, BlockDirection::Front)
+        }
+    }
+
+    pub fn is_water_source(&self) -> bool {
+        self.id == BlockId::Water && self.state == 0
+    }
+
+    pub fn is_falling_water(&self) -> bool {
+        self.id == BlockId::Water && self.state & FALLING_FLAG != 0
+    }
+
+    /// Distance to the source feeding this water, falling water counts as a source
+    pub fn flow_distance(&self) -> u8 {
+        if self.is_falling_water() {
+            0
+        } else {
+            self.state
+        }
+    }
+
+    /// Height of the water surface, from 0 to 1, used for rendering
+    pub fn water_height(&self) -> f32 {
+        if self.is_water_source() || self.is_falling_water() {
+            1.0
+        } else {
+            1.0 - self.state as f32 / (MAX_FLOW_DISTANCE + 1) as f32
+        }
+    }
+}
+
+impl BlockId {
+    /// Blocks destroyed by flowing water, as if the cell was empty
+    pub fn is_replaceable_by_fluid(&self) -> bool {
+        self.get_visibility() == BlockTransparency::Decoration
+    }
+}
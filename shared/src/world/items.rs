@@ -37,10 +37,65 @@ pub enum ItemId {
     Snow,
     Snowball,
     SpruceLog,
+    Stick,
+    WoodenPickaxe,
+    WoodenAxe,
+    WoodenShovel,
+    StonePickaxe,
+    StoneAxe,
+    StoneShovel,
+    Chest,
+    OakSlab,
+    OakStairs,
+    OakDoor,
+    Torch,
+    Glowstone,
+    RawPorkchop,
+    RottenFlesh,
 }
 
 impl ItemId {
+    /// Every item, in declaration order, listed by the creative item picker
+    pub const ALL: [ItemId; 33] = [
+        Self::Dirt,
+        Self::Grass,
+        Self::Stone,
+        Self::OakLog,
+        Self::OakPlanks,
+        Self::OakLeaves,
+        Self::Sand,
+        Self::Cactus,
+        Self::Ice,
+        Self::Glass,
+        Self::Bedrock,
+        Self::Dandelion,
+        Self::TallGrass,
+        Self::Poppy,
+        Self::Cobblestone,
+        Self::Snow,
+        Self::Snowball,
+        Self::SpruceLog,
+        Self::Stick,
+        Self::WoodenPickaxe,
+        Self::WoodenAxe,
+        Self::WoodenShovel,
+        Self::StonePickaxe,
+        Self::StoneAxe,
+        Self::StoneShovel,
+        Self::Chest,
+        Self::OakSlab,
+        Self::OakStairs,
+        Self::OakDoor,
+        Self::Torch,
+        Self::Glowstone,
+        Self::RawPorkchop,
+        Self::RottenFlesh,
+    ];
+
     pub fn get_max_stack(&self) -> u32 {
+        if self.get_tool().is_some() {
+            return 1;
+        }
         64
     }
 
@@ -63,8 +118,57 @@ impl ItemId {
             Self::Cobblestone => ItemType::Block(BlockId::Cobblestone),
             Self::Snow => ItemType::Block(BlockId::Snow),
             Self::SpruceLog => ItemType::Block(BlockId::SpruceLog),
+            Self::Chest => ItemType::Block(BlockId::Chest),
+            Self::OakSlab => ItemType::Block(BlockId::OakSlab),
+            Self::OakStairs => ItemType::Block(BlockId::OakStairs),
+            Self::OakDoor => ItemType::Block(BlockId::OakDoor),
+            Self::Torch => ItemType::Block(BlockId::Torch),
+            Self::Glowstone => ItemType::Block(BlockId::Glowstone),
+
+            Self::Snowball | Self::Stick | Self::RawPorkchop | Self::RottenFlesh => {
+                ItemType::Generic
+            }
+
+            Self::WoodenPickaxe
+            | Self::WoodenAxe
+            | Self::WoodenShovel
+            | Self::StonePickaxe
+            | Self::StoneAxe
+            | Self::StoneShovel => ItemType::Tool {
+                durability: self.get_tool().unwrap().1.get_max_durability(),
+            },
+        }
+    }
+}
 
-            Self::Snowball => ItemType::Generic,
+impl ItemId {
+    /// Item placing the given block, if there is one
+    pub fn from_block(block_id: BlockId) -> Option<Self> {
+        match block_id {
+            BlockId::Dirt => Some(Self::Dirt),
+            BlockId::Bedrock => Some(Self::Bedrock),
+            BlockId::Grass => Some(Self::Grass),
+            BlockId::Stone => Some(Self::Stone),
+            BlockId::OakLog => Some(Self::OakLog),
+            BlockId::OakPlanks => Some(Self::OakPlanks),
+            BlockId::Sand => Some(Self::Sand),
+            BlockId::Cactus => Some(Self::Cactus),
+            BlockId::Ice => Some(Self::Ice),
+            BlockId::OakLeaves => Some(Self::OakLeaves),
+            BlockId::Glass => Some(Self::Glass),
+            BlockId::Dandelion => Some(Self::Dandelion),
+            BlockId::Poppy => Some(Self::Poppy),
+            BlockId::TallGrass => Some(Self::TallGrass),
+            BlockId::Cobblestone => Some(Self::Cobblestone),
+            BlockId::Snow => Some(Self::Snow),
+            BlockId::SpruceLog => Some(Self::SpruceLog),
+            BlockId::Chest => Some(Self::Chest),
+            BlockId::OakSlab => Some(Self::OakSlab),
+            BlockId::OakStairs => Some(Self::OakStairs),
+            BlockId::OakDoor => Some(Self::OakDoor),
+            BlockId::Torch => Some(Self::Torch),
+            BlockId::Glowstone => Some(Self::Glowstone),
+            BlockId::Debug | BlockId::SpruceLeaves | BlockId::Water | BlockId::SnowLayer => None,
         }
     }
 }
@@ -0,0 +1,426 @@
+use bevy::math::IVec3;
+use std::collections::{HashSet, VecDeque};
+
+use crate::CHUNK_SIZE;
+
+use super::{
+    global_block_to_chunk_pos, to_global_pos, to_local_pos, BlockId, BlockTransparency, WorldMap,
+    SIX_OFFSETS,
+};
+
+/// Light of the open sky, and of the brightest blocks
+pub const MAX_LIGHT_LEVEL: u8 = 15;
+
+const CHUNK_VOLUME: usize = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// Sky light comes from above and changes with the time of day, block light comes from blocks such as torches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightChannel {
+    Sky,
+    Block,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LightLevel {
+    pub sky: u8,
+    pub block: u8,
+}
+
+impl LightLevel {
+    pub fn brightest(&self) -> u8 {
+        self.sky.max(self.block)
+    }
+}
+
+/// Light of every block of a chunk, sky light in the high nibble of each level and block light in the low one\
+/// Not saved nor sent, every side computes it when the chunk is loaded
+#[derive(Debug, Clone)]
+pub struct ChunkLight {
+    levels: Vec<u8>,
+    /// Set by `light_chunk`, every level is 0 until then
+    pub is_lit: bool,
+}
+
+impl Default for ChunkLight {
+    fn default() -> Self {
+        Self {
+            levels: vec![0; CHUNK_VOLUME],
+            is_lit: false,
+        }
+    }
+}
+
+impl ChunkLight {
+    fn index(local_pos: &IVec3) -> usize {
+        ((local_pos.y * CHUNK_SIZE + local_pos.z) * CHUNK_SIZE + local_pos.x) as usize
+    }
+
+    pub fn get(&self, local_pos: &IVec3, channel: LightChannel) -> u8 {
+        let level = self.levels[Self::index(local_pos)];
+        match channel {
+            LightChannel::Sky => level >> 4,
+            LightChannel::Block => level & 0xF,
+        }
+    }
+
+    pub fn set(&mut self, local_pos: &IVec3, channel: LightChannel, value: u8) {
+        let level = &mut self.levels[Self::index(local_pos)];
+        *level = match channel {
+            LightChannel::Sky => (*level & 0xF) | (value << 4),
+            LightChannel::Block => (*level & 0xF0) | (value & 0xF),
+        };
+    }
+
+    pub fn get_level(&self, local_pos: &IVec3) -> LightLevel {
+        LightLevel {
+            sky: self.get(local_pos, LightChannel::Sky),
+            block: self.get(local_pos, LightChannel::Block),
+        }
+    }
+}
+
+/// World whose chunks carry their `ChunkLight`, a chunk is loaded if it has one
+pub trait LightWorld: WorldMap {
+    fn get_chunk_light(&self, chunk_pos: &IVec3) -> Option<&ChunkLight>;
+    fn get_chunk_light_mut(&mut self, chunk_pos: &IVec3) -> Option<&mut ChunkLight>;
+}
+
+impl BlockId {
+    /// Levels lost by light going through the block, on top of the one lost for each block travelled\
+    /// `MAX_LIGHT_LEVEL` stops light entirely
+    pub fn get_light_opacity(&self) -> u8 {
+        match *self {
+            Self::OakLeaves | Self::SpruceLeaves => 1,
+            Self::Water => 2,
+            _ if self.get_visibility() == BlockTransparency::Solid && self.is_full_cube() => {
+                MAX_LIGHT_LEVEL
+            }
+            _ => 0,
+        }
+    }
+
+    /// Block light given off by the block
+    pub fn get_light_emission(&self) -> u8 {
+        match *self {
+            Self::Glowstone => MAX_LIGHT_LEVEL,
+            Self::Torch => 14,
+            _ => 0,
+        }
+    }
+}
+
+/// `None` if the chunk is not loaded or not lit yet
+pub fn get_light_level(world: &impl LightWorld, position: &IVec3) -> Option<LightLevel> {
+    world
+        .get_chunk_light(&global_block_to_chunk_pos(position))
+        .filter(|light| light.is_lit)
+        .map(|light| light.get_level(&to_local_pos(position)))
+}
+
+fn get_level(world: &impl LightWorld, position: &IVec3, channel: LightChannel) -> Option<u8> {
+    world
+        .get_chunk_light(&global_block_to_chunk_pos(position))
+        .map(|light| light.get(&to_local_pos(position), channel))
+}
+
+fn set_level(
+    world: &mut impl LightWorld,
+    position: &IVec3,
+    channel: LightChannel,
+    value: u8,
+    changed: &mut HashSet<IVec3>,
+) {
+    let chunk_pos = global_block_to_chunk_pos(position);
+    if let Some(light) = world.get_chunk_light_mut(&chunk_pos) {
+        light.set(&to_local_pos(position), channel, value);
+        changed.insert(chunk_pos);
+    }
+}
+
+/// Only called for loaded positions, the chunk map warns about missing chunks
+fn get_opacity(world: &impl LightWorld, position: &IVec3) -> u8 {
+    world
+        .get_block_by_coordinates(position)
+        .map_or(0, |block| block.id.get_light_opacity())
+}
+
+/// Light a block produces by itself\
+/// Blocks right below a chunk that is not loaded are lit by the sky, as if nothing was above them
+fn get_source_level(world: &impl LightWorld, position: &IVec3, channel: LightChannel) -> u8 {
+    match channel {
+        LightChannel::Block => world
+            .get_block_by_coordinates(position)
+            .map_or(0, |block| block.id.get_light_emission()),
+        LightChannel::Sky => {
+            let above = global_block_to_chunk_pos(&(*position + IVec3::Y));
+            if world.get_chunk_light(&above).is_some() {
+                0
+            } else {
+                MAX_LIGHT_LEVEL.saturating_sub(get_opacity(world, position))
+            }
+        }
+    }
+}
+
+/// Full sky light goes straight down without getting dimmer
+fn is_sky_beam(channel: LightChannel, offset: &IVec3, level: u8) -> bool {
+    channel == LightChannel::Sky && *offset == IVec3::NEG_Y && level == MAX_LIGHT_LEVEL
+}
+
+/// Spreads the light of the queued positions to their neighbours, until it fades out
+fn propagate(
+    world: &mut impl LightWorld,
+    channel: LightChannel,
+    queue: &mut VecDeque<IVec3>,
+    changed: &mut HashSet<IVec3>,
+) {
+    while let Some(position) = queue.pop_front() {
+        let Some(level) = get_level(world, &position, channel) else {
+            continue;
+        };
+        if level <= 1 {
+            continue;
+        }
+
+        for offset in SIX_OFFSETS.iter() {
+            let neighbour = position + *offset;
+            let Some(current) = get_level(world, &neighbour, channel) else {
+                continue;
+            };
+            let opacity = get_opacity(world, &neighbour);
+            if opacity >= MAX_LIGHT_LEVEL {
+                continue;
+            }
+
+            let new_level = if is_sky_beam(channel, offset, level) && opacity == 0 {
+                level
+            } else {
+                level.saturating_sub(1 + opacity)
+            };
+            if new_level > current {
+                set_level(world, &neighbour, channel, new_level, changed);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+}
+
+/// Darkens everything lit by the queued positions, which were already set to 0 with their previous level queued\
+/// Brighter lights met on the way are queued in `addition` to fill the darkened area back
+fn unpropagate(
+    world: &mut impl LightWorld,
+    channel: LightChannel,
+    removal: &mut VecDeque<(IVec3, u8)>,
+    addition: &mut VecDeque<IVec3>,
+    changed: &mut HashSet<IVec3>,
+) {
+    while let Some((position, level)) = removal.pop_front() {
+        for offset in SIX_OFFSETS.iter() {
+            let neighbour = position + *offset;
+            let Some(current) = get_level(world, &neighbour, channel) else {
+                continue;
+            };
+            if current == 0 {
+                continue;
+            }
+
+            if current < level || (is_sky_beam(channel, offset, level) && current == level) {
+                set_level(world, &neighbour, channel, 0, changed);
+                removal.push_back((neighbour, current));
+
+                let source = get_source_level(world, &neighbour, channel);
+                if source > 0 {
+                    set_level(world, &neighbour, channel, source, changed);
+                    addition.push_back(neighbour);
+                }
+            } else {
+                addition.push_back(neighbour);
+            }
+        }
+    }
+}
+
+/// Lights a chunk that was just loaded, along with the loaded chunks its light reaches\
+/// Returns the positions of the chunks whose light changed
+pub fn light_chunk(world: &mut impl LightWorld, chunk_pos: &IVec3) -> HashSet<IVec3> {
+    let mut changed = HashSet::new();
+    let Some(light) = world.get_chunk_light_mut(chunk_pos) else {
+        return changed;
+    };
+    *light = ChunkLight::default();
+
+    for channel in [LightChannel::Sky, LightChannel::Block] {
+        let mut addition = VecDeque::new();
+        for x in 0..CHUNK_SIZE {
+            for y in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    let position = to_global_pos(chunk_pos, &IVec3::new(x, y, z));
+                    let source = get_source_level(world, &position, channel);
+                    if source > 0 {
+                        set_level(world, &position, channel, source, &mut changed);
+                        addition.push_back(position);
+                    }
+
+                    // Light coming in from the chunks around
+                    let is_border = [x, y, z].iter().any(|c| *c == 0 || *c == CHUNK_SIZE - 1);
+                    if is_border {
+                        addition.extend(SIX_OFFSETS.iter().map(|offset| position + *offset));
+                    }
+                }
+            }
+        }
+        propagate(world, channel, &mut addition, &mut changed);
+    }
+
+    // The chunk below was lit as if nothing was above it
+    let below = *chunk_pos - IVec3::Y;
+    if world.get_chunk_light(&below).is_some() {
+        let mut removal = VecDeque::new();
+        let mut addition = VecDeque::new();
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let position = to_global_pos(&below, &IVec3::new(x, CHUNK_SIZE - 1, z));
+                let above = get_level(world, &(position + IVec3::Y), LightChannel::Sky);
+                let current = get_level(world, &position, LightChannel::Sky);
+                if above < Some(MAX_LIGHT_LEVEL) && current == Some(MAX_LIGHT_LEVEL) {
+                    set_level(world, &position, LightChannel::Sky, 0, &mut changed);
+                    removal.push_back((position, MAX_LIGHT_LEVEL));
+                }
+            }
+        }
+        unpropagate(
+            world,
+            LightChannel::Sky,
+            &mut removal,
+            &mut addition,
+            &mut changed,
+        );
+        propagate(world, LightChannel::Sky, &mut addition, &mut changed);
+    }
+
+    if let Some(light) = world.get_chunk_light_mut(chunk_pos) {
+        light.is_lit = true;
+    }
+    changed.insert(*chunk_pos);
+    changed
+}
+
+/// Updates the light around a block that was just placed or removed\
+/// Returns the positions of the chunks whose light changed
+pub fn update_light(world: &mut impl LightWorld, position: &IVec3) -> HashSet<IVec3> {
+    let mut changed = HashSet::new();
+    let is_lit = world
+        .get_chunk_light(&global_block_to_chunk_pos(position))
+        .is_some_and(|light| light.is_lit);
+    if !is_lit {
+        // The whole chunk will be lit at once
+        return changed;
+    }
+
+    for channel in [LightChannel::Sky, LightChannel::Block] {
+        let mut removal = VecDeque::new();
+        let mut addition = VecDeque::new();
+
+        let previous = get_level(world, position, channel).unwrap_or(0);
+        set_level(world, position, channel, 0, &mut changed);
+        if previous > 0 {
+            removal.push_back((*position, previous));
+        }
+
+        let source = get_source_level(world, position, channel);
+        if source > 0 {
+            set_level(world, position, channel, source, &mut changed);
+            addition.push_back(*position);
+        }
+
+        // Light around may now go through the block
+        addition.extend(SIX_OFFSETS.iter().map(|offset| *position + *offset));
+
+        unpropagate(world, channel, &mut removal, &mut addition, &mut changed);
+        propagate(world, channel, &mut addition, &mut changed);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{BlockData, BlockDirection, ServerChunk, ServerChunkWorldMap};
+
+    fn block(id: BlockId) -> BlockData {
+        BlockData::new(id, false, BlockDirection::Front)
+    }
+
+    /// A single chunk, with a stone layer at `roof_y` if given
+    fn single_chunk(roof_y: Option<i32>) -> ServerChunkWorldMap {
+        let mut chunk = ServerChunk::default();
+        if let Some(y) = roof_y {
+            for x in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    chunk.map.insert(IVec3::new(x, y, z), block(BlockId::Stone));
+                }
+            }
+        }
+        let mut world = ServerChunkWorldMap::default();
+        world.map.insert(IVec3::ZERO, chunk);
+        light_chunk(&mut world, &IVec3::ZERO);
+        world
+    }
+
+    #[test]
+    fn channels_are_stored_separately() {
+        let mut light = ChunkLight::default();
+        let position = IVec3::new(3, 4, 5);
+        light.set(&position, LightChannel::Sky, 12);
+        light.set(&position, LightChannel::Block, 7);
+
+        assert_eq!(light.get_level(&position), LightLevel { sky: 12, block: 7 });
+    }
+
+    #[test]
+    fn open_chunks_are_lit_by_the_sky() {
+        let world = single_chunk(None);
+        let level = get_light_level(&world, &IVec3::new(8, 0, 8)).unwrap();
+        assert_eq!(level.sky, MAX_LIGHT_LEVEL);
+    }
+
+    #[test]
+    fn roofs_block_the_sky() {
+        let world = single_chunk(Some(10));
+        assert_eq!(
+            get_light_level(&world, &IVec3::new(8, 11, 8)).unwrap().sky,
+            MAX_LIGHT_LEVEL
+        );
+        assert_eq!(
+            get_light_level(&world, &IVec3::new(8, 5, 8)).unwrap().sky,
+            0
+        );
+    }
+
+    #[test]
+    fn placed_and_removed_lights_update_around_them() {
+        let mut world = single_chunk(Some(10));
+        let torch = IVec3::new(8, 5, 8);
+        let next_to_it = torch + IVec3::X * 2;
+
+        world
+            .map
+            .get_mut(&IVec3::ZERO)
+            .unwrap()
+            .map
+            .insert(torch, block(BlockId::Glowstone));
+        update_light(&mut world, &torch);
+        assert_eq!(
+            get_light_level(&world, &torch).unwrap().block,
+            MAX_LIGHT_LEVEL
+        );
+        assert_eq!(
+            get_light_level(&world, &next_to_it).unwrap().block,
+            MAX_LIGHT_LEVEL - 2
+        );
+
+        world.map.get_mut(&IVec3::ZERO).unwrap().map.remove(&torch);
+        update_light(&mut world, &torch);
+        assert_eq!(get_light_level(&world, &next_to_it).unwrap().block, 0);
+    }
+}
@@ -2,12 +2,49 @@ use bevy::math::{Quat, Vec3};
 use serde::{Deserialize, Serialize};
 
 use crate::messages::PlayerId;
+use crate::world::ItemId;
+use std::ops::RangeInclusive;
 
 pub type MobId = u128;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum MobKind {
     Fox,
+    Pig,
+    Zombie,
+}
+
+impl MobKind {
+    /// Free blocks a mob needs above the ground to stand somewhere
+    pub fn get_height_in_blocks(&self) -> i32 {
+        self.get_hitbox_size().y.ceil() as i32
+    }
+
+    /// Size of the hitbox, standing on the position of the mob
+    pub fn get_hitbox_size(&self) -> Vec3 {
+        match *self {
+            Self::Fox => Vec3::new(0.6, 0.7, 0.6),
+            Self::Pig => Vec3::new(0.9, 0.9, 0.9),
+            Self::Zombie => Vec3::new(0.6, 1.9, 0.6),
+        }
+    }
+
+    pub fn get_max_health(&self) -> u32 {
+        match *self {
+            Self::Fox => 10,
+            Self::Pig => 10,
+            Self::Zombie => 20,
+        }
+    }
+
+    /// Items dropped when the mob dies, with the range of how many of each
+    pub fn get_loot_table(&self) -> Vec<(ItemId, RangeInclusive<u32>)> {
+        match *self {
+            Self::Fox => vec![],
+            Self::Pig => vec![(ItemId::RawPorkchop, 1..=3)],
+            Self::Zombie => vec![(ItemId::RottenFlesh, 0..=2)],
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -31,6 +68,11 @@ pub struct ServerMob {
     pub kind: MobKind,
     pub target: MobTarget,
     pub action: MobAction,
+    /// Position of the feet
     pub position: Vec3,
     pub rotation: Quat,
+    /// In blocks per second
+    pub velocity: Vec3,
+    pub on_ground: bool,
+    pub health: u32,
 }
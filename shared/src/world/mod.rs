@@ -1,11 +1,23 @@
+pub mod block_entities;
 pub mod blocks;
 pub mod data;
+pub mod fluids;
 pub mod items;
+pub mod light;
 pub mod mobs;
+pub mod shapes;
+pub mod tools;
 mod utils;
+pub mod weather;
 
+pub use block_entities::*;
 pub use blocks::*;
 pub use data::*;
+pub use fluids::*;
 pub use items::*;
+pub use light::*;
 pub use mobs::*;
+pub use shapes::*;
+pub use tools::*;
 pub use utils::*;
+pub use weather::*;
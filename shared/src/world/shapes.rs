@@ -0,0 +1,253 @@
+use bevy::math::{bounding::Aabb3d, IVec3, Vec3};
+
+use crate::HALF_BLOCK;
+
+use super::{BlockData, BlockDirection, BlockId, WorldMap};
+
+/// Door state flags : doors are two blocks tall, the upper half has `DOOR_UPPER` set\
+/// Both halves share `DOOR_OPEN`
+pub const DOOR_UPPER: u8 = 0x1;
+pub const DOOR_OPEN: u8 = 0x2;
+
+/// Torch state flag, set on torches hanging on a wall instead of standing on a block
+pub const TORCH_ON_WALL: u8 = 0x1;
+
+/// Snow layers a single block can hold, it is then as tall as a full block
+pub const MAX_SNOW_LAYERS: u8 = 8;
+
+/// Thickness of a door panel, in blocks
+const DOOR_THICKNESS: f32 = 3. / 16.;
+/// Width and height of a torch, in blocks
+const TORCH_WIDTH: f32 = 2. / 16.;
+const TORCH_HEIGHT: f32 = 10. / 16.;
+
+/// Geometry of a block, used for rendering and collisions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockShape {
+    Cube,
+    /// Lower half of a block, upper half when flipped
+    Slab,
+    /// A slab with a step on its back half
+    Stairs,
+    /// A thin panel on the front of the block, on its left side when open
+    Door,
+    /// A thin stick standing in the middle of the block, or against its front side when on a wall
+    Torch,
+    /// Stacked layers at the bottom of the block, such as snow
+    Layers,
+}
+
+impl BlockShape {
+    /// Shapes that can be placed upside down
+    pub fn can_be_flipped(&self) -> bool {
+        matches!(self, Self::Slab | Self::Stairs)
+    }
+}
+
+impl BlockId {
+    pub fn get_shape(&self) -> BlockShape {
+        match *self {
+            Self::OakSlab => BlockShape::Slab,
+            Self::OakStairs => BlockShape::Stairs,
+            Self::OakDoor => BlockShape::Door,
+            Self::Torch => BlockShape::Torch,
+            Self::SnowLayer => BlockShape::Layers,
+            _ => BlockShape::Cube,
+        }
+    }
+
+    /// Whether the block fills its whole cell, hiding the faces of its neighbours
+    pub fn is_full_cube(&self) -> bool {
+        self.get_shape() == BlockShape::Cube
+    }
+}
+
+impl BlockDirection {
+    /// Rotates a vector around the vertical axis, `Front` leaves it unchanged
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        match *self {
+            Self::Front => v,
+            Self::Right => Vec3::new(-v.z, v.y, v.x),
+            Self::Back => Vec3::new(-v.x, v.y, -v.z),
+            Self::Left => Vec3::new(v.z, v.y, -v.x),
+        }
+    }
+
+    /// Direction a block should face to look back at someone looking towards `forward`
+    pub fn facing(forward: Vec3) -> Self {
+        if forward.x.abs() > forward.z.abs() {
+            if forward.x > 0. {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if forward.z > 0. {
+            Self::Front
+        } else {
+            Self::Back
+        }
+    }
+}
+
+impl BlockData {
+    /// Flips then rotates a vector, such as a normal, the way the block is
+    pub fn orient_vector(&self, v: Vec3) -> Vec3 {
+        let v = if self.flipped {
+            Vec3::new(v.x, -v.y, v.z)
+        } else {
+            v
+        };
+        self.direction.rotate(v)
+    }
+
+    /// Moves a point of the block space (0 to 1 on each axis) to where it is once the block is oriented
+    pub fn orient(&self, point: Vec3) -> Vec3 {
+        self.orient_vector(point - HALF_BLOCK) + HALF_BLOCK
+    }
+
+    /// Boxes making up the block as `(min, max)`, in block space and before it is oriented
+    pub fn get_shape_boxes(&self) -> Vec<(Vec3, Vec3)> {
+        match self.id.get_shape() {
+            BlockShape::Cube => vec![(Vec3::ZERO, Vec3::ONE)],
+            BlockShape::Slab => vec![(Vec3::ZERO, Vec3::new(1., 0.5, 1.))],
+            BlockShape::Stairs => vec![
+                (Vec3::ZERO, Vec3::new(1., 0.5, 1.)),
+                (Vec3::new(0., 0.5, 0.5), Vec3::ONE),
+            ],
+            BlockShape::Door => {
+                if self.state & DOOR_OPEN != 0 {
+                    vec![(Vec3::ZERO, Vec3::new(DOOR_THICKNESS, 1., 1.))]
+                } else {
+                    vec![(Vec3::ZERO, Vec3::new(1., 1., DOOR_THICKNESS))]
+                }
+            }
+            BlockShape::Torch => {
+                let (x0, x1) = (0.5 - TORCH_WIDTH / 2., 0.5 + TORCH_WIDTH / 2.);
+                if self.state & TORCH_ON_WALL != 0 {
+                    let y0 = (1. - TORCH_HEIGHT) / 2.;
+                    vec![(
+                        Vec3::new(x0, y0, 0.),
+                        Vec3::new(x1, y0 + TORCH_HEIGHT, TORCH_WIDTH),
+                    )]
+                } else {
+                    vec![(Vec3::new(x0, 0., x0), Vec3::new(x1, TORCH_HEIGHT, x1))]
+                }
+            }
+            BlockShape::Layers => {
+                let height = self.snow_layers() as f32 / MAX_SNOW_LAYERS as f32;
+                vec![(Vec3::ZERO, Vec3::new(1., height, 1.))]
+            }
+        }
+    }
+
+    /// Oriented boxes of the block placed at `position`, in world space
+    pub fn get_boxes(&self, position: &IVec3) -> Vec<Aabb3d> {
+        let origin = position.as_vec3() - HALF_BLOCK;
+        self.get_shape_boxes()
+            .into_iter()
+            .map(|(min, max)| {
+                let (a, b) = (self.orient(min), self.orient(max));
+                Aabb3d {
+                    min: (origin + a.min(b)).into(),
+                    max: (origin + a.max(b)).into(),
+                }
+            })
+            .collect()
+    }
+
+    /// Boxes hit when looking at the block
+    pub fn get_interaction_boxes(&self, position: &IVec3) -> Vec<Aabb3d> {
+        if self.id.is_full_cube() {
+            vec![self.id.get_interaction_box(position)]
+        } else {
+            self.get_boxes(position)
+        }
+    }
+
+    /// Whether `hitbox` overlaps the block, assuming it is within the cell of the block\
+    /// Full cubes fill their whole cell, so only the boxes of other shapes are checked
+    pub fn collides_with(&self, position: &IVec3, hitbox: &Aabb3d) -> bool {
+        if !self.id.has_hitbox() {
+            return false;
+        }
+        if self.id.is_full_cube() {
+            return true;
+        }
+
+        // Touching a box is not a collision, so players can stand on slabs
+        self.get_boxes(position)
+            .iter()
+            .any(|b| b.min.cmplt(hitbox.max).all() && b.max.cmpgt(hitbox.min).all())
+    }
+
+    /// Number of layers of a snow layer block, from 1 to `MAX_SNOW_LAYERS`
+    pub fn snow_layers(&self) -> u8 {
+        self.state.min(MAX_SNOW_LAYERS - 1) + 1
+    }
+
+    /// Position of the block a torch is attached to, below it or behind it when on a wall
+    pub fn torch_support(&self, position: &IVec3) -> IVec3 {
+        if self.state & TORCH_ON_WALL != 0 {
+            *position + self.orient_vector(Vec3::NEG_Z).round().as_ivec3()
+        } else {
+            *position - IVec3::Y
+        }
+    }
+
+    /// Position of the block holding this one, for blocks that break without it
+    pub fn get_support(&self, position: &IVec3) -> Option<IVec3> {
+        match self.id.get_shape() {
+            BlockShape::Torch => Some(self.torch_support(position)),
+            BlockShape::Layers => Some(*position - IVec3::Y),
+            _ => None,
+        }
+    }
+
+    /// Position of the other half of a door
+    pub fn door_partner(&self, position: &IVec3) -> IVec3 {
+        if self.state & DOOR_UPPER != 0 {
+            *position - IVec3::Y
+        } else {
+            *position + IVec3::Y
+        }
+    }
+}
+
+/// Opens or closes the door at `position`, both halves at once\
+/// Returns the positions of the changed blocks
+pub fn toggle_door(world_map: &mut impl WorldMap, position: &IVec3) -> Vec<IVec3> {
+    let Some(&block) = world_map.get_block_by_coordinates(position) else {
+        return vec![];
+    };
+    if block.id.get_shape() != BlockShape::Door {
+        return vec![];
+    }
+
+    let mut changed = vec![];
+    for pos in [*position, block.door_partner(position)] {
+        if let Some(&half) = world_map.get_block_by_coordinates(&pos) {
+            if half.id == block.id {
+                world_map.set_block(
+                    &pos,
+                    BlockData {
+                        state: half.state ^ DOOR_OPEN,
+                        ..half
+                    },
+                );
+                changed.push(pos);
+            }
+        }
+    }
+    changed
+}
+
+/// Whether the block holding the one placed at `position` can hold it, only full blocks with a hitbox can\
+/// Always true for blocks that need nothing to hold them
+pub fn is_supported(world_map: &impl WorldMap, position: &IVec3, block: &BlockData) -> bool {
+    let Some(support) = block.get_support(position) else {
+        return true;
+    };
+    world_map
+        .get_block_by_coordinates(&support)
+        .is_some_and(|support| support.id.has_hitbox() && support.id.is_full_cube())
+}
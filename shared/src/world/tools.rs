@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use super::{BlockId, BlockTags, ItemId};
+
+/// Mining speed when breaking a block tagged `Stone` without a pickaxe
+const WRONG_TOOL_SPEED: f32 = 0.3;
+/// Breaking steps, one per tick, for each unit of `BlockId::get_break_time` when breaking by hand
+const BREAK_STEPS_PER_TIME: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ToolTier {
+    Wooden,
+    Stone,
+}
+
+impl ToolKind {
+    /// Blocks with this tag break faster with this kind of tool
+    pub fn effective_on(&self) -> BlockTags {
+        match *self {
+            Self::Pickaxe => BlockTags::Stone,
+            Self::Axe => BlockTags::Wood,
+            Self::Shovel => BlockTags::Soil,
+        }
+    }
+}
+
+impl ToolTier {
+    pub fn get_speed(&self) -> f32 {
+        match *self {
+            Self::Wooden => 2.0,
+            Self::Stone => 4.0,
+        }
+    }
+
+    pub fn get_max_durability(&self) -> u16 {
+        match *self {
+            Self::Wooden => 60,
+            Self::Stone => 132,
+        }
+    }
+}
+
+impl ItemId {
+    pub fn get_tool(&self) -> Option<(ToolKind, ToolTier)> {
+        match *self {
+            Self::WoodenPickaxe => Some((ToolKind::Pickaxe, ToolTier::Wooden)),
+            Self::WoodenAxe => Some((ToolKind::Axe, ToolTier::Wooden)),
+            Self::WoodenShovel => Some((ToolKind::Shovel, ToolTier::Wooden)),
+            Self::StonePickaxe => Some((ToolKind::Pickaxe, ToolTier::Stone)),
+            Self::StoneAxe => Some((ToolKind::Axe, ToolTier::Stone)),
+            Self::StoneShovel => Some((ToolKind::Shovel, ToolTier::Stone)),
+            _ => None,
+        }
+    }
+}
+
+impl BlockId {
+    /// Whether the item is a tool made for this block, the only tools it wears out
+    pub fn is_effective_tool(&self, tool: Option<ItemId>) -> bool {
+        tool.and_then(|item| item.get_tool())
+            .is_some_and(|(kind, _)| self.get_tags().contains(&kind.effective_on()))
+    }
+
+    /// How many times faster than by hand the block breaks with the given item
+    pub fn get_mining_speed(&self, tool: Option<ItemId>) -> f32 {
+        match tool.and_then(|item| item.get_tool()) {
+            Some((_, tier)) if self.is_effective_tool(tool) => tier.get_speed(),
+            _ if self.get_tags().contains(&BlockTags::Stone) => WRONG_TOOL_SPEED,
+            _ => 1.0,
+        }
+    }
+
+    /// Stone blocks only drop items when mined with a pickaxe
+    pub fn can_harvest(&self, tool: Option<ItemId>) -> bool {
+        if !self.get_tags().contains(&BlockTags::Stone) {
+            return true;
+        }
+        matches!(
+            tool.and_then(|item| item.get_tool()),
+            Some((ToolKind::Pickaxe, _))
+        )
+    }
+
+    /// Ticks of breaking needed to break the block, `None` if it cannot be broken
+    pub fn get_break_steps(&self, tool: Option<ItemId>) -> Option<u32> {
+        let break_time = self.get_break_time()?;
+        let steps = BREAK_STEPS_PER_TIME * break_time as f32 / self.get_mining_speed(tool);
+        Some(steps.ceil() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unbreakable_blocks_have_no_break_steps() {
+        assert_eq!(BlockId::Bedrock.get_break_steps(None), None);
+        assert_eq!(
+            BlockId::Bedrock.get_break_steps(Some(ItemId::StonePickaxe)),
+            None
+        );
+    }
+
+    #[test]
+    fn tools_only_speed_up_their_blocks() {
+        let by_hand = BlockId::Stone.get_break_steps(None).unwrap();
+        let pickaxe = BlockId::Stone.get_break_steps(Some(ItemId::WoodenPickaxe));
+        let axe = BlockId::Stone.get_break_steps(Some(ItemId::WoodenAxe));
+
+        assert!(pickaxe.unwrap() < by_hand);
+        assert_eq!(axe, Some(by_hand));
+        assert!(BlockId::Stone.is_effective_tool(Some(ItemId::WoodenPickaxe)));
+        assert!(!BlockId::Stone.is_effective_tool(Some(ItemId::WoodenAxe)));
+        assert!(!BlockId::Stone.is_effective_tool(None));
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Weather of the whole world, driven by the server
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    /// Heavier rain
+    Thunder,
+}
+
+impl Weather {
+    pub fn has_precipitation(&self) -> bool {
+        *self != Self::Clear
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "clear" => Some(Self::Clear),
+            "rain" => Some(Self::Rain),
+            "thunder" => Some(Self::Thunder),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}